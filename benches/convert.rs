@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use goth_gltf::primitive_reader::{read_f32x4, read_floats};
+use goth_gltf::{Accessor, AccessorType, ComponentType};
+
+const COUNT: usize = 1_000_000;
+
+fn accessor(component_type: ComponentType) -> Accessor<()> {
+    let mut accessor = Accessor::new(0, component_type, AccessorType::Vec4, COUNT);
+    accessor.normalized = true;
+    accessor
+}
+
+fn convert(c: &mut Criterion) {
+    let bytes: Vec<u8> = (0..COUNT * 4 * 2).map(|i| (i * 7) as u8).collect();
+
+    for (name, component_type, size) in [
+        ("u8", ComponentType::UnsignedByte, 1),
+        ("u16", ComponentType::UnsignedShort, 2),
+        ("i16", ComponentType::Short, 2),
+    ] {
+        let accessor = accessor(component_type);
+        let slice = &bytes[..COUNT * 4 * size];
+
+        c.bench_function(&format!("read_f32x4 normalized {}", name), |b| {
+            b.iter(|| read_f32x4(black_box(slice), None, &accessor))
+        });
+        c.bench_function(&format!("read_floats normalized {}", name), |b| {
+            b.iter(|| read_floats(black_box(slice), None, &accessor))
+        });
+    }
+}
+
+criterion_group!(benches, convert);
+criterion_main!(benches);
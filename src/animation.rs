@@ -0,0 +1,578 @@
+#[cfg(feature = "primitive_reader")]
+use crate::Channel;
+#[cfg(feature = "primitive_reader")]
+use crate::{buffers::BufferViewData, primitive_reader, Gltf};
+use crate::{
+    math, Animation, AnimationSampler, Extensions, Interpolation, NodeTransform, TargetPath,
+};
+
+/// Decoded keyframe data for a single animation sampler.
+///
+/// `values` is laid out as the spec describes for the sampler output accessor: one element per
+/// keyframe, or an `(in-tangent, value, out-tangent)` triplet per keyframe for cubic splines.
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframes<'a> {
+    pub interpolation: Interpolation,
+    pub path: TargetPath,
+    pub times: &'a [f32],
+    pub values: &'a [f32],
+}
+
+/// Keyframes resampled onto a uniform rate, by [`Keyframes::resample`]. There's one value per
+/// frame, even for cubic splines.
+#[derive(Debug, Clone, Default)]
+pub struct BakedKeyframes {
+    pub times: Vec<f32>,
+    pub values: Vec<f32>,
+}
+
+/// The most frames that [`Keyframes::resample`] produces, which is over 9 hours at 30 frames
+/// per second.
+pub const MAX_BAKED_FRAMES: usize = 1 << 20;
+
+/// Why keyframes couldn't be resampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResampleError {
+    /// The frame rate isn't positive and finite.
+    InvalidRate(f32),
+    /// The first or last keyframe time isn't finite, or the last is before the first.
+    InvalidTimes { start: f32, end: f32 },
+    /// The keyframes span more than [`MAX_BAKED_FRAMES`] frames at the given rate.
+    TooManyFrames,
+}
+
+impl core::fmt::Display for ResampleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::InvalidRate(rate) => write!(f, "{} frames per second isn't a valid rate", rate),
+            Self::InvalidTimes { start, end } => write!(
+                f,
+                "keyframe times from {} to {} can't be resampled",
+                start, end
+            ),
+            Self::TooManyFrames => write!(
+                f,
+                "resampling would produce more than {} frames",
+                MAX_BAKED_FRAMES
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ResampleError {}
+
+impl<'a> Keyframes<'a> {
+    /// The number of floats in a single value, e.g. 3 for translations or the number of
+    /// morph targets for weights.
+    pub fn components(&self) -> usize {
+        if self.times.is_empty() {
+            return 0;
+        }
+
+        let elements_per_key = match self.interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+
+        self.values.len() / (self.times.len() * elements_per_key)
+    }
+
+    fn value(&self, key: usize) -> &'a [f32] {
+        let components = self.components();
+
+        match self.interpolation {
+            Interpolation::CubicSpline => {
+                &self.values[(key * 3 + 1) * components..(key * 3 + 2) * components]
+            }
+            _ => &self.values[key * components..(key + 1) * components],
+        }
+    }
+
+    fn in_tangent(&self, key: usize) -> &'a [f32] {
+        let components = self.components();
+        &self.values[key * 3 * components..(key * 3 + 1) * components]
+    }
+
+    fn out_tangent(&self, key: usize) -> &'a [f32] {
+        let components = self.components();
+        &self.values[(key * 3 + 2) * components..(key * 3 + 3) * components]
+    }
+
+    /// Evaluate the keyframes at `time`, writing `components()` floats into `output`.
+    ///
+    /// Times outside of the keyframe range are clamped to the first or last value.
+    pub fn sample(&self, time: f32, output: &mut [f32]) {
+        let components = self.components();
+
+        if components == 0 {
+            return;
+        }
+
+        let output = &mut output[..components];
+        let last = self.times.len() - 1;
+
+        // A NaN time isn't caught by the comparisons below.
+        if !time.is_finite() {
+            let key = if time == f32::INFINITY { last } else { 0 };
+            output.copy_from_slice(self.value(key));
+            return;
+        }
+
+        if time <= self.times[0] {
+            output.copy_from_slice(self.value(0));
+            return;
+        }
+
+        if time >= self.times[last] {
+            output.copy_from_slice(self.value(last));
+            return;
+        }
+
+        // The index of the keyframe before `time`. The times come from the file, so they can be
+        // NaN or out of order, in which case the search can land on either end.
+        let key = self
+            .times
+            .partition_point(|&key_time| key_time <= time)
+            .saturating_sub(1)
+            .min(last);
+        let next = (key + 1).min(last);
+        let delta = self.times[next] - self.times[key];
+        let t = if delta > 0.0 {
+            (time - self.times[key]) / delta
+        } else {
+            0.0
+        };
+
+        let is_rotation = self.path == TargetPath::Rotation && components == 4;
+
+        match self.interpolation {
+            Interpolation::Step => output.copy_from_slice(self.value(key)),
+            // Unknown interpolations fall back to linear, the spec default.
+            Interpolation::Linear | Interpolation::Other(_) if is_rotation => {
+                let a = <[f32; 4]>::try_from(self.value(key)).unwrap();
+                let b = <[f32; 4]>::try_from(self.value(next)).unwrap();
+                output.copy_from_slice(&math::slerp(a, b, t));
+            }
+            Interpolation::Linear | Interpolation::Other(_) => {
+                let a = self.value(key);
+                let b = self.value(next);
+
+                for i in 0..components {
+                    output[i] = a[i] + (b[i] - a[i]) * t;
+                }
+            }
+            Interpolation::CubicSpline => {
+                let t2 = t * t;
+                let t3 = t2 * t;
+
+                let value_a = self.value(key);
+                let out_tangent_a = self.out_tangent(key);
+                let value_b = self.value(next);
+                let in_tangent_b = self.in_tangent(next);
+
+                for i in 0..components {
+                    output[i] = (2.0 * t3 - 3.0 * t2 + 1.0) * value_a[i]
+                        + delta * (t3 - 2.0 * t2 + t) * out_tangent_a[i]
+                        + (-2.0 * t3 + 3.0 * t2) * value_b[i]
+                        + delta * (t3 - t2) * in_tangent_b[i];
+                }
+
+                if is_rotation {
+                    let rotation = <[f32; 4]>::try_from(&output[..]).unwrap();
+                    output.copy_from_slice(&math::normalize4(rotation));
+                }
+            }
+        }
+    }
+
+    /// Evaluate the keyframes at a uniform `frames_per_second` rate between the first and last
+    /// keyframe times, with the keyframes' own interpolation. The last keyframe time is always
+    /// included.
+    pub fn resample(&self, frames_per_second: f32) -> Result<BakedKeyframes, ResampleError> {
+        if !(frames_per_second.is_finite() && frames_per_second > 0.0) {
+            return Err(ResampleError::InvalidRate(frames_per_second));
+        }
+
+        let components = self.components();
+
+        if components == 0 {
+            return Ok(BakedKeyframes::default());
+        }
+
+        let start = self.times[0];
+        let end = self.times[self.times.len() - 1];
+
+        if !(start.is_finite() && end.is_finite() && start <= end) {
+            return Err(ResampleError::InvalidTimes { start, end });
+        }
+
+        // In `f64` so that large spans don't overflow to infinity.
+        let frames = ((end as f64 - start as f64) * frames_per_second as f64).ceil();
+
+        if frames >= MAX_BAKED_FRAMES as f64 {
+            return Err(ResampleError::TooManyFrames);
+        }
+
+        let num_frames = frames as usize + 1;
+        let num_values = num_frames
+            .checked_mul(components)
+            .ok_or(ResampleError::TooManyFrames)?;
+
+        let mut baked = BakedKeyframes {
+            times: Vec::with_capacity(num_frames),
+            values: vec![0.0; num_values],
+        };
+
+        for (frame, output) in baked.values.chunks_exact_mut(components).enumerate() {
+            let time = (start + frame as f32 / frames_per_second).min(end);
+            self.sample(time, output);
+            baked.times.push(time);
+        }
+
+        Ok(baked)
+    }
+}
+
+/// The samplers animating a single node's properties.
+#[derive(Debug)]
+pub struct NodeTracks<'a, E: Extensions> {
+    pub node: usize,
+    pub translation: Option<&'a AnimationSampler<E>>,
+    pub rotation: Option<&'a AnimationSampler<E>>,
+    pub scale: Option<&'a AnimationSampler<E>>,
+    pub weights: Option<&'a AnimationSampler<E>>,
+}
+
+// Implemented manually as deriving would require `E: Copy`.
+impl<'a, E: Extensions> Clone for NodeTracks<'a, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E: Extensions> Copy for NodeTracks<'a, E> {}
+
+/// An animation's channels grouped by the node they target.
+#[derive(Debug)]
+pub struct AnimationClip<'a, E: Extensions> {
+    /// One entry per animated node, in the order each node is first targeted.
+    pub nodes: Vec<NodeTracks<'a, E>>,
+}
+
+impl<'a, E: Extensions> Clone for AnimationClip<'a, E> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+        }
+    }
+}
+
+impl<'a, E: Extensions> Default for AnimationClip<'a, E> {
+    fn default() -> Self {
+        Self { nodes: Vec::new() }
+    }
+}
+
+impl<'a, E: Extensions> AnimationClip<'a, E> {
+    pub fn node(&self, node: usize) -> Option<&NodeTracks<'a, E>> {
+        self.nodes.iter().find(|tracks| tracks.node == node)
+    }
+
+    /// The samplers of every track in the clip, e.g. for finding the clip's duration from the
+    /// sampler input accessors. A sampler shared between tracks is returned once per track.
+    pub fn samplers(&self) -> impl Iterator<Item = &'a AnimationSampler<E>> + '_ {
+        self.nodes.iter().flat_map(|tracks| {
+            [
+                tracks.translation,
+                tracks.rotation,
+                tracks.scale,
+                tracks.weights,
+            ]
+            .into_iter()
+            .flatten()
+        })
+    }
+}
+
+impl<E: Extensions> Animation<E> {
+    /// Group the channels by target node.
+    ///
+    /// Channels without a target node, such as `KHR_animation_pointer` channels, or with an
+    /// out-of-range sampler are skipped. If several
+    /// channels target the same property of a node, the last one wins.
+    pub fn clip(&self) -> AnimationClip<'_, E> {
+        let mut clip = AnimationClip::default();
+
+        for channel in &self.channels {
+            let (node, sampler) = match (channel.target.node, self.samplers.get(channel.sampler)) {
+                (Some(node), Some(sampler)) => (node, sampler),
+                _ => continue,
+            };
+
+            let index = match clip.nodes.iter().position(|tracks| tracks.node == node) {
+                Some(index) => index,
+                None => {
+                    clip.nodes.push(NodeTracks {
+                        node,
+                        translation: None,
+                        rotation: None,
+                        scale: None,
+                        weights: None,
+                    });
+                    clip.nodes.len() - 1
+                }
+            };
+
+            let tracks = &mut clip.nodes[index];
+
+            let track = match channel.target.path {
+                TargetPath::Translation => &mut tracks.translation,
+                TargetPath::Rotation => &mut tracks.rotation,
+                TargetPath::Scale => &mut tracks.scale,
+                TargetPath::Weights => &mut tracks.weights,
+                TargetPath::Pointer => continue,
+            };
+
+            *track = Some(sampler);
+        }
+
+        clip
+    }
+}
+
+/// The animated state of a node at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePose {
+    pub node: usize,
+    /// The node's transform, with any properties that aren't animated taken from the node.
+    pub transform: NodeTransform,
+    /// The node's morph target weights, if they're animated.
+    pub weights: Option<Vec<f32>>,
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<E: Extensions> Animation<E> {
+    /// Evaluate the animation at `time`, returning the pose of every targeted node.
+    ///
+    /// Properties of a node that aren't animated fall back to the node's rest values. Channels
+    /// that target a node that doesn't exist are skipped.
+    pub fn pose_at(
+        &self,
+        gltf: &Gltf<E>,
+        buffer_view_map: &dyn BufferViewData,
+        time: f32,
+    ) -> Result<Vec<NodePose>, primitive_reader::Error>
+    where
+        E::BufferViewExtensions: primitive_reader::MeshOptCompressionExtension,
+    {
+        let read = |accessor: usize| {
+            let accessor = gltf
+                .accessors
+                .get(accessor)
+                .ok_or(primitive_reader::Error::AccessorIndexOutOfBounds(accessor))?;
+            let (slice, byte_stride) =
+                primitive_reader::read_buffer_with_accessor(buffer_view_map, gltf, accessor)?;
+            Ok(primitive_reader::read_floats(&slice, byte_stride, accessor))
+        };
+
+        let sample = |sampler: &AnimationSampler<E>,
+                      path: TargetPath|
+         -> Result<Vec<f32>, primitive_reader::Error> {
+            let times = read(sampler.input)?;
+            let values = read(sampler.output)?;
+            let keyframes = Keyframes {
+                interpolation: sampler.interpolation,
+                path,
+                times: &times,
+                values: &values,
+            };
+            let mut output = vec![0.0; keyframes.components()];
+            keyframes.sample(time, &mut output);
+            Ok(output)
+        };
+
+        let mut poses = Vec::new();
+
+        for tracks in self.clip().nodes {
+            let node = match gltf.nodes.get(tracks.node) {
+                Some(node) => node,
+                None => continue,
+            };
+
+            let (mut translation, mut rotation, mut scale) = node.transform().to_trs();
+
+            if let Some(sampler) = tracks.translation {
+                if let Ok(value) = <[f32; 3]>::try_from(sample(sampler, TargetPath::Translation)?) {
+                    translation = value;
+                }
+            }
+
+            if let Some(sampler) = tracks.rotation {
+                if let Ok(value) = <[f32; 4]>::try_from(sample(sampler, TargetPath::Rotation)?) {
+                    rotation = value;
+                }
+            }
+
+            if let Some(sampler) = tracks.scale {
+                if let Ok(value) = <[f32; 3]>::try_from(sample(sampler, TargetPath::Scale)?) {
+                    scale = value;
+                }
+            }
+
+            let weights = match tracks.weights {
+                Some(sampler) => Some(sample(sampler, TargetPath::Weights)?),
+                None => None,
+            };
+
+            poses.push(NodePose {
+                node: tracks.node,
+                transform: NodeTransform::Set {
+                    translation,
+                    rotation,
+                    scale,
+                },
+                weights,
+            });
+        }
+
+        Ok(poses)
+    }
+}
+
+/// The decoded output values of an animation channel.
+///
+/// For cubic splines there are three values per keyframe, as described on [`Keyframes`].
+#[cfg(feature = "primitive_reader")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelOutputs {
+    Translations(Vec<[f32; 3]>),
+    /// `[x, y, z, w]` quaternions, with quantized values already normalized.
+    Rotations(Vec<[f32; 4]>),
+    Scales(Vec<[f32; 3]>),
+    /// Morph target weights, with the weights of each keyframe stored consecutively.
+    Weights(Vec<f32>),
+    /// Every component of a `KHR_animation_pointer` target's values.
+    Pointer(Vec<f32>),
+}
+
+/// Reads the keyframe data of an animation's channels.
+#[cfg(feature = "primitive_reader")]
+pub struct AnimationReader<'a, E: Extensions> {
+    gltf: &'a Gltf<E>,
+    pub animation: &'a Animation<E>,
+    buffer_view_map: &'a dyn BufferViewData,
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<'a, E: Extensions> AnimationReader<'a, E>
+where
+    E::BufferViewExtensions: primitive_reader::MeshOptCompressionExtension,
+{
+    pub fn new(
+        gltf: &'a Gltf<E>,
+        animation: &'a Animation<E>,
+        buffer_view_map: &'a dyn BufferViewData,
+    ) -> Self {
+        Self {
+            gltf,
+            animation,
+            buffer_view_map,
+        }
+    }
+
+    fn sampler(&self, channel: usize) -> Result<&'a AnimationSampler<E>, primitive_reader::Error> {
+        let channel = self
+            .animation
+            .channels
+            .get(channel)
+            .ok_or(primitive_reader::Error::ChannelIndexOutOfBounds(channel))?;
+
+        self.channel_sampler(channel)
+    }
+
+    fn channel_sampler(
+        &self,
+        channel: &Channel<E>,
+    ) -> Result<&'a AnimationSampler<E>, primitive_reader::Error> {
+        self.animation.samplers.get(channel.sampler).ok_or(
+            primitive_reader::Error::SamplerIndexOutOfBounds(channel.sampler),
+        )
+    }
+
+    fn accessor(&self, accessor: usize) -> Result<&'a crate::Accessor<E>, primitive_reader::Error> {
+        self.gltf
+            .accessors
+            .get(accessor)
+            .ok_or(primitive_reader::Error::AccessorIndexOutOfBounds(accessor))
+    }
+
+    fn read_floats(&self, accessor: usize) -> Result<Vec<f32>, primitive_reader::Error> {
+        let accessor = self.accessor(accessor)?;
+        let (slice, byte_stride) =
+            primitive_reader::read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
+        Ok(primitive_reader::read_floats(&slice, byte_stride, accessor))
+    }
+
+    /// Read the keyframe times of a channel's sampler, in seconds.
+    pub fn read_inputs(&self, channel: usize) -> Result<Vec<f32>, primitive_reader::Error> {
+        let accessor = self.accessor(self.sampler(channel)?.input)?;
+        primitive_reader::read_accessor(self.buffer_view_map, self.gltf, accessor)
+    }
+
+    /// Read the output values of a channel's sampler, typed by the channel's target path.
+    pub fn read_outputs(&self, channel: usize) -> Result<ChannelOutputs, primitive_reader::Error> {
+        let sampler = self.sampler(channel)?;
+        let accessor = self.accessor(sampler.output)?;
+        let path = self.animation.channels[channel].target.path;
+
+        Ok(match path {
+            TargetPath::Translation => ChannelOutputs::Translations(
+                primitive_reader::read_accessor(self.buffer_view_map, self.gltf, accessor)?,
+            ),
+            TargetPath::Rotation => ChannelOutputs::Rotations(primitive_reader::read_accessor(
+                self.buffer_view_map,
+                self.gltf,
+                accessor,
+            )?),
+            TargetPath::Scale => ChannelOutputs::Scales(primitive_reader::read_accessor(
+                self.buffer_view_map,
+                self.gltf,
+                accessor,
+            )?),
+            TargetPath::Weights => ChannelOutputs::Weights(self.read_floats(sampler.output)?),
+            TargetPath::Pointer => ChannelOutputs::Pointer(self.read_floats(sampler.output)?),
+        })
+    }
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<E: Extensions> Channel<E> {
+    /// Evaluate the channel at `time` with its sampler's interpolation, returning the animated
+    /// value, e.g. 4 floats for a rotation or one per morph target for weights.
+    ///
+    /// `reader` needs to be for the animation that this channel belongs to. Times outside of the
+    /// keyframe range are clamped, as with [`Keyframes::sample`].
+    pub fn sample(
+        &self,
+        reader: &AnimationReader<E>,
+        time: f32,
+    ) -> Result<Vec<f32>, primitive_reader::Error>
+    where
+        E::BufferViewExtensions: primitive_reader::MeshOptCompressionExtension,
+    {
+        let sampler = reader.channel_sampler(self)?;
+        let times = reader.read_floats(sampler.input)?;
+        let values = reader.read_floats(sampler.output)?;
+
+        let keyframes = Keyframes {
+            interpolation: sampler.interpolation,
+            path: self.target.path,
+            times: &times,
+            values: &values,
+        };
+
+        let mut output = vec![0.0; keyframes.components()];
+        keyframes.sample(time, &mut output);
+        Ok(output)
+    }
+}
@@ -0,0 +1,225 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{Extensions, Target, TargetPath};
+
+pub trait AnimationPointerExtension {
+    fn khr_animation_pointer(&self) -> Option<&str>;
+}
+
+#[cfg(feature = "khr_animation_pointer")]
+impl AnimationPointerExtension for crate::default_extensions::TargetExtensions {
+    fn khr_animation_pointer(&self) -> Option<&str> {
+        self.khr_animation_pointer
+            .as_ref()
+            .map(|extension| extension.pointer.as_str())
+    }
+}
+
+impl AnimationPointerExtension for () {
+    fn khr_animation_pointer(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<E: Extensions> Target<E>
+where
+    E::TargetExtensions: AnimationPointerExtension,
+{
+    /// The resolved `KHR_animation_pointer` target, if the path is `pointer` and the pointer is
+    /// valid.
+    pub fn pointer_target(&self) -> Option<PointerTarget> {
+        match self.path {
+            TargetPath::Pointer => PointerTarget::parse(self.extensions.khr_animation_pointer()?),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of object a `KHR_animation_pointer` pointer targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Node,
+    Mesh,
+    Material,
+    Camera,
+    /// A `KHR_lights_punctual` light.
+    Light,
+}
+
+/// A material texture slot, for texture transform and scale/strength properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialTexture {
+    BaseColor,
+    MetallicRoughness,
+    Normal,
+    Occlusion,
+    Emissive,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Property {
+    Translation,
+    Rotation,
+    Scale,
+    /// Morph target weights, on either a node or a mesh.
+    Weights,
+    BaseColorFactor,
+    MetallicFactor,
+    RoughnessFactor,
+    EmissiveFactor,
+    AlphaCutoff,
+    NormalTextureScale,
+    OcclusionTextureStrength,
+    TextureTransformOffset(MaterialTexture),
+    TextureTransformRotation(MaterialTexture),
+    TextureTransformScale(MaterialTexture),
+    PerspectiveYfov,
+    PerspectiveAspectRatio,
+    PerspectiveZnear,
+    PerspectiveZfar,
+    OrthographicXmag,
+    OrthographicYmag,
+    OrthographicZnear,
+    OrthographicZfar,
+    LightColor,
+    LightIntensity,
+    LightRange,
+    LightSpotInnerConeAngle,
+    LightSpotOuterConeAngle,
+    /// Any other property, such as one defined by a material extension, as the remaining
+    /// pointer path after the object index (e.g. `extensions/KHR_materials_ior/ior`).
+    Other(String),
+}
+
+impl Property {
+    /// The number of floats the animated value has, if known. `None` for weights, which depend
+    /// on the number of morph targets, and for `Other` properties.
+    pub fn components(&self) -> Option<usize> {
+        match self {
+            Self::Weights | Self::Other(_) => None,
+            Self::Rotation | Self::BaseColorFactor => Some(4),
+            Self::Translation | Self::Scale | Self::EmissiveFactor | Self::LightColor => Some(3),
+            Self::TextureTransformOffset(_) | Self::TextureTransformScale(_) => Some(2),
+            _ => Some(1),
+        }
+    }
+}
+
+/// A resolved `KHR_animation_pointer` target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerTarget {
+    pub object: ObjectKind,
+    pub index: usize,
+    pub property: Property,
+}
+
+impl PointerTarget {
+    /// Resolve a JSON pointer such as `/materials/2/pbrMetallicRoughness/baseColorFactor`.
+    ///
+    /// Returns `None` if the pointer is malformed or doesn't point into an animatable object.
+    pub fn parse(pointer: &str) -> Option<Self> {
+        let tokens: Vec<String> = pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        let tokens: Vec<&str> = tokens.iter().map(|token| token.as_str()).collect();
+
+        let (object, index, path) = match tokens.as_slice() {
+            ["extensions", "KHR_lights_punctual", "lights", index, path @ ..] => {
+                (ObjectKind::Light, index, path)
+            }
+            [collection, index, path @ ..] => {
+                let object = match *collection {
+                    "nodes" => ObjectKind::Node,
+                    "meshes" => ObjectKind::Mesh,
+                    "materials" => ObjectKind::Material,
+                    "cameras" => ObjectKind::Camera,
+                    _ => return None,
+                };
+                (object, index, path)
+            }
+            _ => return None,
+        };
+
+        let index = index.parse().ok()?;
+
+        if path.is_empty() {
+            return None;
+        }
+
+        let property = match (object, path) {
+            (ObjectKind::Node, ["translation"]) => Property::Translation,
+            (ObjectKind::Node, ["rotation"]) => Property::Rotation,
+            (ObjectKind::Node, ["scale"]) => Property::Scale,
+            (ObjectKind::Node | ObjectKind::Mesh, ["weights"]) => Property::Weights,
+            (ObjectKind::Material, ["pbrMetallicRoughness", "baseColorFactor"]) => {
+                Property::BaseColorFactor
+            }
+            (ObjectKind::Material, ["pbrMetallicRoughness", "metallicFactor"]) => {
+                Property::MetallicFactor
+            }
+            (ObjectKind::Material, ["pbrMetallicRoughness", "roughnessFactor"]) => {
+                Property::RoughnessFactor
+            }
+            (ObjectKind::Material, ["emissiveFactor"]) => Property::EmissiveFactor,
+            (ObjectKind::Material, ["alphaCutoff"]) => Property::AlphaCutoff,
+            (ObjectKind::Material, ["normalTexture", "scale"]) => Property::NormalTextureScale,
+            (ObjectKind::Material, ["occlusionTexture", "strength"]) => {
+                Property::OcclusionTextureStrength
+            }
+            (
+                ObjectKind::Material,
+                [texture @ .., "extensions", "KHR_texture_transform", field],
+            ) => {
+                let texture = match texture {
+                    ["pbrMetallicRoughness", "baseColorTexture"] => MaterialTexture::BaseColor,
+                    ["pbrMetallicRoughness", "metallicRoughnessTexture"] => {
+                        MaterialTexture::MetallicRoughness
+                    }
+                    ["normalTexture"] => MaterialTexture::Normal,
+                    ["occlusionTexture"] => MaterialTexture::Occlusion,
+                    ["emissiveTexture"] => MaterialTexture::Emissive,
+                    _ => return Some(Self::other(object, index, path)),
+                };
+
+                match *field {
+                    "offset" => Property::TextureTransformOffset(texture),
+                    "rotation" => Property::TextureTransformRotation(texture),
+                    "scale" => Property::TextureTransformScale(texture),
+                    _ => return None,
+                }
+            }
+            (ObjectKind::Camera, ["perspective", "yfov"]) => Property::PerspectiveYfov,
+            (ObjectKind::Camera, ["perspective", "aspectRatio"]) => {
+                Property::PerspectiveAspectRatio
+            }
+            (ObjectKind::Camera, ["perspective", "znear"]) => Property::PerspectiveZnear,
+            (ObjectKind::Camera, ["perspective", "zfar"]) => Property::PerspectiveZfar,
+            (ObjectKind::Camera, ["orthographic", "xmag"]) => Property::OrthographicXmag,
+            (ObjectKind::Camera, ["orthographic", "ymag"]) => Property::OrthographicYmag,
+            (ObjectKind::Camera, ["orthographic", "znear"]) => Property::OrthographicZnear,
+            (ObjectKind::Camera, ["orthographic", "zfar"]) => Property::OrthographicZfar,
+            (ObjectKind::Light, ["color"]) => Property::LightColor,
+            (ObjectKind::Light, ["intensity"]) => Property::LightIntensity,
+            (ObjectKind::Light, ["range"]) => Property::LightRange,
+            (ObjectKind::Light, ["spot", "innerConeAngle"]) => Property::LightSpotInnerConeAngle,
+            (ObjectKind::Light, ["spot", "outerConeAngle"]) => Property::LightSpotOuterConeAngle,
+            _ => return Some(Self::other(object, index, path)),
+        };
+
+        Some(Self {
+            object,
+            index,
+            property,
+        })
+    }
+
+    fn other(object: ObjectKind, index: usize, path: &[&str]) -> Self {
+        Self {
+            object,
+            index,
+            property: Property::Other(path.join("/")),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::buffers::BufferViewData;
+use crate::geometry::{sequential_indices, to_list_indices};
+use crate::primitive_reader::{
+    AccessorCache, DracoCompressionExtension, DracoDecoder, Error, MeshOptCompressionExtension,
+    PrimitiveReader,
+};
+use crate::{Extensions, Gltf, PrimitiveMode};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// The part of a [`Batch`] that came from a single primitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawRange {
+    pub mesh: usize,
+    pub primitive: usize,
+    /// The range of [`Batch::indices`] that draws the primitive.
+    pub indices: Range<usize>,
+    /// The range of vertices that the primitive added.
+    pub vertices: Range<usize>,
+}
+
+/// The merged vertex data of every triangle primitive that uses the same material.
+///
+/// Attributes that a primitive doesn't have are filled in with defaults: zero normals and
+/// texture coordinates, `[1, 0, 0, 1]` tangents and white colors.
+#[derive(Debug, Clone, Default)]
+pub struct Batch {
+    /// `None` for primitives that use the default material.
+    pub material: Option<usize>,
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub tangents: Vec<[f32; 4]>,
+    pub uvs: Vec<[f32; 2]>,
+    pub colors: Vec<[f32; 4]>,
+    /// Triangle list indices into the batch's vertices.
+    pub indices: Vec<u32>,
+    pub draws: Vec<DrawRange>,
+}
+
+/// Read the primitives of `meshes` and merge them into a batch per material, ordered by material
+/// index. Pass `0..gltf.meshes.len()` to batch the whole document.
+///
+/// Strips and fans are converted into lists, and primitives that don't draw triangles are skipped.
+/// Vertices aren't transformed, so this is most useful for meshes that are only instanced once or
+/// that are already in world space. Accessors shared between primitives are only decoded once.
+pub fn batch_by_material<E: Extensions>(
+    gltf: &Gltf<E>,
+    buffer_view_map: &dyn BufferViewData,
+    meshes: impl IntoIterator<Item = usize>,
+    draco_decoder: Option<&dyn DracoDecoder>,
+) -> Result<Vec<Batch>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+    E::PrimitiveExtensions: DracoCompressionExtension,
+{
+    let cache = AccessorCache::new(gltf);
+    let mut batches: BTreeMap<Option<usize>, Batch> = BTreeMap::new();
+
+    for mesh_index in meshes {
+        let mesh = match gltf.meshes.get(mesh_index) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+
+        for (primitive_index, primitive) in mesh.primitives.iter().enumerate() {
+            if primitive.mode.list_mode() != PrimitiveMode::Triangles {
+                continue;
+            }
+
+            let mut reader =
+                PrimitiveReader::new(gltf, primitive, buffer_view_map).with_cache(&cache);
+
+            if let Some(decoder) = draco_decoder {
+                reader = reader.with_draco_decoder(decoder);
+            }
+
+            let data = reader.read_all()?;
+            let vertex_count = data.vertex_count();
+
+            let indices = match data.indices {
+                Some(indices) => indices.into_u32().into_owned(),
+                None => sequential_indices(vertex_count),
+            };
+
+            let batch = batches.entry(primitive.material).or_insert_with(|| Batch {
+                material: primitive.material,
+                ..Default::default()
+            });
+
+            let base_vertex = batch.positions.len();
+            let first_index = batch.indices.len();
+
+            batch.positions.extend_from_slice(&data.positions);
+            batch
+                .normals
+                .extend(fill(data.normals.as_deref(), vertex_count, [0.0; 3]));
+            batch.tangents.extend(fill(
+                data.tangents.as_deref(),
+                vertex_count,
+                [1.0, 0.0, 0.0, 1.0],
+            ));
+            batch
+                .uvs
+                .extend(fill(data.uvs.as_deref(), vertex_count, [0.0; 2]));
+            batch
+                .colors
+                .extend(fill(data.colors.as_deref(), vertex_count, [1.0; 4]));
+            batch.indices.extend(
+                to_list_indices(primitive.mode, &indices)
+                    .into_iter()
+                    .map(|index| index + base_vertex as u32),
+            );
+
+            batch.draws.push(DrawRange {
+                mesh: mesh_index,
+                primitive: primitive_index,
+                indices: first_index..batch.indices.len(),
+                vertices: base_vertex..batch.positions.len(),
+            });
+        }
+    }
+
+    Ok(batches.into_values().collect())
+}
+
+fn fill<T: Copy>(values: Option<&[T]>, count: usize, default: T) -> impl Iterator<Item = T> + '_ {
+    values
+        .unwrap_or_default()
+        .iter()
+        .copied()
+        .chain(std::iter::repeat(default))
+        .take(count)
+}
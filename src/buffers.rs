@@ -0,0 +1,422 @@
+use crate::extensions::ExtMeshoptCompression;
+use crate::primitive_reader::MeshOptCompressionExtension;
+use crate::{Extensions, Gltf};
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use thiserror::Error;
+
+type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+type Loader<'a> = Box<dyn FnMut(&str) -> Result<Vec<u8>, BoxedError> + 'a>;
+
+pub trait MeshOptFallbackExtension {
+    /// Whether the buffer is an `EXT_meshopt_compression` fallback buffer, which may not have
+    /// any data.
+    fn ext_meshopt_fallback(&self) -> bool;
+}
+
+impl MeshOptFallbackExtension for crate::default_extensions::BufferExtensions {
+    fn ext_meshopt_fallback(&self) -> bool {
+        self.ext_meshopt_compression
+            .is_some_and(|extension| extension.fallback)
+    }
+}
+
+impl MeshOptFallbackExtension for () {
+    fn ext_meshopt_fallback(&self) -> bool {
+        false
+    }
+}
+
+/// Provides the bytes of each buffer view to the readers.
+///
+/// This is implemented for the map that [`BufferViewMapBuilder`] builds, and for
+/// [`BorrowedBufferViews`], which slices buffers that are already in memory.
+pub trait BufferViewData {
+    fn buffer_view_bytes(&self, buffer_view: usize) -> Option<&[u8]>;
+}
+
+impl BufferViewData for HashMap<usize, Vec<u8>> {
+    fn buffer_view_bytes(&self, buffer_view: usize) -> Option<&[u8]> {
+        self.get(&buffer_view).map(|bytes| bytes.as_slice())
+    }
+}
+
+/// Buffer view data borrowed straight from buffers that are already in memory, such as the binary
+/// chunk of a .glb file, without copying anything into a map.
+///
+/// `buffer_bytes` returns the bytes of a buffer by index. `EXT_meshopt_compression` buffer views
+/// can't be decoded this way, so they aren't available.
+pub struct BorrowedBufferViews<'a, E: Extensions, F> {
+    gltf: &'a Gltf<E>,
+    buffer_bytes: F,
+}
+
+impl<'a, E: Extensions, F: Fn(usize) -> Option<&'a [u8]>> BorrowedBufferViews<'a, E, F> {
+    pub fn new(gltf: &'a Gltf<E>, buffer_bytes: F) -> Self {
+        Self { gltf, buffer_bytes }
+    }
+}
+
+impl<'a, E: Extensions, F: Fn(usize) -> Option<&'a [u8]>> BufferViewData
+    for BorrowedBufferViews<'a, E, F>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    fn buffer_view_bytes(&self, buffer_view: usize) -> Option<&[u8]> {
+        let buffer_view = self.gltf.buffer_views.get(buffer_view)?;
+
+        if buffer_view.extensions.ext_meshopt_compression().is_some() {
+            return None;
+        }
+
+        (self.buffer_bytes)(buffer_view.buffer)?
+            .get(buffer_view.byte_offset..buffer_view.byte_offset + buffer_view.byte_length)
+    }
+}
+
+/// Fetches the files that uris point to, for loading without blocking.
+///
+/// This is runtime-agnostic, so it can be implemented with `fetch` on wasm or with tokio's
+/// filesystem or an HTTP client elsewhere.
+pub trait BufferSource {
+    fn fetch(&self, uri: &str) -> impl std::future::Future<Output = Result<Vec<u8>, BoxedError>>;
+}
+
+/// Decodes `EXT_meshopt_compression` buffer views.
+///
+/// As with [`DracoDecoder`](crate::primitive_reader::DracoDecoder), there's no decoder bundled
+/// with this crate. The `meshopt` crate provides the decoding functions needed to implement one.
+pub trait MeshoptDecoder {
+    /// Decode `extension.count` elements of `extension.byte_stride` bytes each from `bytes`, which
+    /// is the compressed range of the source buffer, applying `extension.filter`.
+    fn decode(
+        &self,
+        bytes: &[u8],
+        extension: &ExtMeshoptCompression,
+    ) -> Result<Vec<u8>, BoxedError>;
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Buffer index {0} out of bounds")]
+    BufferIndexOutOfBounds(usize),
+    #[error("Buffer {0} has no uri and there's no binary chunk for it")]
+    MissingBufferData(usize),
+    #[error("Buffer {0} has an invalid data uri")]
+    InvalidDataUri(usize),
+    #[error("No loader was provided to load {0}")]
+    LoaderMissing(String),
+    #[error("Failed to load {0}: {1}")]
+    Load(String, BoxedError),
+    #[error("Image {0} has no uri or buffer view, or an invalid data uri")]
+    MissingImageData(usize),
+    #[error("Buffer view {0} is out of bounds of its buffer")]
+    BufferViewOutOfBounds(usize),
+    #[error("Buffer view {0} is meshopt-compressed with only a fallback buffer, but no decoder was provided")]
+    MeshoptDecoderMissing(usize),
+    #[error("Failed to decode meshopt data for buffer view {0}: {1}")]
+    MeshoptDecode(usize, BoxedError),
+    #[error("Buffer view {0} is meshopt-compressed, which can't be repacked")]
+    MeshoptRepack(usize),
+    #[error("{0}")]
+    Glb(crate::GlbError),
+}
+
+/// Builds the map from buffer view index to buffer view bytes that
+/// [`PrimitiveReader`](crate::primitive_reader::PrimitiveReader) and the other readers take.
+///
+/// Buffers are only loaded if a buffer view needs them, so fallback buffers are skipped when
+/// their buffer views are decoded with a [`MeshoptDecoder`].
+pub struct BufferViewMapBuilder<'a, E: Extensions> {
+    gltf: &'a Gltf<E>,
+    binary_chunk: Option<&'a [u8]>,
+    loader: Option<Loader<'a>>,
+    meshopt_decoder: Option<&'a dyn MeshoptDecoder>,
+}
+
+impl<'a, E: Extensions> BufferViewMapBuilder<'a, E>
+where
+    E::BufferExtensions: MeshOptFallbackExtension,
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    pub fn new(gltf: &'a Gltf<E>) -> Self {
+        Self {
+            gltf,
+            binary_chunk: None,
+            loader: None,
+            meshopt_decoder: None,
+        }
+    }
+
+    /// Use the binary chunk of a .glb file, as returned by [`Gltf::from_bytes`], for the buffer
+    /// without a uri.
+    pub fn with_binary_chunk(mut self, binary_chunk: Option<&'a [u8]>) -> Self {
+        self.binary_chunk = binary_chunk;
+        self
+    }
+
+    /// Use `loader` to load buffers from their uris. Base64 `data:` uris are decoded without
+    /// going through the loader.
+    pub fn with_loader(
+        mut self,
+        loader: impl FnMut(&str) -> Result<Vec<u8>, BoxedError> + 'a,
+    ) -> Self {
+        self.loader = Some(Box::new(loader));
+        self
+    }
+
+    /// Use `decoder` to decompress `EXT_meshopt_compression` buffer views.
+    pub fn with_meshopt_decoder(mut self, decoder: &'a dyn MeshoptDecoder) -> Self {
+        self.meshopt_decoder = Some(decoder);
+        self
+    }
+
+    // The buffers that `build` reads from, in order of first use.
+    fn required_buffers(&self) -> Vec<usize> {
+        let mut buffers = Vec::new();
+
+        for buffer_view in &self.gltf.buffer_views {
+            let buffer = match (
+                buffer_view.extensions.ext_meshopt_compression(),
+                self.meshopt_decoder,
+            ) {
+                (Some(extension), Some(_)) => extension.buffer,
+                _ => buffer_view.buffer,
+            };
+
+            if !buffers.contains(&buffer) {
+                buffers.push(buffer);
+            }
+        }
+
+        buffers
+    }
+
+    /// Build the map, fetching external buffers from `source` instead of using a loader.
+    ///
+    /// Buffers are fetched one at a time, before any of them are decoded.
+    pub async fn build_async<S: BufferSource>(
+        self,
+        source: &S,
+    ) -> Result<HashMap<usize, Vec<u8>>, Error> {
+        let mut fetched = HashMap::new();
+
+        for index in self.required_buffers() {
+            let uri = match self
+                .gltf
+                .buffers
+                .get(index)
+                .and_then(|buffer| buffer.uri.as_deref())
+            {
+                Some(uri) if !uri.starts_with("data:") => uri,
+                _ => continue,
+            };
+
+            let bytes = source
+                .fetch(uri)
+                .await
+                .map_err(|error| Error::Load(uri.to_string(), error))?;
+
+            fetched.insert(uri, bytes);
+        }
+
+        self.with_loader(move |uri| {
+            fetched
+                .remove(uri)
+                .ok_or_else(|| format!("{} wasn't fetched", uri).into())
+        })
+        .build()
+    }
+
+    pub fn build(mut self) -> Result<HashMap<usize, Vec<u8>>, Error> {
+        let mut buffers = HashMap::new();
+        let mut map = HashMap::with_capacity(self.gltf.buffer_views.len());
+
+        for (index, buffer_view) in self.gltf.buffer_views.iter().enumerate() {
+            let meshopt = buffer_view.extensions.ext_meshopt_compression();
+
+            let bytes = match (meshopt, self.meshopt_decoder) {
+                (Some(extension), Some(decoder)) => {
+                    let buffer = self.load_buffer(&mut buffers, extension.buffer)?;
+                    let compressed = buffer
+                        .get(extension.byte_offset..extension.byte_offset + extension.byte_length)
+                        .ok_or(Error::BufferViewOutOfBounds(index))?;
+
+                    decoder
+                        .decode(compressed, &extension)
+                        .map_err(|error| Error::MeshoptDecode(index, error))?
+                }
+                (meshopt, _) => {
+                    let is_fallback = self
+                        .gltf
+                        .buffers
+                        .get(buffer_view.buffer)
+                        .ok_or(Error::BufferIndexOutOfBounds(buffer_view.buffer))?
+                        .extensions
+                        .ext_meshopt_fallback();
+
+                    if meshopt.is_some() && is_fallback {
+                        return Err(Error::MeshoptDecoderMissing(index));
+                    }
+
+                    let buffer = self.load_buffer(&mut buffers, buffer_view.buffer)?;
+
+                    buffer
+                        .get(
+                            buffer_view.byte_offset
+                                ..buffer_view.byte_offset + buffer_view.byte_length,
+                        )
+                        .ok_or(Error::BufferViewOutOfBounds(index))?
+                        .to_vec()
+                }
+            };
+
+            map.insert(index, bytes);
+        }
+
+        Ok(map)
+    }
+
+    fn load_buffer<'b>(
+        &mut self,
+        buffers: &'b mut HashMap<usize, Cow<'a, [u8]>>,
+        index: usize,
+    ) -> Result<&'b [u8], Error> {
+        if let Entry::Vacant(entry) = buffers.entry(index) {
+            let buffer = self
+                .gltf
+                .buffers
+                .get(index)
+                .ok_or(Error::BufferIndexOutOfBounds(index))?;
+
+            let bytes = match (&buffer.uri, &mut self.loader) {
+                (Some(uri), _) if uri.starts_with("data:") => Cow::Owned(
+                    crate::uri::decode_data_uri(uri)
+                        .ok_or(Error::InvalidDataUri(index))?
+                        .data,
+                ),
+                (Some(uri), Some(loader)) => {
+                    Cow::Owned(loader(uri).map_err(|error| Error::Load(uri.clone(), error))?)
+                }
+                (Some(uri), None) => return Err(Error::LoaderMissing(uri.clone())),
+                (None, _) => match (index, self.binary_chunk) {
+                    (0, Some(binary_chunk)) => Cow::Borrowed(binary_chunk),
+                    _ => return Err(Error::MissingBufferData(index)),
+                },
+            };
+
+            entry.insert(bytes);
+        }
+
+        Ok(&buffers[&index])
+    }
+}
+
+/// Load the encoded data of every image, from `data:` uris, buffer views or `source`.
+pub async fn load_images<E: Extensions, S: BufferSource>(
+    gltf: &Gltf<E>,
+    buffer_view_map: &dyn BufferViewData,
+    source: &S,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut images = Vec::with_capacity(gltf.images.len());
+
+    for (index, image) in gltf.images.iter().enumerate() {
+        let bytes = match (&image.uri, image.buffer_view) {
+            (Some(uri), _) if uri.starts_with("data:") => {
+                crate::uri::decode_data_uri(uri)
+                    .ok_or(Error::MissingImageData(index))?
+                    .data
+            }
+            (Some(uri), _) => source
+                .fetch(uri)
+                .await
+                .map_err(|error| Error::Load(uri.clone(), error))?,
+            (None, Some(buffer_view)) => buffer_view_map
+                .buffer_view_bytes(buffer_view)
+                .ok_or(Error::MissingImageData(index))?
+                .to_vec(),
+            (None, None) => return Err(Error::MissingImageData(index)),
+        };
+
+        images.push(bytes);
+    }
+
+    Ok(images)
+}
+
+/// Merge every buffer into a single new buffer, returning its bytes.
+///
+/// Each buffer view is copied in order, starting on a 4-byte boundary, and its offset is
+/// updated. Bytes that aren't in any buffer view are dropped. The new buffer has no uri, so it's
+/// ready to be the binary chunk of a .glb file; give it a uri to write it out separately.
+///
+/// `buffer_bytes` returns the bytes of a buffer by index, as with [`BorrowedBufferViews`].
+/// `EXT_meshopt_compression` buffer views aren't supported, as their compressed data is
+/// referenced from the extension. The document is left unchanged if there's an error.
+pub fn repack_buffers<'a, E: Extensions>(
+    gltf: &mut Gltf<E>,
+    buffer_bytes: impl Fn(usize) -> Option<&'a [u8]>,
+) -> Result<Vec<u8>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let mut packed = Vec::new();
+    let mut offsets = Vec::with_capacity(gltf.buffer_views.len());
+
+    for (index, buffer_view) in gltf.buffer_views.iter().enumerate() {
+        if buffer_view.extensions.ext_meshopt_compression().is_some() {
+            return Err(Error::MeshoptRepack(index));
+        }
+
+        if gltf.buffers.get(buffer_view.buffer).is_none() {
+            return Err(Error::BufferIndexOutOfBounds(buffer_view.buffer));
+        }
+
+        let bytes = buffer_bytes(buffer_view.buffer)
+            .ok_or(Error::MissingBufferData(buffer_view.buffer))?
+            .get(buffer_view.byte_offset..buffer_view.byte_offset + buffer_view.byte_length)
+            .ok_or(Error::BufferViewOutOfBounds(index))?;
+
+        packed.resize(packed.len().next_multiple_of(4), 0);
+        offsets.push(packed.len());
+        packed.extend_from_slice(bytes);
+    }
+
+    packed.resize(packed.len().next_multiple_of(4), 0);
+
+    for (buffer_view, offset) in gltf.buffer_views.iter_mut().zip(offsets) {
+        buffer_view.buffer = 0;
+        buffer_view.byte_offset = offset;
+    }
+
+    // Buffers can't be empty.
+    gltf.buffers = if packed.is_empty() {
+        Vec::new()
+    } else {
+        vec![crate::Buffer::new(None, packed.len())]
+    };
+
+    Ok(packed)
+}
+
+/// Pack a document and its buffers into a .glb file, with every buffer merged into the binary
+/// chunk by [`repack_buffers`]. `gltf` is updated to match the .glb file.
+///
+/// Images with a uri are left as they are, so they're still loaded from outside the file.
+pub fn pack_glb<'a, E: Extensions>(
+    gltf: &mut Gltf<E>,
+    buffer_bytes: impl Fn(usize) -> Option<&'a [u8]>,
+) -> Result<Vec<u8>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let binary_chunk = repack_buffers(gltf, buffer_bytes)?;
+
+    gltf.to_glb(if binary_chunk.is_empty() {
+        None
+    } else {
+        Some(&binary_chunk)
+    })
+    .map_err(Error::Glb)
+}
@@ -0,0 +1,212 @@
+use crate::json::{object_members, write_object, RawValue};
+use core::str::Chars;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok, SerJson, SerJsonState};
+
+/// Two sets of extensions parsed from the same `extensions` (or `extras`) object.
+///
+/// Usually created through [`compose_extensions!`](crate::compose_extensions), to add support
+/// for extensions on top of the ones in [`default_extensions`](crate::default_extensions).
+/// When serialized, the members of `extra` are written after those of `base`, unless `base`
+/// already wrote them.
+#[derive(Debug, Clone, Default)]
+pub struct Compose<A, B> {
+    pub base: A,
+    pub extra: B,
+}
+
+impl<A: DeJson, B: DeJson> DeJson for Compose<A, B> {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Self, DeJsonErr> {
+        if state.tok != DeJsonTok::CurlyOpen {
+            return Err(state.err_token("{"));
+        }
+
+        let json = match RawValue::de_json(state, input)? {
+            RawValue::Object(members) => write_object(&members),
+            _ => unreachable!(),
+        };
+
+        // Errors from the nested parses have positions within the object, which aren't useful
+        // on their own.
+        let map_err = |err: DeJsonErr| DeJsonErr {
+            msg: err.msg,
+            line: state.line,
+            col: state.col,
+        };
+
+        Ok(Self {
+            base: A::deserialize_json(&json).map_err(map_err)?,
+            extra: B::deserialize_json(&json).map_err(map_err)?,
+        })
+    }
+}
+
+impl<A: SerJson, B: SerJson> SerJson for Compose<A, B> {
+    fn ser_json(&self, _d: usize, s: &mut SerJsonState) {
+        let mut members = object_members(&self.base.serialize_json());
+
+        for (name, value) in object_members(&self.extra.serialize_json()) {
+            if !members.iter().any(|(existing, _)| *existing == name) {
+                members.push((name, value));
+            }
+        }
+
+        s.out.push_str(&write_object(&members));
+    }
+}
+
+impl<A: crate::TextureSourceExtensions, B> crate::TextureSourceExtensions for Compose<A, B> {
+    fn source(&self, kind: crate::TextureSourceKind) -> Option<usize> {
+        self.base.source(kind)
+    }
+}
+
+impl<A: crate::animation_pointer::AnimationPointerExtension, B>
+    crate::animation_pointer::AnimationPointerExtension for Compose<A, B>
+{
+    fn khr_animation_pointer(&self) -> Option<&str> {
+        self.base.khr_animation_pointer()
+    }
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<A: crate::primitive_reader::MeshOptCompressionExtension, B>
+    crate::primitive_reader::MeshOptCompressionExtension for Compose<A, B>
+{
+    fn ext_meshopt_compression(&self) -> Option<crate::extensions::ExtMeshoptCompression> {
+        self.base.ext_meshopt_compression()
+    }
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<A: crate::primitive_reader::DracoCompressionExtension, B>
+    crate::primitive_reader::DracoCompressionExtension for Compose<A, B>
+{
+    fn khr_draco_mesh_compression(&self) -> Option<&crate::extensions::KhrDracoMeshCompression> {
+        self.base.khr_draco_mesh_compression()
+    }
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<A: crate::buffers::MeshOptFallbackExtension, B> crate::buffers::MeshOptFallbackExtension
+    for Compose<A, B>
+{
+    fn ext_meshopt_fallback(&self) -> bool {
+        self.base.ext_meshopt_fallback()
+    }
+}
+
+#[cfg(feature = "primitive_reader")]
+impl<A: crate::primitive_reader::MeshGpuInstancingExtension, B>
+    crate::primitive_reader::MeshGpuInstancingExtension for Compose<A, B>
+{
+    fn ext_mesh_gpu_instancing(&self) -> Option<&crate::extensions::ExtMeshGpuInstancing> {
+        self.base.ext_mesh_gpu_instancing()
+    }
+}
+
+/// Declare an extension set that's [`default_extensions`](crate::default_extensions) with some
+/// of the associated types extended through [`Compose`].
+///
+/// ```
+/// use nanoserde::{DeJson, SerJson};
+///
+/// #[derive(Debug, Default, Clone, DeJson, SerJson)]
+/// pub struct MyNodeExtensions {
+///     #[nserde(rename = "ACME_tag")]
+///     pub acme_tag: Option<String>,
+/// }
+///
+/// goth_gltf::compose_extensions! {
+///     pub struct MyExtensions {
+///         NodeExtensions += MyNodeExtensions,
+///     }
+/// }
+///
+/// let json = r#"{
+///     "asset": {"version": "2.0"},
+///     "nodes": [{"extensions": {"ACME_tag": "door", "MSFT_lod": {"ids": [1]}}}]
+/// }"#;
+/// let gltf = goth_gltf::Gltf::<MyExtensions>::from_json_string(json).unwrap();
+///
+/// let extensions = &gltf.nodes[0].extensions;
+/// assert_eq!(extensions.extra.acme_tag.as_deref(), Some("door"));
+/// ```
+#[macro_export]
+macro_rules! compose_extensions {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($ty:ident += $extra:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Clone, Copy)]
+        $vis struct $name;
+
+        impl $crate::__private::nanoserde::DeJson for $name {
+            fn de_json(
+                state: &mut $crate::__private::nanoserde::DeJsonState,
+                input: &mut ::core::str::Chars,
+            ) -> ::core::result::Result<Self, $crate::__private::nanoserde::DeJsonErr> {
+                <$crate::default_extensions::Extensions as $crate::__private::nanoserde::DeJson>
+                    ::de_json(state, input)?;
+                Ok(Self)
+            }
+        }
+
+        impl $crate::__private::nanoserde::SerJson for $name {
+            fn ser_json(&self, d: usize, s: &mut $crate::__private::nanoserde::SerJsonState) {
+                $crate::__private::nanoserde::SerJson::ser_json(
+                    &$crate::default_extensions::Extensions,
+                    d,
+                    s,
+                );
+            }
+        }
+
+        // Scoped so that the helper macros don't clash between invocations.
+        const _: () = {
+            // The picker needs its own metavariables, which can't be written directly inside
+            // this macro, so the `$` is passed in as a token.
+            macro_rules! __goth_gltf_with_dollar {
+                ($d:tt) => {
+                    macro_rules! __goth_gltf_pick {
+                        $(
+                            ($ty, $d default:ty) => { $crate::Compose<$d default, $extra> };
+                        )*
+                        ($d other:ident, $d default:ty) => { $d default };
+                    }
+                };
+            }
+
+            __goth_gltf_with_dollar!($);
+
+            impl $crate::Extensions for $name {
+                type RootExtensions = __goth_gltf_pick!(RootExtensions, $crate::default_extensions::RootExtensions);
+                type ImageExtensions = __goth_gltf_pick!(ImageExtensions, $crate::default_extensions::ImageExtensions);
+                type SamplerExtensions = __goth_gltf_pick!(SamplerExtensions, $crate::default_extensions::SamplerExtensions);
+                type TextureExtensions = __goth_gltf_pick!(TextureExtensions, $crate::default_extensions::TextureExtensions);
+                type TextureInfoExtensions = __goth_gltf_pick!(TextureInfoExtensions, $crate::default_extensions::TextureInfoExtensions);
+                type MaterialExtensions = __goth_gltf_pick!(MaterialExtensions, $crate::default_extensions::MaterialExtensions<Self>);
+                type MaterialExtras = __goth_gltf_pick!(MaterialExtras, $crate::default_extensions::MaterialExtras);
+                type BufferExtensions = __goth_gltf_pick!(BufferExtensions, $crate::default_extensions::BufferExtensions);
+                type NodeExtensions = __goth_gltf_pick!(NodeExtensions, $crate::default_extensions::NodeExtensions);
+                type NodeExtras = __goth_gltf_pick!(NodeExtras, $crate::default_extensions::NodeExtras);
+                type MeshExtensions = __goth_gltf_pick!(MeshExtensions, $crate::default_extensions::MeshExtensions);
+                type SceneExtensions = __goth_gltf_pick!(SceneExtensions, $crate::default_extensions::SceneExtensions);
+                type SkinExtensions = __goth_gltf_pick!(SkinExtensions, $crate::default_extensions::SkinExtensions);
+                type BufferViewExtensions = __goth_gltf_pick!(BufferViewExtensions, $crate::default_extensions::BufferViewExtensions);
+                type AccessorExtensions = __goth_gltf_pick!(AccessorExtensions, $crate::default_extensions::AccessorExtensions);
+                type PrimitiveExtensions = __goth_gltf_pick!(PrimitiveExtensions, $crate::default_extensions::PrimitiveExtensions);
+                type AnimationExtensions = __goth_gltf_pick!(AnimationExtensions, $crate::default_extensions::AnimationExtensions);
+                type ChannelExtensions = __goth_gltf_pick!(ChannelExtensions, $crate::default_extensions::ChannelExtensions);
+                type TargetExtensions = __goth_gltf_pick!(TargetExtensions, $crate::default_extensions::TargetExtensions);
+                type AnimationSamplerExtensions = __goth_gltf_pick!(AnimationSamplerExtensions, $crate::default_extensions::AnimationSamplerExtensions);
+                type CameraExtensions = __goth_gltf_pick!(CameraExtensions, $crate::default_extensions::CameraExtensions);
+                type AssetExtensions = __goth_gltf_pick!(AssetExtensions, $crate::default_extensions::AssetExtensions);
+                type AssetExtras = __goth_gltf_pick!(AssetExtras, $crate::default_extensions::AssetExtras);
+                type Extras = __goth_gltf_pick!(Extras, $crate::default_extensions::Extras);
+            }
+        };
+    };
+}
@@ -0,0 +1,380 @@
+// Defaults and constructors for building documents in memory. Objects without required
+// properties implement `Default`, with the values the spec uses when a property is missing. The
+// rest have a `new` taking the required properties.
+
+use crate::*;
+
+impl<E: Extensions> Default for Gltf<E> {
+    fn default() -> Self {
+        Self {
+            asset: Default::default(),
+            extensions_used: Vec::new(),
+            extensions_required: Vec::new(),
+            images: Vec::new(),
+            textures: Vec::new(),
+            materials: Vec::new(),
+            buffers: Vec::new(),
+            buffer_views: Vec::new(),
+            accessors: Vec::new(),
+            meshes: Vec::new(),
+            animations: Vec::new(),
+            nodes: Vec::new(),
+            skins: Vec::new(),
+            samplers: Vec::new(),
+            cameras: Vec::new(),
+            extensions: Default::default(),
+            extras: Default::default(),
+            scenes: Vec::new(),
+            scene: None,
+            unknown_json: None,
+        }
+    }
+}
+
+impl<E: Extensions> Default for Asset<E> {
+    fn default() -> Self {
+        Self {
+            version: "2.0".to_string(),
+            ..Self::missing()
+        }
+    }
+}
+
+impl<E: Extensions> Default for Scene<E> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Node<E> {
+    fn default() -> Self {
+        Self {
+            camera: None,
+            children: Vec::new(),
+            skin: None,
+            matrix: None,
+            mesh: None,
+            rotation: None,
+            scale: None,
+            translation: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Mesh<E> {
+    fn default() -> Self {
+        Self {
+            primitives: Vec::new(),
+            weights: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Primitive<E> {
+    fn default() -> Self {
+        Self {
+            attributes: Default::default(),
+            indices: None,
+            material: None,
+            mode: PrimitiveMode::Triangles,
+            targets: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Skin<E> {
+    fn default() -> Self {
+        Self {
+            inverse_bind_matrices: None,
+            skeleton: None,
+            joints: Vec::new(),
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Animation<E> {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            samplers: Vec::new(),
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Channel<E> {
+    pub fn new(sampler: usize, target: Target<E>) -> Self {
+        Self {
+            sampler,
+            target,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Target<E> {
+    pub fn new(node: usize, path: TargetPath) -> Self {
+        Self {
+            node: Some(node),
+            path,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> AnimationSampler<E> {
+    pub fn new(input: usize, output: usize, interpolation: Interpolation) -> Self {
+        Self {
+            input,
+            interpolation,
+            output,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Buffer<E> {
+    /// A buffer with the given uri, or the binary chunk of a glb file if `None`.
+    pub fn new(uri: Option<String>, byte_length: usize) -> Self {
+        Self {
+            uri,
+            byte_length,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> BufferView<E> {
+    pub fn new(buffer: usize, byte_offset: usize, byte_length: usize) -> Self {
+        Self {
+            buffer,
+            byte_offset,
+            byte_length,
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Accessor<E> {
+    /// An accessor with tightly packed elements at the start of a buffer view.
+    pub fn new(
+        buffer_view: usize,
+        component_type: ComponentType,
+        accessor_type: AccessorType,
+        count: usize,
+    ) -> Self {
+        Self {
+            buffer_view: Some(buffer_view),
+            byte_offset: 0,
+            component_type,
+            normalized: false,
+            count,
+            accessor_type,
+            sparse: None,
+            min: None,
+            max: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Sparse<E> {
+    pub fn new(count: usize, indices: SparseIndices<E>, values: SparseValues<E>) -> Self {
+        Self {
+            count,
+            indices,
+            values,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> SparseIndices<E> {
+    pub fn new(buffer_view: usize, component_type: ComponentType) -> Self {
+        Self {
+            buffer_view,
+            byte_offset: 0,
+            component_type,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> SparseValues<E> {
+    pub fn new(buffer_view: usize) -> Self {
+        Self {
+            buffer_view,
+            byte_offset: 0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Image<E> {
+    fn default() -> Self {
+        Self {
+            uri: None,
+            mime_type: None,
+            buffer_view: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Texture<E> {
+    fn default() -> Self {
+        Self {
+            sampler: None,
+            source: None,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Default for Sampler<E> {
+    fn default() -> Self {
+        Self {
+            mag_filter: None,
+            min_filter: None,
+            wrap_s: SamplerWrap::Repeat,
+            wrap_t: SamplerWrap::Repeat,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> TextureInfo<E> {
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            tex_coord: 0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> NormalTextureInfo<E> {
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            tex_coord: 0,
+            scale: 1.0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> OcclusionTextureInfo<E> {
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            tex_coord: 0,
+            strength: 1.0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> Camera<E> {
+    pub fn perspective(perspective: CameraPerspective<E>) -> Self {
+        Self {
+            perspective: Some(perspective),
+            orthographic: None,
+            ty: CameraType::Perspective,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+
+    pub fn orthographic(orthographic: CameraOrthographic<E>) -> Self {
+        Self {
+            perspective: None,
+            orthographic: Some(orthographic),
+            ty: CameraType::Orthographic,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> CameraPerspective<E> {
+    /// An infinite perspective projection that uses the aspect ratio of the viewport.
+    pub fn new(yfov: f32, znear: f32) -> Self {
+        Self {
+            yfov,
+            znear,
+            zfar: None,
+            aspect_ratio: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl<E: Extensions> CameraOrthographic<E> {
+    pub fn new(xmag: f32, ymag: f32, znear: f32, zfar: f32) -> Self {
+        Self {
+            xmag,
+            ymag,
+            zfar,
+            znear,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
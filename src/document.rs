@@ -0,0 +1,377 @@
+use crate::*;
+use core::ops::Deref;
+
+/// A borrowed [`Gltf`] that resolves the indices between objects.
+///
+/// The raw structs are still available through [`Handle`]'s `Deref`, so this only saves writing
+/// the index lookups:
+///
+/// ```
+/// # use goth_gltf::{document::Document, Gltf, AnyExtensions};
+/// let json = r#"{
+///     "asset": {"version": "2.0"},
+///     "nodes": [{"mesh": 0}],
+///     "meshes": [{"primitives": [{"attributes": {}, "material": 0}]}],
+///     "materials": [{"alphaCutoff": 0.25}]
+/// }"#;
+/// let gltf = Gltf::<AnyExtensions>::from_json_string(json).unwrap();
+/// let document = Document::new(&gltf);
+///
+/// let node = document.node(0).unwrap();
+/// let primitive = node.mesh().unwrap().primitives().next().unwrap();
+/// assert_eq!(primitive.material().unwrap().alpha_cutoff, 0.25);
+/// ```
+///
+/// Indices that are out of bounds resolve to `None`.
+pub struct Document<'a, E: Extensions> {
+    gltf: &'a Gltf<E>,
+}
+
+impl<'a, E: Extensions> Clone for Document<'a, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E: Extensions> Copy for Document<'a, E> {}
+
+impl<'a, E: Extensions> Document<'a, E> {
+    pub fn new(gltf: &'a Gltf<E>) -> Self {
+        Self { gltf }
+    }
+
+    pub fn gltf(&self) -> &'a Gltf<E> {
+        self.gltf
+    }
+
+    fn handle<T>(&self, items: &'a [T], index: usize) -> Option<Handle<'a, E, T>> {
+        items.get(index).map(|value| Handle {
+            gltf: self.gltf,
+            index,
+            value,
+        })
+    }
+
+    fn handles<T>(&self, items: &'a [T]) -> impl ExactSizeIterator<Item = Handle<'a, E, T>> + 'a {
+        let gltf = self.gltf;
+        items
+            .iter()
+            .enumerate()
+            .map(move |(index, value)| Handle { gltf, index, value })
+    }
+
+    /// The scene to display when the document is loaded, if specified.
+    pub fn default_scene(&self) -> Option<Handle<'a, E, Scene<E>>> {
+        self.scene(self.gltf.scene?)
+    }
+
+    pub fn scene(&self, index: usize) -> Option<Handle<'a, E, Scene<E>>> {
+        self.handle(&self.gltf.scenes, index)
+    }
+
+    pub fn scenes(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Scene<E>>> + 'a {
+        self.handles(&self.gltf.scenes)
+    }
+
+    pub fn node(&self, index: usize) -> Option<Handle<'a, E, Node<E>>> {
+        self.handle(&self.gltf.nodes, index)
+    }
+
+    pub fn nodes(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Node<E>>> + 'a {
+        self.handles(&self.gltf.nodes)
+    }
+
+    pub fn mesh(&self, index: usize) -> Option<Handle<'a, E, Mesh<E>>> {
+        self.handle(&self.gltf.meshes, index)
+    }
+
+    pub fn meshes(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Mesh<E>>> + 'a {
+        self.handles(&self.gltf.meshes)
+    }
+
+    pub fn material(&self, index: usize) -> Option<Handle<'a, E, Material<E>>> {
+        self.handle(&self.gltf.materials, index)
+    }
+
+    pub fn materials(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Material<E>>> + 'a {
+        self.handles(&self.gltf.materials)
+    }
+
+    pub fn texture(&self, index: usize) -> Option<Handle<'a, E, Texture<E>>> {
+        self.handle(&self.gltf.textures, index)
+    }
+
+    pub fn textures(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Texture<E>>> + 'a {
+        self.handles(&self.gltf.textures)
+    }
+
+    pub fn image(&self, index: usize) -> Option<Handle<'a, E, Image<E>>> {
+        self.handle(&self.gltf.images, index)
+    }
+
+    pub fn images(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Image<E>>> + 'a {
+        self.handles(&self.gltf.images)
+    }
+
+    pub fn sampler(&self, index: usize) -> Option<Handle<'a, E, Sampler<E>>> {
+        self.handle(&self.gltf.samplers, index)
+    }
+
+    pub fn samplers(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Sampler<E>>> + 'a {
+        self.handles(&self.gltf.samplers)
+    }
+
+    pub fn accessor(&self, index: usize) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.handle(&self.gltf.accessors, index)
+    }
+
+    pub fn accessors(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Accessor<E>>> + 'a {
+        self.handles(&self.gltf.accessors)
+    }
+
+    pub fn buffer_view(&self, index: usize) -> Option<Handle<'a, E, BufferView<E>>> {
+        self.handle(&self.gltf.buffer_views, index)
+    }
+
+    pub fn buffer(&self, index: usize) -> Option<Handle<'a, E, Buffer<E>>> {
+        self.handle(&self.gltf.buffers, index)
+    }
+
+    pub fn skin(&self, index: usize) -> Option<Handle<'a, E, Skin<E>>> {
+        self.handle(&self.gltf.skins, index)
+    }
+
+    pub fn skins(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Skin<E>>> + 'a {
+        self.handles(&self.gltf.skins)
+    }
+
+    pub fn camera(&self, index: usize) -> Option<Handle<'a, E, Camera<E>>> {
+        self.handle(&self.gltf.cameras, index)
+    }
+
+    pub fn cameras(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Camera<E>>> + 'a {
+        self.handles(&self.gltf.cameras)
+    }
+
+    pub fn animation(&self, index: usize) -> Option<Handle<'a, E, Animation<E>>> {
+        self.handle(&self.gltf.animations, index)
+    }
+
+    pub fn animations(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Animation<E>>> + 'a {
+        self.handles(&self.gltf.animations)
+    }
+}
+
+/// An object in a [`Document`], along with its index.
+///
+/// Derefs to the raw struct, so e.g. `node.mesh` is the mesh index and `node.mesh()` is the
+/// resolved mesh. For a [`Primitive`], the index is within its mesh.
+pub struct Handle<'a, E: Extensions, T> {
+    gltf: &'a Gltf<E>,
+    index: usize,
+    value: &'a T,
+}
+
+impl<'a, E: Extensions, T> Clone for Handle<'a, E, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E: Extensions, T> Copy for Handle<'a, E, T> {}
+
+impl<'a, E: Extensions, T: core::fmt::Debug> core::fmt::Debug for Handle<'a, E, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Handle")
+            .field("index", &self.index)
+            .field("value", self.value)
+            .finish()
+    }
+}
+
+impl<'a, E: Extensions, T> Deref for Handle<'a, E, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, E: Extensions, T> Handle<'a, E, T> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The raw struct, with the lifetime of the document rather than the handle.
+    pub fn get(&self) -> &'a T {
+        self.value
+    }
+
+    pub fn document(&self) -> Document<'a, E> {
+        Document::new(self.gltf)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Scene<E>> {
+    /// The root nodes of the scene.
+    pub fn nodes(&self) -> impl Iterator<Item = Handle<'a, E, Node<E>>> + 'a {
+        let document = self.document();
+        self.value
+            .nodes
+            .iter()
+            .filter_map(move |&index| document.node(index))
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Node<E>> {
+    pub fn mesh(&self) -> Option<Handle<'a, E, Mesh<E>>> {
+        self.document().mesh(self.value.mesh?)
+    }
+
+    pub fn skin(&self) -> Option<Handle<'a, E, Skin<E>>> {
+        self.document().skin(self.value.skin?)
+    }
+
+    pub fn camera(&self) -> Option<Handle<'a, E, Camera<E>>> {
+        self.document().camera(self.value.camera?)
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = Handle<'a, E, Node<E>>> + 'a {
+        let document = self.document();
+        self.value
+            .children
+            .iter()
+            .filter_map(move |&index| document.node(index))
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Mesh<E>> {
+    pub fn primitives(&self) -> impl ExactSizeIterator<Item = Handle<'a, E, Primitive<E>>> + 'a {
+        self.document().handles(&self.value.primitives)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Primitive<E>> {
+    /// The material of the primitive. `None` means the default material should be used.
+    pub fn material(&self) -> Option<Handle<'a, E, Material<E>>> {
+        self.document().material(self.value.material?)
+    }
+
+    pub fn indices(&self) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.document().accessor(self.value.indices?)
+    }
+
+    pub fn position(&self) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.document().accessor(self.value.attributes.position?)
+    }
+
+    pub fn normal(&self) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.document().accessor(self.value.attributes.normal?)
+    }
+
+    pub fn tangent(&self) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.document().accessor(self.value.attributes.tangent?)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Material<E>> {
+    fn texture(&self, index: Option<usize>) -> Option<Handle<'a, E, Texture<E>>> {
+        self.document().texture(index?)
+    }
+
+    pub fn base_color_texture(&self) -> Option<Handle<'a, E, Texture<E>>> {
+        self.texture(
+            self.value
+                .pbr_metallic_roughness
+                .base_color_texture
+                .as_ref()
+                .map(|info| info.index),
+        )
+    }
+
+    pub fn metallic_roughness_texture(&self) -> Option<Handle<'a, E, Texture<E>>> {
+        self.texture(
+            self.value
+                .pbr_metallic_roughness
+                .metallic_roughness_texture
+                .as_ref()
+                .map(|info| info.index),
+        )
+    }
+
+    pub fn normal_texture(&self) -> Option<Handle<'a, E, Texture<E>>> {
+        self.texture(self.value.normal_texture.as_ref().map(|info| info.index))
+    }
+
+    pub fn occlusion_texture(&self) -> Option<Handle<'a, E, Texture<E>>> {
+        self.texture(self.value.occlusion_texture.as_ref().map(|info| info.index))
+    }
+
+    pub fn emissive_texture(&self) -> Option<Handle<'a, E, Texture<E>>> {
+        self.texture(self.value.emissive_texture.as_ref().map(|info| info.index))
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Texture<E>> {
+    /// The core `source` image. See [`preferred_image`](Self::preferred_image) for images from
+    /// extensions such as `KHR_texture_basisu`.
+    pub fn image(&self) -> Option<Handle<'a, E, Image<E>>> {
+        self.document().image(self.value.source?)
+    }
+
+    /// The sampler of the texture. `None` means repeat wrapping and auto filtering should be
+    /// used.
+    pub fn sampler(&self) -> Option<Handle<'a, E, Sampler<E>>> {
+        self.document().sampler(self.value.sampler?)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Texture<E>>
+where
+    E::TextureExtensions: TextureSourceExtensions,
+{
+    /// The image picked by [`Texture::preferred_source`].
+    pub fn preferred_image(
+        &self,
+        supported: &[TextureSourceKind],
+    ) -> Option<Handle<'a, E, Image<E>>> {
+        self.document()
+            .image(self.value.preferred_source(supported)?.image)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Image<E>> {
+    pub fn buffer_view(&self) -> Option<Handle<'a, E, BufferView<E>>> {
+        self.document().buffer_view(self.value.buffer_view?)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, BufferView<E>> {
+    pub fn buffer(&self) -> Option<Handle<'a, E, Buffer<E>>> {
+        self.document().buffer(self.value.buffer)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Accessor<E>> {
+    pub fn buffer_view(&self) -> Option<Handle<'a, E, BufferView<E>>> {
+        self.document().buffer_view(self.value.buffer_view?)
+    }
+}
+
+impl<'a, E: Extensions> Handle<'a, E, Skin<E>> {
+    pub fn inverse_bind_matrices(&self) -> Option<Handle<'a, E, Accessor<E>>> {
+        self.document().accessor(self.value.inverse_bind_matrices?)
+    }
+
+    pub fn skeleton(&self) -> Option<Handle<'a, E, Node<E>>> {
+        self.document().node(self.value.skeleton?)
+    }
+
+    pub fn joints(&self) -> impl Iterator<Item = Handle<'a, E, Node<E>>> + 'a {
+        let document = self.document();
+        self.value
+            .joints
+            .iter()
+            .filter_map(move |&index| document.node(index))
+    }
+}
@@ -0,0 +1,265 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::write::PathSegment;
+use crate::{Extensions, Gltf, Material, Mesh, Node, Scene, UnknownJson};
+
+/// Why an edit couldn't be made. The document is left unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditError {
+    NodeOutOfBounds(usize),
+    MeshOutOfBounds(usize),
+    MaterialOutOfBounds(usize),
+    /// The new parent is the node itself or one of its descendants.
+    WouldCreateCycle {
+        node: usize,
+        parent: usize,
+    },
+    /// The node is a joint of a skin, so removing it would change the joint indices that
+    /// vertices refer to.
+    NodeIsJoint {
+        node: usize,
+        skin: usize,
+    },
+}
+
+impl core::fmt::Display for EditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::NodeOutOfBounds(index) => write!(f, "node {} doesn't exist", index),
+            Self::MeshOutOfBounds(index) => write!(f, "mesh {} doesn't exist", index),
+            Self::MaterialOutOfBounds(index) => write!(f, "material {} doesn't exist", index),
+            Self::WouldCreateCycle { node, parent } => write!(
+                f,
+                "node {} can't be a child of node {}, which is itself or its descendant",
+                node, parent
+            ),
+            Self::NodeIsJoint { node, skin } => {
+                write!(f, "node {} is a joint of skin {}", node, skin)
+            }
+        }
+    }
+}
+
+impl core::error::Error for EditError {}
+
+// Map an index after the object at `removed` is removed, or `None` if it's the removed one.
+fn shift(index: usize, removed: usize) -> Option<usize> {
+    match index.cmp(&removed) {
+        core::cmp::Ordering::Less => Some(index),
+        core::cmp::Ordering::Equal => None,
+        core::cmp::Ordering::Greater => Some(index - 1),
+    }
+}
+
+fn shift_all(indices: &mut Vec<usize>, removed: usize) {
+    indices.retain_mut(|index| match shift(*index, removed) {
+        Some(shifted) => {
+            *index = shifted;
+            true
+        }
+        None => false,
+    });
+}
+
+// Keep the unknown JSON members with their objects when an element of `array` is removed.
+pub(crate) fn remove_unknown(
+    unknown: &mut Option<UnknownJson>,
+    array: &[PathSegment],
+    index: usize,
+) {
+    if let Some(unknown) = unknown {
+        unknown.remove_element(array, index);
+    }
+}
+
+/// References from extensions, such as `KHR_animation_pointer` pointers or
+/// `KHR_materials_variants` mappings, aren't updated, as the extension types are generic.
+impl<E: Extensions> Gltf<E> {
+    /// Add a node, returning its index. It isn't part of any scene or hierarchy until it's
+    /// added to one, e.g. with [`reparent_node`](Self::reparent_node).
+    pub fn add_node(&mut self, node: Node<E>) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_mesh(&mut self, mesh: Mesh<E>) -> usize {
+        self.meshes.push(mesh);
+        self.meshes.len() - 1
+    }
+
+    pub fn add_material(&mut self, material: Material<E>) -> usize {
+        self.materials.push(material);
+        self.materials.len() - 1
+    }
+
+    pub fn add_scene(&mut self, scene: Scene<E>) -> usize {
+        self.scenes.push(scene);
+        self.scenes.len() - 1
+    }
+
+    /// Move a node to be the last child of `parent`, or detach it from its parents if `None`.
+    ///
+    /// A node that's given a parent is also removed from the root nodes of any scene, as
+    /// scenes can only reference root nodes.
+    pub fn reparent_node(&mut self, node: usize, parent: Option<usize>) -> Result<(), EditError> {
+        if node >= self.nodes.len() {
+            return Err(EditError::NodeOutOfBounds(node));
+        }
+
+        if let Some(parent) = parent {
+            if parent >= self.nodes.len() {
+                return Err(EditError::NodeOutOfBounds(parent));
+            }
+
+            let parents = self.node_parents();
+
+            if parent == node || parents.ancestors(parent).any(|ancestor| ancestor == node) {
+                return Err(EditError::WouldCreateCycle { node, parent });
+            }
+        }
+
+        for other in &mut self.nodes {
+            other.children.retain(|&child| child != node);
+        }
+
+        if let Some(parent) = parent {
+            for scene in &mut self.scenes {
+                scene.nodes.retain(|&root| root != node);
+            }
+
+            self.nodes[parent].children.push(node);
+        }
+
+        Ok(())
+    }
+
+    /// Remove a node, shifting down the indices of the nodes after it.
+    ///
+    /// Its children become root nodes, and are added to the scenes it was part of, in its place if
+    /// it was a root node itself. Animation channels that target it are removed and skins that use
+    /// it as their skeleton root lose it. Nodes that are skin joints can't be removed.
+    pub fn remove_node(&mut self, node: usize) -> Result<Node<E>, EditError> {
+        if node >= self.nodes.len() {
+            return Err(EditError::NodeOutOfBounds(node));
+        }
+
+        if let Some(skin) = self
+            .skins
+            .iter()
+            .position(|skin| skin.joints.contains(&node))
+        {
+            return Err(EditError::NodeIsJoint { node, skin });
+        }
+
+        let parents = self.node_parents();
+        let in_scene: Vec<bool> = self
+            .scenes
+            .iter()
+            .map(|scene| {
+                core::iter::once(node)
+                    .chain(parents.ancestors(node))
+                    .any(|ancestor| scene.nodes.contains(&ancestor))
+            })
+            .collect();
+
+        let removed = self.nodes.remove(node);
+        remove_unknown(&mut self.unknown_json, &[PathSegment::key("nodes")], node);
+
+        let mut children = removed.children.clone();
+        shift_all(&mut children, node);
+
+        for other in &mut self.nodes {
+            shift_all(&mut other.children, node);
+        }
+
+        for (scene, in_scene) in self.scenes.iter_mut().zip(in_scene) {
+            let position = scene.nodes.iter().position(|&root| root == node);
+            shift_all(&mut scene.nodes, node);
+
+            if in_scene {
+                let position = position.unwrap_or(scene.nodes.len());
+                let new_roots: Vec<usize> = children
+                    .iter()
+                    .copied()
+                    .filter(|child| !scene.nodes.contains(child))
+                    .collect();
+                scene.nodes.splice(position..position, new_roots);
+            }
+        }
+
+        for skin in &mut self.skins {
+            shift_all(&mut skin.joints, node);
+            skin.skeleton = skin.skeleton.and_then(|skeleton| shift(skeleton, node));
+        }
+
+        for (i, animation) in self.animations.iter_mut().enumerate() {
+            let channels = [
+                PathSegment::key("animations"),
+                PathSegment::Index(i),
+                PathSegment::key("channels"),
+            ];
+            let mut channel_index = 0;
+
+            animation.channels.retain_mut(|channel| {
+                let keep = match channel.target.node {
+                    Some(target) => match shift(target, node) {
+                        Some(shifted) => {
+                            channel.target.node = Some(shifted);
+                            true
+                        }
+                        None => false,
+                    },
+                    None => true,
+                };
+
+                if keep {
+                    channel_index += 1;
+                } else {
+                    remove_unknown(&mut self.unknown_json, &channels, channel_index);
+                }
+
+                keep
+            });
+        }
+
+        Ok(removed)
+    }
+
+    /// Remove a mesh, shifting down the indices of the meshes after it. Nodes that used it are
+    /// left without a mesh.
+    pub fn remove_mesh(&mut self, mesh: usize) -> Result<Mesh<E>, EditError> {
+        if mesh >= self.meshes.len() {
+            return Err(EditError::MeshOutOfBounds(mesh));
+        }
+
+        let removed = self.meshes.remove(mesh);
+        remove_unknown(&mut self.unknown_json, &[PathSegment::key("meshes")], mesh);
+
+        for node in &mut self.nodes {
+            node.mesh = node.mesh.and_then(|index| shift(index, mesh));
+        }
+
+        Ok(removed)
+    }
+
+    /// Remove a material, shifting down the indices of the materials after it. Primitives that
+    /// used it fall back to the default material.
+    pub fn remove_material(&mut self, material: usize) -> Result<Material<E>, EditError> {
+        if material >= self.materials.len() {
+            return Err(EditError::MaterialOutOfBounds(material));
+        }
+
+        let removed = self.materials.remove(material);
+        remove_unknown(
+            &mut self.unknown_json,
+            &[PathSegment::key("materials")],
+            material,
+        );
+
+        for primitive in self.meshes.iter_mut().flat_map(|mesh| &mut mesh.primitives) {
+            primitive.material = primitive.material.and_then(|index| shift(index, material));
+        }
+
+        Ok(removed)
+    }
+}
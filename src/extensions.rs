@@ -1,12 +1,50 @@
+#[cfg(any(
+    feature = "khr_materials",
+    feature = "adobe_materials_thin_transparency"
+))]
 use crate::{Extensions, TextureInfo};
-use nanoserde::DeJson;
+// Unused when every extension feature is disabled.
+#[cfg(feature = "no_std")]
+#[allow(unused_imports)]
+use crate::prelude::*;
+#[cfg(any(feature = "khr_draco", feature = "vrm"))]
+use crate::HashMap;
+// Unused when every extension feature is disabled.
+#[allow(unused_imports)]
+use nanoserde::{DeJson, SerJson};
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "texture_formats")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct KhrTextureBasisu {
     pub source: usize,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "texture_formats")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct ExtTextureAstc {
+    pub source: usize,
+}
+
+#[cfg(feature = "texture_formats")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct ExtTextureAvif {
+    pub source: usize,
+}
+
+#[cfg(feature = "texture_formats")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct ExtTextureWebp {
+    pub source: usize,
+}
+
+#[cfg(feature = "texture_formats")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct MsftTextureDds {
+    pub source: usize,
+}
+
+#[cfg(feature = "khr_texture_transform")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct KhrTextureTransform {
     #[nserde(default)]
     pub offset: [f32; 2],
@@ -14,12 +52,13 @@ pub struct KhrTextureTransform {
     pub rotation: f32,
     #[nserde(default = "[1.0, 1.0]")]
     pub scale: [f32; 2],
+    /// Overrides the `texCoord` of the texture info when set.
     #[nserde(rename = "texCoord")]
-    #[nserde(default)]
-    pub tex_coord: usize,
+    pub tex_coord: Option<usize>,
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct KhrMaterialsSheen<E: Extensions> {
     #[nserde(rename = "sheenColorFactor")]
     #[nserde(default)]
@@ -33,17 +72,77 @@ pub struct KhrMaterialsSheen<E: Extensions> {
     pub sheen_roughness_texture: Option<TextureInfo<E>>,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct KhrMaterialsEmissiveStrength {
     #[nserde(rename = "emissiveStrength")]
     #[nserde(default = "1.0")]
     pub emissive_strength: f32,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct KhrMaterialsUnlit {}
 
-#[derive(Debug, DeJson, Clone)]
+/// The property an animation channel targets, as a JSON pointer such as
+/// `/materials/0/pbrMetallicRoughness/baseColorFactor`.
+#[cfg(feature = "khr_animation_pointer")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct KhrAnimationPointer {
+    pub pointer: String,
+}
+
+/// A Draco-compressed primitive.
+#[cfg(feature = "khr_draco")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct KhrDracoMeshCompression {
+    /// The buffer view containing the compressed data.
+    #[nserde(rename = "bufferView")]
+    pub buffer_view: usize,
+    /// Maps attribute semantics such as `POSITION` to Draco attribute ids.
+    pub attributes: HashMap<String, u32>,
+}
+
+/// Marks a primitive's triangles as fan-triangulated n-gons.
+#[cfg(feature = "fb_ngon_encoding")]
+#[derive(Debug, SerJson, Clone, Copy)]
+pub struct FbNgonEncoding {}
+
+#[cfg(feature = "fb_ngon_encoding")]
+impl DeJson for FbNgonEncoding {
+    fn de_json(
+        state: &mut nanoserde::DeJsonState,
+        input: &mut core::str::Chars,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        crate::json::skip_object(state, input)?;
+        Ok(Self {})
+    }
+}
+
+#[cfg(feature = "fb_ngon_encoding")]
+impl FbNgonEncoding {
+    /// Reconstruct the polygon faces from a triangle list.
+    ///
+    /// Each n-gon is stored as a fan of consecutive triangles sharing the same first index,
+    /// and neighbouring n-gons are encoded to start with different indices, so a face is a run
+    /// of triangles with the same first index. Trailing indices that don't make up a whole
+    /// triangle are ignored.
+    pub fn faces(indices: &[u32]) -> Vec<Vec<u32>> {
+        let mut faces: Vec<Vec<u32>> = Vec::new();
+
+        for triangle in indices.chunks_exact(3) {
+            match faces.last_mut() {
+                Some(face) if face[0] == triangle[0] => face.push(triangle[2]),
+                _ => faces.push(triangle.to_vec()),
+            }
+        }
+
+        faces
+    }
+}
+
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct KhrMaterialsSpecular<E: Extensions> {
     #[nserde(rename = "specularFactor")]
     #[nserde(default = "1.0")]
@@ -56,7 +155,8 @@ pub struct KhrMaterialsSpecular<E: Extensions> {
     pub specular_color_texture: Option<TextureInfo<E>>,
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct KhrMaterialsTransmission<E: Extensions> {
     #[nserde(rename = "transmissionFactor")]
     #[nserde(default = "1.0")]
@@ -65,24 +165,53 @@ pub struct KhrMaterialsTransmission<E: Extensions> {
     pub transmission_texture: Option<TextureInfo<E>>,
 }
 
-#[derive(Debug, DeJson, Clone)]
+/// Adobe's transmission extension for thin surfaces, which predates
+/// `KHR_materials_transmission`.
+#[cfg(feature = "adobe_materials_thin_transparency")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct AdobeMaterialsThinTransparency<E: Extensions> {
+    #[nserde(rename = "transmissionFactor")]
+    #[nserde(default = "1.0")]
+    pub transmission_factor: f32,
+    /// Transmission is read from the red channel.
+    #[nserde(rename = "transmissionTexture")]
+    pub transmission_texture: Option<TextureInfo<E>>,
+    #[nserde(default = "1.33")]
+    pub ior: f32,
+}
+
+#[cfg(feature = "khr_lights")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct KhrLightsPunctual {
     #[nserde(default)]
     pub lights: Vec<Light>,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+/// A reference from a node to a light in the root `KHR_lights_punctual` extension.
+#[cfg(feature = "khr_lights")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct KhrLightsPunctualNode {
+    pub light: usize,
+}
+
+#[cfg(feature = "khr_lights")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct Light {
+    pub name: Option<String>,
     #[nserde(default = "[1.0, 1.0, 1.0]")]
     pub color: [f32; 3],
     #[nserde(default = "1.0")]
     pub intensity: f32,
     #[nserde(rename = "type")]
     pub ty: LightType,
+    /// The distance at which the light's intensity reaches zero, for point and spot lights.
+    /// Unbounded if unset.
+    pub range: Option<f32>,
     pub spot: Option<LightSpot>,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "khr_lights")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub enum LightType {
     #[nserde(rename = "point")]
     Point,
@@ -92,23 +221,26 @@ pub enum LightType {
     Spot,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "khr_lights")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct LightSpot {
     #[nserde(rename = "innerConeAngle")]
     #[nserde(default)]
     pub inner_cone_angle: f32,
     #[nserde(rename = "outerConeAngle")]
-    #[nserde(default = "std::f32::consts::FRAC_PI_4")]
+    #[nserde(default = "core::f32::consts::FRAC_PI_4")]
     pub outer_cone_angle: f32,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "khr_materials")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct KhrMaterialsIor {
     #[nserde(default = "1.5")]
     pub ior: f32,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "ext_meshopt")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct ExtMeshoptCompression {
     pub buffer: usize,
     #[nserde(rename = "byteOffset")]
@@ -124,7 +256,8 @@ pub struct ExtMeshoptCompression {
     pub filter: CompressionFilter,
 }
 
-#[derive(Debug, DeJson, PartialEq, Eq, Clone, Copy)]
+#[cfg(feature = "ext_meshopt")]
+#[derive(Debug, DeJson, SerJson, PartialEq, Eq, Clone, Copy)]
 pub enum CompressionMode {
     #[nserde(rename = "ATTRIBUTES")]
     Attributes,
@@ -134,9 +267,11 @@ pub enum CompressionMode {
     Indices,
 }
 
-#[derive(Debug, DeJson, PartialEq, Eq, Clone, Copy)]
+#[cfg(feature = "ext_meshopt")]
+#[derive(Debug, DeJson, SerJson, PartialEq, Eq, Clone, Copy, Default)]
 pub enum CompressionFilter {
     #[nserde(rename = "NONE")]
+    #[default]
     None,
     #[nserde(rename = "OCTAHEDRAL")]
     Octahedral,
@@ -146,34 +281,703 @@ pub enum CompressionFilter {
     Exponential,
 }
 
-impl Default for CompressionFilter {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "ext_meshopt")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct ExtMeshoptCompressionBuffer {
     #[nserde(default)]
     pub fallback: bool,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "ext_mesh_gpu_instancing")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct ExtMeshGpuInstancing {
     pub attributes: ExtMeshGpuInstancingAttributes,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[cfg(feature = "ext_mesh_gpu_instancing")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
 pub struct ExtMeshGpuInstancingAttributes {
     #[nserde(rename = "ROTATION")]
-    pub rotation: usize,
+    pub rotation: Option<usize>,
     #[nserde(rename = "SCALE")]
-    pub scale: usize,
+    pub scale: Option<usize>,
     #[nserde(rename = "TRANSLATION")]
-    pub translation: usize,
+    pub translation: Option<usize>,
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[cfg(feature = "msft_lod")]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct MsftLod {
     pub ids: Vec<usize>,
 }
+
+/// The physics shapes referenced by `OMI_physics_body` colliders and triggers.
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct OmiPhysicsShape {
+    #[nserde(default)]
+    pub shapes: Vec<PhysicsShape>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct PhysicsShape {
+    #[nserde(rename = "type")]
+    pub ty: PhysicsShapeType,
+    #[nserde(rename = "box")]
+    pub box_shape: Option<PhysicsBox>,
+    pub sphere: Option<PhysicsSphere>,
+    pub capsule: Option<PhysicsCapsule>,
+    pub cylinder: Option<PhysicsCylinder>,
+    pub convex: Option<PhysicsMeshShape>,
+    pub trimesh: Option<PhysicsMeshShape>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicsShapeType {
+    #[nserde(rename = "box")]
+    Box,
+    #[nserde(rename = "sphere")]
+    Sphere,
+    #[nserde(rename = "capsule")]
+    Capsule,
+    #[nserde(rename = "cylinder")]
+    Cylinder,
+    #[nserde(rename = "convex")]
+    Convex,
+    #[nserde(rename = "trimesh")]
+    Trimesh,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsBox {
+    #[nserde(default = "[1.0, 1.0, 1.0]")]
+    pub size: [f32; 3],
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsSphere {
+    #[nserde(default = "0.5")]
+    pub radius: f32,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsCapsule {
+    #[nserde(default = "0.5")]
+    pub radius: f32,
+    #[nserde(default = "2.0")]
+    pub height: f32,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsCylinder {
+    #[nserde(default = "0.5")]
+    pub radius: f32,
+    #[nserde(default = "2.0")]
+    pub height: f32,
+}
+
+/// A convex hull or triangle mesh shape built from a mesh.
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsMeshShape {
+    pub mesh: usize,
+}
+
+/// Physics properties of a node.
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct OmiPhysicsBody {
+    pub motion: Option<PhysicsMotion>,
+    pub collider: Option<PhysicsCollider>,
+    pub trigger: Option<PhysicsTrigger>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsMotion {
+    #[nserde(rename = "type")]
+    pub ty: PhysicsMotionType,
+    #[nserde(default = "1.0")]
+    pub mass: f32,
+    #[nserde(rename = "linearVelocity")]
+    #[nserde(default)]
+    pub linear_velocity: [f32; 3],
+    #[nserde(rename = "angularVelocity")]
+    #[nserde(default)]
+    pub angular_velocity: [f32; 3],
+    #[nserde(rename = "centerOfMass")]
+    #[nserde(default)]
+    pub center_of_mass: [f32; 3],
+    /// If not set, the inertia should be calculated from the colliders.
+    #[nserde(rename = "inertiaDiagonal")]
+    pub inertia_diagonal: Option<[f32; 3]>,
+    #[nserde(rename = "inertiaOrientation")]
+    pub inertia_orientation: Option<[f32; 4]>,
+    #[nserde(rename = "gravityFactor")]
+    #[nserde(default = "1.0")]
+    pub gravity_factor: f32,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicsMotionType {
+    #[nserde(rename = "static")]
+    Static,
+    #[nserde(rename = "kinematic")]
+    Kinematic,
+    #[nserde(rename = "dynamic")]
+    Dynamic,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct PhysicsCollider {
+    /// An index into [`OmiPhysicsShape::shapes`].
+    pub shape: Option<usize>,
+    #[nserde(rename = "physicsMaterial")]
+    pub physics_material: Option<usize>,
+    #[nserde(rename = "collisionFilter")]
+    pub collision_filter: Option<usize>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct PhysicsTrigger {
+    /// An index into [`OmiPhysicsShape::shapes`].
+    pub shape: Option<usize>,
+    /// Child nodes whose shapes make up a compound trigger.
+    pub nodes: Option<Vec<usize>>,
+    #[nserde(rename = "collisionFilter")]
+    pub collision_filter: Option<usize>,
+}
+
+/// The colliders of the older `OMI_collider` extension, which has since been split into
+/// `OMI_physics_shape` and `OMI_physics_body`.
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct OmiCollider {
+    #[nserde(default)]
+    pub colliders: Vec<Collider>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct Collider {
+    #[nserde(rename = "type")]
+    pub ty: ColliderType,
+    #[nserde(rename = "isTrigger")]
+    #[nserde(default)]
+    pub is_trigger: bool,
+    pub size: Option<[f32; 3]>,
+    pub radius: Option<f32>,
+    pub height: Option<f32>,
+    pub mesh: Option<usize>,
+}
+
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
+pub enum ColliderType {
+    #[nserde(rename = "box")]
+    Box,
+    #[nserde(rename = "sphere")]
+    Sphere,
+    #[nserde(rename = "capsule")]
+    Capsule,
+    #[nserde(rename = "hull")]
+    Hull,
+    #[nserde(rename = "trimesh")]
+    Trimesh,
+    #[nserde(rename = "compound")]
+    Compound,
+}
+
+/// A node's reference to an `OMI_collider` collider.
+#[cfg(feature = "omi")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct OmiColliderNode {
+    /// An index into [`OmiCollider::colliders`].
+    pub collider: usize,
+}
+
+/// A VRM 1.0 humanoid avatar.
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmcVrm {
+    #[nserde(rename = "specVersion")]
+    pub spec_version: String,
+    pub meta: VrmMeta,
+    pub humanoid: VrmHumanoid,
+    #[nserde(rename = "firstPerson")]
+    pub first_person: Option<VrmFirstPerson>,
+    #[nserde(rename = "lookAt")]
+    pub look_at: Option<VrmLookAt>,
+    pub expressions: Option<VrmExpressions>,
+}
+
+/// Information about the avatar and its license.
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmMeta {
+    pub name: String,
+    pub version: Option<String>,
+    #[nserde(default)]
+    pub authors: Vec<String>,
+    #[nserde(rename = "copyrightInformation")]
+    pub copyright_information: Option<String>,
+    #[nserde(rename = "contactInformation")]
+    pub contact_information: Option<String>,
+    #[nserde(default)]
+    pub references: Vec<String>,
+    #[nserde(rename = "thirdPartyLicenses")]
+    pub third_party_licenses: Option<String>,
+    /// An image index.
+    #[nserde(rename = "thumbnailImage")]
+    pub thumbnail_image: Option<usize>,
+    #[nserde(rename = "licenseUrl")]
+    pub license_url: String,
+    #[nserde(rename = "avatarPermission")]
+    #[nserde(default)]
+    pub avatar_permission: VrmAvatarPermission,
+    #[nserde(rename = "allowExcessivelyViolentUsage")]
+    #[nserde(default)]
+    pub allow_excessively_violent_usage: bool,
+    #[nserde(rename = "allowExcessivelySexualUsage")]
+    #[nserde(default)]
+    pub allow_excessively_sexual_usage: bool,
+    #[nserde(rename = "commercialUsage")]
+    #[nserde(default)]
+    pub commercial_usage: VrmCommercialUsage,
+    #[nserde(rename = "allowPoliticalOrReligiousUsage")]
+    #[nserde(default)]
+    pub allow_political_or_religious_usage: bool,
+    #[nserde(rename = "allowAntisocialOrHateUsage")]
+    #[nserde(default)]
+    pub allow_antisocial_or_hate_usage: bool,
+    #[nserde(rename = "creditNotation")]
+    #[nserde(default)]
+    pub credit_notation: VrmCreditNotation,
+    #[nserde(rename = "allowRedistribution")]
+    #[nserde(default)]
+    pub allow_redistribution: bool,
+    #[nserde(default)]
+    pub modification: VrmModification,
+    #[nserde(rename = "otherLicenseUrl")]
+    pub other_license_url: Option<String>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmAvatarPermission {
+    #[nserde(rename = "onlyAuthor")]
+    #[default]
+    OnlyAuthor,
+    #[nserde(rename = "onlySeparatelyLicensedPerson")]
+    OnlySeparatelyLicensedPerson,
+    #[nserde(rename = "everyone")]
+    Everyone,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmCommercialUsage {
+    #[nserde(rename = "personalNonProfit")]
+    #[default]
+    PersonalNonProfit,
+    #[nserde(rename = "personalProfit")]
+    PersonalProfit,
+    #[nserde(rename = "corporation")]
+    Corporation,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmCreditNotation {
+    #[nserde(rename = "required")]
+    #[default]
+    Required,
+    #[nserde(rename = "unnecessary")]
+    Unnecessary,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmModification {
+    #[nserde(rename = "prohibited")]
+    #[default]
+    Prohibited,
+    #[nserde(rename = "allowModification")]
+    AllowModification,
+    #[nserde(rename = "allowModificationRedistribution")]
+    AllowModificationRedistribution,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmHumanoid {
+    /// Maps bone names such as `hips` or `leftUpperArm` to nodes.
+    #[nserde(rename = "humanBones")]
+    pub human_bones: HashMap<String, VrmHumanBone>,
+}
+
+#[cfg(feature = "vrm")]
+impl VrmHumanoid {
+    /// The node for a bone, e.g. `hips`.
+    pub fn bone(&self, name: &str) -> Option<usize> {
+        self.human_bones.get(name).map(|bone| bone.node)
+    }
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmHumanBone {
+    pub node: usize,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmFirstPerson {
+    #[nserde(rename = "meshAnnotations")]
+    #[nserde(default)]
+    pub mesh_annotations: Vec<VrmMeshAnnotation>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmMeshAnnotation {
+    pub node: usize,
+    #[nserde(rename = "type")]
+    pub ty: VrmFirstPersonType,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
+pub enum VrmFirstPersonType {
+    #[nserde(rename = "auto")]
+    Auto,
+    #[nserde(rename = "both")]
+    Both,
+    #[nserde(rename = "thirdPersonOnly")]
+    ThirdPersonOnly,
+    #[nserde(rename = "firstPersonOnly")]
+    FirstPersonOnly,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmLookAt {
+    #[nserde(rename = "offsetFromHeadBone")]
+    #[nserde(default)]
+    pub offset_from_head_bone: [f32; 3],
+    #[nserde(rename = "type")]
+    #[nserde(default)]
+    pub ty: VrmLookAtType,
+    #[nserde(rename = "rangeMapHorizontalInner")]
+    pub range_map_horizontal_inner: Option<VrmRangeMap>,
+    #[nserde(rename = "rangeMapHorizontalOuter")]
+    pub range_map_horizontal_outer: Option<VrmRangeMap>,
+    #[nserde(rename = "rangeMapVerticalDown")]
+    pub range_map_vertical_down: Option<VrmRangeMap>,
+    #[nserde(rename = "rangeMapVerticalUp")]
+    pub range_map_vertical_up: Option<VrmRangeMap>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmLookAtType {
+    #[nserde(rename = "bone")]
+    #[default]
+    Bone,
+    #[nserde(rename = "expression")]
+    Expression,
+}
+
+/// Maps a look-at angle in degrees to a bone rotation or expression weight.
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmRangeMap {
+    #[nserde(rename = "inputMaxValue")]
+    #[nserde(default = "90.0")]
+    pub input_max_value: f32,
+    #[nserde(rename = "outputScale")]
+    #[nserde(default = "10.0")]
+    pub output_scale: f32,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Default)]
+pub struct VrmExpressions {
+    /// Preset expressions keyed by name, e.g. `happy`, `aa` or `blinkLeft`.
+    #[nserde(default)]
+    pub preset: HashMap<String, VrmExpression>,
+    #[nserde(default)]
+    pub custom: HashMap<String, VrmExpression>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmExpression {
+    #[nserde(rename = "morphTargetBinds")]
+    #[nserde(default)]
+    pub morph_target_binds: Vec<VrmMorphTargetBind>,
+    #[nserde(rename = "materialColorBinds")]
+    #[nserde(default)]
+    pub material_color_binds: Vec<VrmMaterialColorBind>,
+    #[nserde(rename = "textureTransformBinds")]
+    #[nserde(default)]
+    pub texture_transform_binds: Vec<VrmTextureTransformBind>,
+    #[nserde(rename = "isBinary")]
+    #[nserde(default)]
+    pub is_binary: bool,
+    #[nserde(rename = "overrideBlink")]
+    #[nserde(default)]
+    pub override_blink: VrmExpressionOverride,
+    #[nserde(rename = "overrideLookAt")]
+    #[nserde(default)]
+    pub override_look_at: VrmExpressionOverride,
+    #[nserde(rename = "overrideMouth")]
+    #[nserde(default)]
+    pub override_mouth: VrmExpressionOverride,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmMorphTargetBind {
+    pub node: usize,
+    /// The morph target index.
+    pub index: usize,
+    pub weight: f32,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmMaterialColorBind {
+    pub material: usize,
+    #[nserde(rename = "type")]
+    pub ty: VrmMaterialColorType,
+    #[nserde(rename = "targetValue")]
+    pub target_value: [f32; 4],
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
+pub enum VrmMaterialColorType {
+    #[nserde(rename = "color")]
+    Color,
+    #[nserde(rename = "emissionColor")]
+    EmissionColor,
+    #[nserde(rename = "shadeColor")]
+    ShadeColor,
+    #[nserde(rename = "matcapColor")]
+    MatcapColor,
+    #[nserde(rename = "rimColor")]
+    RimColor,
+    #[nserde(rename = "outlineColor")]
+    OutlineColor,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmTextureTransformBind {
+    pub material: usize,
+    #[nserde(default = "[1.0, 1.0]")]
+    pub scale: [f32; 2],
+    #[nserde(default)]
+    pub offset: [f32; 2],
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VrmExpressionOverride {
+    #[nserde(rename = "none")]
+    #[default]
+    None,
+    #[nserde(rename = "block")]
+    Block,
+    #[nserde(rename = "blend")]
+    Blend,
+}
+
+/// VRM spring bones, for secondary motion such as hair and clothing.
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmcSpringBone {
+    #[nserde(rename = "specVersion")]
+    pub spec_version: String,
+    #[nserde(default)]
+    pub colliders: Vec<VrmSpringBoneCollider>,
+    #[nserde(rename = "colliderGroups")]
+    #[nserde(default)]
+    pub collider_groups: Vec<VrmColliderGroup>,
+    #[nserde(default)]
+    pub springs: Vec<VrmSpring>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmSpringBoneCollider {
+    pub node: usize,
+    pub shape: VrmColliderShape,
+}
+
+/// Exactly one of the shapes should be set.
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmColliderShape {
+    pub sphere: Option<VrmColliderSphere>,
+    pub capsule: Option<VrmColliderCapsule>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmColliderSphere {
+    #[nserde(default)]
+    pub offset: [f32; 3],
+    #[nserde(default)]
+    pub radius: f32,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmColliderCapsule {
+    #[nserde(default)]
+    pub offset: [f32; 3],
+    #[nserde(default)]
+    pub radius: f32,
+    #[nserde(default)]
+    pub tail: [f32; 3],
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmColliderGroup {
+    pub name: Option<String>,
+    /// Indices into [`VrmcSpringBone::colliders`].
+    pub colliders: Vec<usize>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct VrmSpring {
+    pub name: Option<String>,
+    /// The chain of joints, from the root outwards.
+    pub joints: Vec<VrmSpringJoint>,
+    /// Indices into [`VrmcSpringBone::collider_groups`].
+    #[nserde(rename = "colliderGroups")]
+    #[nserde(default)]
+    pub collider_groups: Vec<usize>,
+    /// A node whose space the simulation runs in.
+    pub center: Option<usize>,
+}
+
+#[cfg(feature = "vrm")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct VrmSpringJoint {
+    pub node: usize,
+    #[nserde(rename = "hitRadius")]
+    #[nserde(default)]
+    pub hit_radius: f32,
+    #[nserde(default = "1.0")]
+    pub stiffness: f32,
+    #[nserde(rename = "gravityPower")]
+    #[nserde(default)]
+    pub gravity_power: f32,
+    #[nserde(rename = "gravityDir")]
+    #[nserde(default = "[0.0, -1.0, 0.0]")]
+    pub gravity_dir: [f32; 3],
+    #[nserde(rename = "dragForce")]
+    #[nserde(default = "0.5")]
+    pub drag_force: f32,
+}
+
+/// Timed media, such as videos, referenced by other MPEG extensions.
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct MpegMedia {
+    #[nserde(default)]
+    pub media: Vec<Media>,
+}
+
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct Media {
+    pub name: Option<String>,
+    /// Seconds from the start of the scene until the media starts playing.
+    #[nserde(rename = "startTime")]
+    #[nserde(default)]
+    pub start_time: f32,
+    #[nserde(rename = "startTimeOffset")]
+    #[nserde(default)]
+    pub start_time_offset: f32,
+    #[nserde(rename = "endTimeOffset")]
+    pub end_time_offset: Option<f32>,
+    #[nserde(default = "true")]
+    pub autoplay: bool,
+    #[nserde(rename = "autoplayGroup")]
+    pub autoplay_group: Option<String>,
+    #[nserde(rename = "loop")]
+    #[nserde(default)]
+    pub looping: bool,
+    #[nserde(default)]
+    pub controls: bool,
+    /// Alternative sources for the same media, e.g. with different codecs.
+    pub alternatives: Vec<MediaAlternative>,
+}
+
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct MediaAlternative {
+    #[nserde(rename = "mimeType")]
+    pub mime_type: String,
+    pub uri: String,
+    #[nserde(default)]
+    pub tracks: Vec<MediaTrack>,
+}
+
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct MediaTrack {
+    /// A URL fragment selecting the track within the source.
+    pub track: String,
+    pub codecs: Option<String>,
+}
+
+/// A texture whose contents come from a video, decoded into a timed accessor.
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy)]
+pub struct MpegTextureVideo {
+    pub accessor: usize,
+    pub width: u32,
+    pub height: u32,
+    #[nserde(default)]
+    pub format: VideoFormat,
+}
+
+#[cfg(feature = "mpeg")]
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VideoFormat {
+    #[nserde(rename = "RED")]
+    Red,
+    #[nserde(rename = "GREEN")]
+    Green,
+    #[nserde(rename = "BLUE")]
+    Blue,
+    #[nserde(rename = "RG")]
+    Rg,
+    #[nserde(rename = "RGB")]
+    #[default]
+    Rgb,
+    #[nserde(rename = "RGBA")]
+    Rgba,
+    #[nserde(rename = "BGR")]
+    Bgr,
+    #[nserde(rename = "BGRA")]
+    Bgra,
+    #[nserde(rename = "DEPTH_COMPONENT")]
+    DepthComponent,
+}
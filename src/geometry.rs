@@ -0,0 +1,359 @@
+use crate::math;
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+
+const MAX_EXAMPLES: usize = 8;
+
+/// Decoded vertex data for a single triangle-list primitive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrimitiveGeometry<'a> {
+    pub positions: &'a [[f32; 3]],
+    /// If `None`, the vertices are used in order.
+    pub indices: Option<&'a [u32]>,
+    pub normals: Option<&'a [[f32; 3]]>,
+    pub tangents: Option<&'a [[f32; 4]]>,
+    pub uvs: Option<&'a [[f32; 2]]>,
+}
+
+impl<'a> PrimitiveGeometry<'a> {
+    fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        let count = match self.indices {
+            Some(indices) => indices.len() / 3,
+            None => self.positions.len() / 3,
+        };
+
+        (0..count).map(move |triangle| {
+            core::array::from_fn(|i| match self.indices {
+                Some(indices) => indices[triangle * 3 + i] as usize,
+                None => triangle * 3 + i,
+            })
+        })
+    }
+}
+
+/// The number of occurrences of a problem, along with the first few indices at which it occurs.
+#[derive(Debug, Clone, Default)]
+pub struct Occurrences {
+    pub count: usize,
+    pub examples: Vec<usize>,
+}
+
+impl Occurrences {
+    fn push(&mut self, index: usize) {
+        if self.examples.len() < MAX_EXAMPLES {
+            self.examples.push(index);
+        }
+        self.count += 1;
+    }
+}
+
+/// Problems found in a primitive's geometry.
+///
+/// Triangle problems are reported by triangle index, vertex problems by vertex index.
+#[derive(Debug, Clone, Default)]
+pub struct DegenerateReport {
+    /// Triangles that repeat a vertex or have zero area.
+    pub degenerate_triangles: Occurrences,
+    /// Triangles that reference vertices that don't exist.
+    pub out_of_range_triangles: Occurrences,
+    /// Triangles whose texture coordinates have zero area.
+    pub zero_area_uvs: Occurrences,
+    pub zero_length_normals: Occurrences,
+    pub zero_length_tangents: Occurrences,
+    /// Vertices with a NaN or infinite value in any attribute.
+    pub non_finite_vertices: Occurrences,
+}
+
+impl DegenerateReport {
+    pub fn is_clean(&self) -> bool {
+        self.degenerate_triangles.count == 0
+            && self.out_of_range_triangles.count == 0
+            && self.zero_area_uvs.count == 0
+            && self.zero_length_normals.count == 0
+            && self.zero_length_tangents.count == 0
+            && self.non_finite_vertices.count == 0
+    }
+}
+
+fn is_finite<const N: usize>(attribute: Option<&[[f32; N]]>, vertex: usize) -> bool {
+    match attribute.and_then(|values| values.get(vertex)) {
+        Some(value) => value.iter().all(|v| v.is_finite()),
+        None => true,
+    }
+}
+
+/// Scan a primitive for degenerate or broken geometry.
+pub fn find_degenerates(geometry: &PrimitiveGeometry) -> DegenerateReport {
+    let mut report = DegenerateReport::default();
+
+    for vertex in 0..geometry.positions.len() {
+        let finite = geometry.positions[vertex].iter().all(|v| v.is_finite())
+            && is_finite(geometry.normals, vertex)
+            && is_finite(geometry.tangents, vertex)
+            && is_finite(geometry.uvs, vertex);
+
+        if !finite {
+            report.non_finite_vertices.push(vertex);
+        }
+    }
+
+    for (vertex, &normal) in geometry.normals.unwrap_or(&[]).iter().enumerate() {
+        if math::dot3(normal, normal) <= f32::EPSILON {
+            report.zero_length_normals.push(vertex);
+        }
+    }
+
+    for (vertex, tangent) in geometry.tangents.unwrap_or(&[]).iter().enumerate() {
+        let xyz = [tangent[0], tangent[1], tangent[2]];
+        if math::dot3(xyz, xyz) <= f32::EPSILON {
+            report.zero_length_tangents.push(vertex);
+        }
+    }
+
+    for (triangle, [a, b, c]) in geometry.triangles().enumerate() {
+        let normal = match triangle_normal(geometry, [a, b, c]) {
+            Some(normal) => normal,
+            None => {
+                report.out_of_range_triangles.push(triangle);
+                continue;
+            }
+        };
+
+        if a == b || b == c || a == c || math::dot3(normal, normal) <= f32::EPSILON * f32::EPSILON {
+            report.degenerate_triangles.push(triangle);
+        }
+
+        if let Some(uvs) = geometry.uvs {
+            if let (Some(ua), Some(ub), Some(uc)) = (uvs.get(a), uvs.get(b), uvs.get(c)) {
+                let area = (ub[0] - ua[0]) * (uc[1] - ua[1]) - (uc[0] - ua[0]) * (ub[1] - ua[1]);
+
+                if area.abs() <= f32::EPSILON * f32::EPSILON {
+                    report.zero_area_uvs.push(triangle);
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Indices that use every vertex in order, for primitives without an index accessor.
+pub fn sequential_indices(vertex_count: usize) -> Vec<u32> {
+    (0..vertex_count as u32).collect()
+}
+
+impl crate::PrimitiveMode {
+    /// The mode of the indices produced by [`to_list_indices`]. Unknown modes are returned
+    /// unchanged.
+    pub fn list_mode(self) -> Self {
+        match self {
+            Self::Points => Self::Points,
+            Self::Lines | Self::LineLoop | Self::LineStrip => Self::Lines,
+            Self::Triangles | Self::TriangleStrip | Self::TriangleFan => Self::Triangles,
+            Self::Unknown(_) => self,
+        }
+    }
+}
+
+/// Convert indices in any mode into a point, line or triangle list, as given by
+/// [`PrimitiveMode::list_mode`](crate::PrimitiveMode::list_mode).
+///
+/// Triangle strips keep a consistent winding order, and the degenerate triangles that are used to
+/// join strips together are removed. Incomplete trailing lines and triangles are ignored, and
+/// unknown modes produce no indices.
+pub fn to_list_indices(mode: crate::PrimitiveMode, indices: &[u32]) -> Vec<u32> {
+    use crate::PrimitiveMode;
+
+    match mode {
+        PrimitiveMode::Points => indices.to_vec(),
+        PrimitiveMode::Lines => indices[..indices.len() - indices.len() % 2].to_vec(),
+        PrimitiveMode::Triangles => indices[..indices.len() - indices.len() % 3].to_vec(),
+        PrimitiveMode::LineStrip => indices.windows(2).flatten().copied().collect(),
+        PrimitiveMode::LineLoop => {
+            let mut lines: Vec<u32> = indices.windows(2).flatten().copied().collect();
+
+            if let (Some(&first), Some(&last)) = (indices.first(), indices.last()) {
+                if indices.len() > 2 {
+                    lines.extend([last, first]);
+                }
+            }
+
+            lines
+        }
+        PrimitiveMode::TriangleStrip => indices
+            .windows(3)
+            .enumerate()
+            .map(|(i, window)| {
+                if i % 2 == 0 {
+                    [window[0], window[1], window[2]]
+                } else {
+                    [window[1], window[0], window[2]]
+                }
+            })
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .flatten()
+            .collect(),
+        PrimitiveMode::TriangleFan => match indices.split_first() {
+            Some((&first, rest)) => rest
+                .windows(2)
+                .flat_map(|window| [window[0], window[1], first])
+                .collect(),
+            None => Vec::new(),
+        },
+        PrimitiveMode::Unknown(_) => Vec::new(),
+    }
+}
+
+fn triangle_normal(geometry: &PrimitiveGeometry, [a, b, c]: [usize; 3]) -> Option<[f32; 3]> {
+    let (pa, pb, pc) = (
+        geometry.positions.get(a)?,
+        geometry.positions.get(b)?,
+        geometry.positions.get(c)?,
+    );
+
+    let edge_1 = core::array::from_fn(|i| pb[i] - pa[i]);
+    let edge_2 = core::array::from_fn(|i| pc[i] - pa[i]);
+    Some(math::cross(edge_1, edge_2))
+}
+
+/// A primitive with its vertices split up so that every triangle has its own three, as needed
+/// for flat shading.
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Clone, Default)]
+pub struct FlatGeometry {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// The original vertex of each new vertex, for splitting up the other attributes.
+    pub source_vertices: Vec<u32>,
+}
+
+/// Compute the flat normals that the spec requires for triangle primitives without `NORMAL`.
+///
+/// Triangles that reference vertices out of range are skipped, and degenerate triangles get a
+/// zero normal.
+#[cfg(not(feature = "no_std"))]
+pub fn flat_normals(geometry: &PrimitiveGeometry) -> FlatGeometry {
+    let mut flat = FlatGeometry::default();
+
+    for triangle in geometry.triangles() {
+        let normal = match triangle_normal(geometry, triangle) {
+            Some(normal) => math::normalize3(normal),
+            None => continue,
+        };
+
+        for vertex in triangle {
+            flat.positions.push(geometry.positions[vertex]);
+            flat.normals.push(normal);
+            flat.source_vertices.push(vertex as u32);
+        }
+    }
+
+    flat
+}
+
+/// Compute a smooth normal for each vertex by averaging the normals of the triangles that use it,
+/// weighted by their area.
+///
+/// This keeps the vertex stream as is, but doesn't match the flat shading that the spec requires
+/// for primitives without `NORMAL`. Vertices that aren't part of any triangle get a zero normal.
+#[cfg(not(feature = "no_std"))]
+pub fn smooth_normals(geometry: &PrimitiveGeometry) -> Vec<[f32; 3]> {
+    let mut normals = vec![[0.0; 3]; geometry.positions.len()];
+
+    for triangle in geometry.triangles() {
+        let normal = match triangle_normal(geometry, triangle) {
+            Some(normal) => normal,
+            None => continue,
+        };
+
+        for vertex in triangle {
+            for i in 0..3 {
+                normals[vertex][i] += normal[i];
+            }
+        }
+    }
+
+    normals.into_iter().map(math::normalize3).collect()
+}
+
+#[cfg(feature = "mikktspace")]
+struct TangentGeometry<'a> {
+    geometry: &'a PrimitiveGeometry<'a>,
+    normals: &'a [[f32; 3]],
+    uvs: &'a [[f32; 2]],
+    triangles: Vec<[usize; 3]>,
+    tangents: Vec<[f32; 4]>,
+}
+
+#[cfg(feature = "mikktspace")]
+impl<'a> mikktspace::Geometry for TangentGeometry<'a> {
+    fn num_faces(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.geometry.positions[self.triangles[face][vert]]
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.normals[self.triangles[face][vert]]
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.uvs[self.triangles[face][vert]]
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        self.tangents[self.triangles[face][vert]] = tangent;
+    }
+}
+
+/// Generate `TANGENT` data with the MikkTSpace algorithm, as the spec recommends for
+/// normal-mapped primitives without tangents. `uvs` should be the texture coordinates that the
+/// normal texture uses.
+///
+/// Returns `None` if the geometry doesn't have normals and uvs for every vertex, if any triangle
+/// references a vertex out of range, or if generation fails. MikkTSpace can give a vertex a
+/// different tangent for each triangle that shares it, in which case the last one is kept, so
+/// flat geometry (see [`flat_normals`]) gives the most accurate results.
+#[cfg(feature = "mikktspace")]
+pub fn generate_tangents(geometry: &PrimitiveGeometry) -> Option<Vec<[f32; 4]>> {
+    let vertex_count = geometry.positions.len();
+
+    let (normals, uvs) = match (geometry.normals, geometry.uvs) {
+        (Some(normals), Some(uvs))
+            if normals.len() >= vertex_count && uvs.len() >= vertex_count =>
+        {
+            (normals, uvs)
+        }
+        _ => return None,
+    };
+
+    let triangles: Vec<[usize; 3]> = geometry.triangles().collect();
+
+    if triangles
+        .iter()
+        .flatten()
+        .any(|&vertex| vertex >= vertex_count)
+    {
+        return None;
+    }
+
+    let mut tangent_geometry = TangentGeometry {
+        geometry,
+        normals,
+        uvs,
+        triangles,
+        tangents: vec![[1.0, 0.0, 0.0, 1.0]; vertex_count],
+    };
+
+    if !mikktspace::generate_tangents(&mut tangent_geometry) {
+        return None;
+    }
+
+    Some(tangent_geometry.tangents)
+}
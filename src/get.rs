@@ -0,0 +1,61 @@
+use crate::*;
+
+/// An index that's out of range of the array it indexes, returned by the `try_` getters on
+/// [`Gltf`], e.g. [`Gltf::try_node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    /// The array that was indexed, e.g. `nodes`.
+    pub array: &'static str,
+    pub index: usize,
+    pub len: usize,
+}
+
+impl core::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{}[{}] is out of range, as there are only {}",
+            self.array, self.index, self.len
+        )
+    }
+}
+
+impl core::error::Error for IndexError {}
+
+macro_rules! getters {
+    ($($field:ident, $ty:ident, $get:ident, $try_get:ident;)*) => {
+        impl<E: Extensions> Gltf<E> {
+            $(
+                pub fn $get(&self, index: usize) -> Option<&$ty<E>> {
+                    self.$field.get(index)
+                }
+
+                #[doc = concat!("Like [`", stringify!($get), "`](Self::", stringify!($get), "), but with an error naming the index if it's out of range.")]
+                pub fn $try_get(&self, index: usize) -> Result<&$ty<E>, IndexError> {
+                    self.$field.get(index).ok_or(IndexError {
+                        array: stringify!($field),
+                        index,
+                        len: self.$field.len(),
+                    })
+                }
+            )*
+        }
+    };
+}
+
+getters! {
+    accessors, Accessor, accessor, try_accessor;
+    animations, Animation, animation, try_animation;
+    buffers, Buffer, buffer, try_buffer;
+    buffer_views, BufferView, buffer_view, try_buffer_view;
+    cameras, Camera, camera, try_camera;
+    images, Image, image, try_image;
+    materials, Material, material, try_material;
+    meshes, Mesh, mesh, try_mesh;
+    nodes, Node, node, try_node;
+    samplers, Sampler, sampler, try_sampler;
+    // Not `scene`, which would read as the default scene index field.
+    scenes, Scene, scene_at, try_scene_at;
+    skins, Skin, skin, try_skin;
+    textures, Texture, texture, try_texture;
+}
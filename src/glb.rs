@@ -0,0 +1,139 @@
+use nanoserde::DeJsonErr;
+
+/// Why a file couldn't be loaded by [`Gltf::from_bytes`](crate::Gltf::from_bytes).
+#[derive(Debug)]
+pub enum GlbError {
+    /// The file starts with the `glTF` magic, but is too short for the .glb header.
+    TruncatedHeader,
+    /// The .glb container version isn't 2.
+    UnsupportedVersion(u32),
+    /// The first chunk isn't a JSON chunk.
+    MissingJsonChunk,
+    /// A chunk, starting at `offset` with `length` bytes of data, extends past the end of the
+    /// file.
+    ChunkOutOfBounds { offset: usize, length: usize },
+    /// The JSON is invalid or isn't a valid document.
+    Json(DeJsonErr),
+    /// A .glb file of this many bytes can't be written, as the header stores lengths in 32 bits.
+    TooLarge(usize),
+    /// A binary chunk was given to write, but the first buffer has a uri or there are no
+    /// buffers, so nothing refers to it.
+    UnreferencedBinaryChunk,
+}
+
+impl core::fmt::Display for GlbError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::TruncatedHeader => f.write_str("glb file is too short for its header"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "glb container version {} is unsupported", version)
+            }
+            Self::MissingJsonChunk => f.write_str("glb file doesn't start with a JSON chunk"),
+            Self::ChunkOutOfBounds { offset, length } => write!(
+                f,
+                "glb chunk at offset {} with length {} extends past the end of the file",
+                offset, length
+            ),
+            Self::Json(error) => error.fmt(f),
+            Self::TooLarge(length) => write!(
+                f,
+                "glb file of {} bytes is too large for its 32-bit length",
+                length
+            ),
+            Self::UnreferencedBinaryChunk => {
+                f.write_str("glb binary chunk isn't referred to by a buffer without a uri")
+            }
+        }
+    }
+}
+
+impl core::error::Error for GlbError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<DeJsonErr> for GlbError {
+    fn from(error: DeJsonErr) -> Self {
+        Self::Json(error)
+    }
+}
+
+const JSON_CHUNK: u32 = 0x4E4F534A;
+const BIN_CHUNK: u32 = 0x004E4942;
+
+struct Chunk<'a> {
+    ty: u32,
+    data: &'a [u8],
+    // The offset just past the chunk, where the next one starts.
+    end: usize,
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+// Returns `None` if there aren't any bytes left for another chunk.
+fn read_chunk(bytes: &[u8], offset: usize) -> Result<Option<Chunk<'_>>, GlbError> {
+    if offset >= bytes.len() {
+        return Ok(None);
+    }
+
+    let (length, ty) = match (read_u32(bytes, offset), read_u32(bytes, offset + 4)) {
+        (Some(length), Some(ty)) => (length as usize, ty),
+        _ => return Err(GlbError::ChunkOutOfBounds { offset, length: 0 }),
+    };
+
+    // `offset` is in bounds, so only the length can overflow.
+    let end = length
+        .checked_add(8)
+        .and_then(|length| offset.checked_add(length))
+        .ok_or(GlbError::ChunkOutOfBounds { offset, length })?;
+    let data = bytes
+        .get(offset + 8..end)
+        .ok_or(GlbError::ChunkOutOfBounds { offset, length })?;
+
+    Ok(Some(Chunk { ty, data, end }))
+}
+
+/// Split a file into its json and binary buffer chunks. Non-glb files are treated as pure json.
+///
+/// Chunks of unknown types are skipped, as the spec requires.
+pub fn split_chunks(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), GlbError> {
+    // Check for the 4-byte magic.
+    if !bytes.starts_with(b"glTF") {
+        return Ok((bytes, None));
+    }
+
+    let version = read_u32(bytes, 4).ok_or(GlbError::TruncatedHeader)?;
+
+    if version != 2 {
+        return Err(GlbError::UnsupportedVersion(version));
+    }
+
+    if bytes.len() < 12 {
+        return Err(GlbError::TruncatedHeader);
+    }
+
+    // There's always a json chunk at the start:
+    // https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#structured-json-content
+    let (json, mut offset) = match read_chunk(bytes, 12)? {
+        Some(chunk) if chunk.ty == JSON_CHUNK => (chunk.data, chunk.end),
+        _ => return Err(GlbError::MissingJsonChunk),
+    };
+
+    while let Some(chunk) = read_chunk(bytes, offset)? {
+        if chunk.ty == BIN_CHUNK {
+            return Ok((json, Some(chunk.data)));
+        }
+
+        offset = chunk.end;
+    }
+
+    Ok((json, None))
+}
@@ -0,0 +1,288 @@
+use crate::primitive_reader::{
+    component_byte_offset, element_size, Component, DracoCompressionExtension, Error, Indices,
+    MeshOptCompressionExtension, PrimitiveReader,
+};
+use crate::{ComponentType, Extensions};
+use std::borrow::Cow;
+
+/// The format of an attribute in an interleaved vertex buffer.
+///
+/// Integer formats take the values as they're stored, while the normalized formats take the
+/// normalized values and clamp them into range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexFormat {
+    Float32,
+    Float32x2,
+    Float32x3,
+    Float32x4,
+    Unorm8x4,
+    Snorm8x4,
+    Unorm16x2,
+    Unorm16x4,
+    Snorm16x2,
+    Snorm16x4,
+    Uint8x4,
+    Uint16x4,
+    Uint32,
+    Uint32x4,
+}
+
+impl VertexFormat {
+    pub fn components(self) -> usize {
+        match self {
+            Self::Float32 | Self::Uint32 => 1,
+            Self::Float32x2 | Self::Unorm16x2 | Self::Snorm16x2 => 2,
+            Self::Float32x3 => 3,
+            _ => 4,
+        }
+    }
+
+    pub fn component_size(self) -> usize {
+        match self {
+            Self::Unorm8x4 | Self::Snorm8x4 | Self::Uint8x4 => 1,
+            Self::Unorm16x2 | Self::Unorm16x4 | Self::Snorm16x2 | Self::Snorm16x4 => 2,
+            Self::Uint16x4 => 2,
+            _ => 4,
+        }
+    }
+
+    pub fn byte_size(self) -> usize {
+        self.components() * self.component_size()
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(
+            self,
+            Self::Uint8x4 | Self::Uint16x4 | Self::Uint32 | Self::Uint32x4
+        )
+    }
+
+    fn write_float(self, value: f32, out: &mut [u8]) {
+        match self {
+            Self::Unorm8x4 => out[0] = (value.clamp(0.0, 1.0) * 255.0).round() as u8,
+            Self::Snorm8x4 => out[0] = (value.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8,
+            Self::Unorm16x2 | Self::Unorm16x4 => out
+                .copy_from_slice(&((value.clamp(0.0, 1.0) * 65535.0).round() as u16).to_le_bytes()),
+            Self::Snorm16x2 | Self::Snorm16x4 => out.copy_from_slice(
+                &((value.clamp(-1.0, 1.0) * 32767.0).round() as i16).to_le_bytes(),
+            ),
+            _ => out.copy_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    fn write_integer(self, value: u32, out: &mut [u8]) {
+        match self {
+            Self::Uint8x4 => out[0] = value as u8,
+            Self::Uint16x4 => out.copy_from_slice(&(value as u16).to_le_bytes()),
+            _ => out.copy_from_slice(&value.to_le_bytes()),
+        }
+    }
+}
+
+/// An attribute in a [`VertexLayout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// The semantic of the primitive attribute to read, e.g. `NORMAL` or `TEXCOORD_1`.
+    pub semantic: String,
+    pub format: VertexFormat,
+    /// The byte offset of the attribute within a vertex.
+    pub offset: usize,
+}
+
+/// The layout of a single vertex in an interleaved vertex buffer.
+///
+/// ```
+/// use goth_gltf::interleave::{VertexFormat, VertexLayout};
+///
+/// let layout = VertexLayout::new()
+///     .with_attribute("POSITION", VertexFormat::Float32x3)
+///     .with_attribute("NORMAL", VertexFormat::Snorm8x4)
+///     .with_attribute("TEXCOORD_0", VertexFormat::Float32x2)
+///     .with_padding(4);
+///
+/// assert_eq!(layout.stride, 28);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VertexLayout {
+    pub attributes: Vec<VertexAttribute>,
+    /// The size of a vertex in bytes, including any padding.
+    pub stride: usize,
+}
+
+impl VertexLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an attribute directly after the previous attribute or padding.
+    pub fn with_attribute(mut self, semantic: &str, format: VertexFormat) -> Self {
+        self.attributes.push(VertexAttribute {
+            semantic: semantic.to_string(),
+            format,
+            offset: self.stride,
+        });
+        self.stride += format.byte_size();
+        self
+    }
+
+    /// Add `bytes` of zeroed padding after the previous attribute.
+    pub fn with_padding(mut self, bytes: usize) -> Self {
+        self.stride += bytes;
+        self
+    }
+}
+
+/// An interleaved vertex buffer and the primitive's indices.
+#[derive(Debug, Clone)]
+pub struct InterleavedVertices<'a> {
+    pub vertices: Vec<u8>,
+    pub vertex_count: usize,
+    pub indices: Option<Indices<'a>>,
+}
+
+// Where the values of an attribute come from.
+enum Source<'a> {
+    Accessor {
+        bytes: Cow<'a, [u8]>,
+        stride: usize,
+        element_size: usize,
+        offsets: Vec<usize>,
+        component_type: ComponentType,
+        normalized: bool,
+    },
+    Draco {
+        values: &'a [f32],
+        components: usize,
+    },
+    Missing,
+}
+
+impl<'a> Source<'a> {
+    fn element_count(&self) -> usize {
+        match self {
+            Self::Accessor {
+                bytes,
+                stride,
+                element_size,
+                ..
+            } => match bytes.len() >= *element_size {
+                true => (bytes.len() - element_size) / stride + 1,
+                false => 0,
+            },
+            Self::Draco { values, components } => values.len() / (*components).max(1),
+            Self::Missing => 0,
+        }
+    }
+
+    fn read<C: Component>(&self, vertex: usize, component: usize) -> Option<C> {
+        match self {
+            Self::Accessor {
+                bytes,
+                stride,
+                element_size,
+                offsets,
+                component_type,
+                normalized,
+            } => {
+                let start = vertex * stride;
+                let element = bytes.get(start..start + element_size)?;
+                let offset = *offsets.get(component)?;
+                Some(C::read(&element[offset..], *component_type, *normalized))
+            }
+            Self::Draco { values, components } if component < *components => {
+                let value = *values.get(vertex * components + component)?;
+                Some(C::read(&value.to_le_bytes(), ComponentType::Float, false))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, E: Extensions> PrimitiveReader<'a, E>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+    E::PrimitiveExtensions: DracoCompressionExtension,
+{
+    fn interleave_source(&self, semantic: &str) -> Result<Source<'_>, Error> {
+        if let Some(values) = self.draco_attribute_values(semantic)? {
+            let components = self
+                .primitive
+                .attributes
+                .get(semantic)
+                .and_then(|index| self.gltf.accessors.get(index))
+                .map(|accessor| accessor.accessor_type.num_components())
+                .unwrap_or(1);
+
+            return Ok(Source::Draco { values, components });
+        }
+
+        let accessor_index = match self.primitive.attributes.get(semantic) {
+            Some(index) => index,
+            None => return Ok(Source::Missing),
+        };
+
+        let accessor = self
+            .gltf
+            .accessors
+            .get(accessor_index)
+            .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+
+        let (bytes, byte_stride) = self.read_buffer(accessor)?;
+        let element_size = element_size(accessor);
+
+        Ok(Source::Accessor {
+            bytes,
+            stride: byte_stride.unwrap_or(element_size),
+            element_size,
+            offsets: (0..accessor.accessor_type.num_components())
+                .map(|i| component_byte_offset(accessor, i))
+                .collect(),
+            component_type: accessor.component_type,
+            normalized: accessor.normalized,
+        })
+    }
+
+    /// Write the primitive's vertices straight into an interleaved buffer with the given layout,
+    /// without decoding each attribute separately first.
+    ///
+    /// The number of vertices is taken from `POSITION`. Attributes that the primitive doesn't
+    /// have, along with any components an attribute doesn't have, are filled in with zero, or
+    /// one for the fourth component, as GPUs do for vertex inputs.
+    pub fn read_interleaved(
+        &self,
+        layout: &VertexLayout,
+    ) -> Result<InterleavedVertices<'a>, Error> {
+        let vertex_count = self.interleave_source("POSITION")?.element_count();
+        let mut vertices = vec![0; vertex_count * layout.stride];
+
+        for attribute in &layout.attributes {
+            let source = self.interleave_source(&attribute.semantic)?;
+            let format = attribute.format;
+            let component_size = format.component_size();
+
+            for (vertex, out) in vertices.chunks_exact_mut(layout.stride).enumerate() {
+                let out = &mut out[attribute.offset..attribute.offset + format.byte_size()];
+
+                for (component, out) in out.chunks_exact_mut(component_size).enumerate() {
+                    let default = if component == 3 { 1 } else { 0 };
+
+                    if format.is_integer() {
+                        let value = source.read::<u32>(vertex, component).unwrap_or(default);
+                        format.write_integer(value, out);
+                    } else {
+                        let value = source
+                            .read::<f32>(vertex, component)
+                            .unwrap_or(default as f32);
+                        format.write_float(value, out);
+                    }
+                }
+            }
+        }
+
+        Ok(InterleavedVertices {
+            vertices,
+            vertex_count,
+            indices: self.read_indices()?,
+        })
+    }
+}
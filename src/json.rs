@@ -0,0 +1,479 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use core::str::Chars;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok};
+
+/// How deeply arrays and objects can be nested in a [`RawValue`], the same as the default
+/// [`ParseLimits::max_depth`](crate::ParseLimits::max_depth). Building values recurses once per
+/// level, so without a limit deeply nested input would overflow the stack.
+pub const MAX_DEPTH: usize = 64;
+
+/// Skip over the value at the current token without building anything from it.
+///
+/// Unlike `DeJsonState::whole_field`, this returns an error on malformed input instead of
+/// panicking.
+pub fn skip_value(state: &mut DeJsonState, input: &mut Chars) -> Result<(), DeJsonErr> {
+    // The closing token expected for each open array or object.
+    let mut closers = Vec::new();
+
+    loop {
+        match state.tok {
+            DeJsonTok::CurlyOpen => closers.push(DeJsonTok::CurlyClose),
+            DeJsonTok::BlockOpen => closers.push(DeJsonTok::BlockClose),
+            DeJsonTok::CurlyClose | DeJsonTok::BlockClose => {
+                if closers.pop().as_ref() != Some(&state.tok) {
+                    return Err(state.err_token("value"));
+                }
+            }
+            DeJsonTok::Str
+            | DeJsonTok::U64(_)
+            | DeJsonTok::I64(_)
+            | DeJsonTok::F64(_)
+            | DeJsonTok::Bool(_)
+            | DeJsonTok::Null => {}
+            DeJsonTok::Colon | DeJsonTok::Comma if !closers.is_empty() => {}
+            _ => return Err(state.err_token("value")),
+        }
+
+        state.next_tok(input)?;
+
+        if closers.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+/// Skip over an object, returning an error if the current token doesn't start one.
+pub fn skip_object(state: &mut DeJsonState, input: &mut Chars) -> Result<(), DeJsonErr> {
+    if state.tok != DeJsonTok::CurlyOpen {
+        return Err(state.err_token("{"));
+    }
+
+    skip_value(state, input)
+}
+
+/// A JSON value with numbers and strings kept as their (escaped) source text, so that values
+/// can be rearranged and written back out without changing them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<RawValue>),
+    Object(Vec<(String, RawValue)>),
+}
+
+impl RawValue {
+    pub fn parse(json: &str) -> Option<Self> {
+        let mut parser = RawParser::new(json);
+
+        let value = parser.value()?;
+        parser.whitespace();
+
+        if parser.position == json.len() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn write(&self, out: &mut String) {
+        match self {
+            Self::Null => out.push_str("null"),
+            Self::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Self::Number(number) => out.push_str(number),
+            Self::String(string) => {
+                out.push('"');
+                out.push_str(string);
+                out.push('"');
+            }
+            Self::Array(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    value.write(out);
+                }
+                out.push(']');
+            }
+            Self::Object(members) => {
+                out.push('{');
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push('"');
+                    out.push_str(key);
+                    out.push_str("\":");
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+pub fn write_object(members: &[(String, RawValue)]) -> String {
+    let mut json = String::new();
+    RawValue::Object(members.to_vec()).write(&mut json);
+    json
+}
+
+/// The members of a serialized object, or nothing if `json` isn't an object.
+pub fn object_members(json: &str) -> Vec<(String, RawValue)> {
+    match RawValue::parse(json) {
+        Some(RawValue::Object(members)) => members,
+        _ => Vec::new(),
+    }
+}
+
+/// The members of an object as `(key, value)` pairs of their source text, with the keys still
+/// escaped. Values are only scanned for their extent, not validated. Returns `None` if `json`
+/// isn't an object.
+pub fn member_spans(json: &str) -> Option<Vec<(&str, &str)>> {
+    let mut parser = RawParser::new(json);
+
+    if !parser.eat(b'{') {
+        return None;
+    }
+
+    let mut members = Vec::new();
+
+    if !parser.eat(b'}') {
+        loop {
+            let key = parser.string()?;
+
+            if !parser.eat(b':') {
+                return None;
+            }
+
+            parser.whitespace();
+            let start = parser.position;
+            parser.skip()?;
+            members.push((key, &json[start..parser.position]));
+
+            if parser.eat(b'}') {
+                break;
+            }
+
+            if !parser.eat(b',') {
+                return None;
+            }
+        }
+    }
+
+    parser.whitespace();
+
+    if parser.position == json.len() {
+        Some(members)
+    } else {
+        None
+    }
+}
+
+/// The elements of an array as their source text. As with [`member_spans`], they're only
+/// scanned for their extent. Returns `None` if `json` isn't an array.
+pub fn element_spans(json: &str) -> Option<Vec<&str>> {
+    let mut parser = RawParser::new(json);
+
+    if !parser.eat(b'[') {
+        return None;
+    }
+
+    let mut elements = Vec::new();
+
+    if !parser.eat(b']') {
+        loop {
+            parser.whitespace();
+            let start = parser.position;
+            parser.skip()?;
+            elements.push(&json[start..parser.position]);
+
+            if parser.eat(b']') {
+                break;
+            }
+
+            if !parser.eat(b',') {
+                return None;
+            }
+        }
+    }
+
+    parser.whitespace();
+
+    if parser.position == json.len() {
+        Some(elements)
+    } else {
+        None
+    }
+}
+
+// Builds the value back up from tokens, as the source text isn't available through
+// `DeJsonState`. Strings are re-escaped, so escapes may differ from the original.
+impl DeJson for RawValue {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Self, DeJsonErr> {
+        Self::de_json_nested(state, input, 0)
+    }
+}
+
+impl RawValue {
+    fn de_json_nested(
+        state: &mut DeJsonState,
+        input: &mut Chars,
+        depth: usize,
+    ) -> Result<Self, DeJsonErr> {
+        if matches!(state.tok, DeJsonTok::BlockOpen | DeJsonTok::CurlyOpen) && depth == MAX_DEPTH {
+            return Err(crate::limits::exceeded(state, "nesting depth", MAX_DEPTH));
+        }
+
+        let value = match state.tok {
+            DeJsonTok::Null => Self::Null,
+            DeJsonTok::Bool(value) => Self::Bool(value),
+            DeJsonTok::U64(_) | DeJsonTok::I64(_) | DeJsonTok::F64(_) => {
+                Self::Number(state.numbuf.trim_start_matches('+').to_string())
+            }
+            DeJsonTok::Str => Self::String(escape(&state.strbuf)),
+            DeJsonTok::BlockOpen => {
+                state.next_tok(input)?;
+                let mut values = Vec::new();
+
+                while state.tok != DeJsonTok::BlockClose {
+                    values.push(Self::de_json_nested(state, input, depth + 1)?);
+                    state.eat_comma_block(input)?;
+                }
+
+                Self::Array(values)
+            }
+            DeJsonTok::CurlyOpen => {
+                state.next_tok(input)?;
+                let mut members = Vec::new();
+
+                while state.tok != DeJsonTok::CurlyClose {
+                    if state.tok != DeJsonTok::Str {
+                        return Err(state.err_token("String"));
+                    }
+
+                    let key = escape(&state.strbuf);
+                    state.next_colon(input)?;
+                    members.push((key, Self::de_json_nested(state, input, depth + 1)?));
+                    state.eat_comma_curly(input)?;
+                }
+
+                Self::Object(members)
+            }
+            _ => return Err(state.err_token("value")),
+        };
+
+        state.next_tok(input)?;
+        Ok(value)
+    }
+}
+
+pub fn escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+struct RawParser<'a> {
+    bytes: &'a [u8],
+    json: &'a str,
+    position: usize,
+    // The number of arrays and objects that `value` is inside of.
+    depth: usize,
+}
+
+impl<'a> RawParser<'a> {
+    fn new(json: &'a str) -> Self {
+        Self {
+            bytes: json.as_bytes(),
+            json,
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(
+            self.bytes.get(self.position),
+            Some(b' ' | b'\t' | b'\n' | b'\r')
+        ) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.whitespace();
+
+        if self.bytes.get(self.position) == Some(&byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn literal(&mut self, literal: &str) -> bool {
+        if self.json[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Returns the string contents without the quotes, still escaped.
+    fn string(&mut self) -> Option<&'a str> {
+        if !self.eat(b'"') {
+            return None;
+        }
+
+        let start = self.position;
+
+        loop {
+            match self.bytes.get(self.position)? {
+                b'"' => break,
+                b'\\' => self.position += 2,
+                _ => self.position += 1,
+            }
+        }
+
+        let string = self.json.get(start..self.position)?;
+        self.position += 1;
+        Some(string)
+    }
+
+    // Move past a value without building anything from it, only matching up brackets.
+    fn skip(&mut self) -> Option<()> {
+        self.whitespace();
+
+        if !matches!(self.bytes.get(self.position)?, b'[' | b'{') {
+            return self.value().map(|_| ());
+        }
+
+        // The closing bracket expected for each open array or object.
+        let mut closers = Vec::new();
+
+        loop {
+            match self.bytes.get(self.position)? {
+                b'"' => {
+                    self.string()?;
+                    continue;
+                }
+                b'[' => closers.push(b']'),
+                b'{' => closers.push(b'}'),
+                &byte @ (b']' | b'}') => {
+                    if closers.pop()? != byte {
+                        return None;
+                    }
+
+                    if closers.is_empty() {
+                        self.position += 1;
+                        return Some(());
+                    }
+                }
+                _ => {}
+            }
+
+            self.position += 1;
+        }
+    }
+
+    fn value(&mut self) -> Option<RawValue> {
+        self.whitespace();
+
+        let value = match self.bytes.get(self.position)? {
+            b'[' | b'{' if self.depth == MAX_DEPTH => return None,
+            b'[' | b'{' => {
+                self.depth += 1;
+                let value = self.container();
+                self.depth -= 1;
+                value?
+            }
+            b'n' if self.literal("null") => RawValue::Null,
+            b't' if self.literal("true") => RawValue::Bool(true),
+            b'f' if self.literal("false") => RawValue::Bool(false),
+            b'"' => RawValue::String(self.string()?.to_string()),
+            _ => {
+                let start = self.position;
+
+                while matches!(
+                    self.bytes.get(self.position),
+                    Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+                ) {
+                    self.position += 1;
+                }
+
+                if start == self.position {
+                    return None;
+                }
+
+                RawValue::Number(self.json[start..self.position].to_string())
+            }
+        };
+
+        Some(value)
+    }
+
+    // An array or object, with the parser at its opening bracket.
+    fn container(&mut self) -> Option<RawValue> {
+        if self.eat(b'[') {
+            let mut values = Vec::new();
+
+            if !self.eat(b']') {
+                loop {
+                    values.push(self.value()?);
+
+                    if self.eat(b']') {
+                        break;
+                    }
+
+                    if !self.eat(b',') {
+                        return None;
+                    }
+                }
+            }
+
+            return Some(RawValue::Array(values));
+        }
+
+        self.position += 1;
+        let mut members = Vec::new();
+
+        if !self.eat(b'}') {
+            loop {
+                let key = self.string()?.to_string();
+
+                if !self.eat(b':') {
+                    return None;
+                }
+
+                members.push((key, self.value()?));
+
+                if self.eat(b'}') {
+                    break;
+                }
+
+                if !self.eat(b',') {
+                    return None;
+                }
+            }
+        }
+
+        Some(RawValue::Object(members))
+    }
+}
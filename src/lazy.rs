@@ -0,0 +1,304 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{
+    glb, json, json_str,
+    options::{self, UnknownEnumValues},
+    Accessor, Animation, Asset, Buffer, BufferView, Camera, Extensions, GlbError, Gltf, Image,
+    Material, Mesh, Node, ParseOptions, Sampler, Scene, Skin, Texture,
+};
+use core::cell::OnceCell;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState};
+
+/// A top-level array that's kept as its source text until it's first used.
+#[derive(Debug)]
+pub struct LazyArray<'a, T> {
+    key: &'static str,
+    json: Option<&'a str>,
+    value: OnceCell<Vec<T>>,
+    // `None` when the options tolerate unknown enum values.
+    first_unknown_enum_value: Option<fn(&T) -> Option<String>>,
+}
+
+impl<'a, T: DeJson> LazyArray<'a, T> {
+    fn new(key: &'static str, json: Option<&'a str>, options: &ParseOptions) -> Self
+    where
+        T: UnknownEnumValues,
+    {
+        Self {
+            key,
+            json,
+            value: OnceCell::new(),
+            first_unknown_enum_value: (!options.tolerates_unknown_enum_values())
+                .then_some(T::first_unknown_enum_value),
+        }
+    }
+
+    fn deserialize(&self, json: &str) -> Result<Vec<T>, DeJsonErr> {
+        let value = Vec::<T>::deserialize_json(json)?;
+        self.check_enum_values(&value)?;
+        Ok(value)
+    }
+
+    fn check_enum_values(&self, value: &[T]) -> Result<(), DeJsonErr> {
+        match self.first_unknown_enum_value {
+            Some(first_unknown) => {
+                options::check_unknown_enum_values(self.key, value, first_unknown)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The source text of the array, or `None` if the document doesn't have it.
+    pub fn json(&self) -> Option<&'a str> {
+        self.json
+    }
+
+    pub fn is_parsed(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Deserialize the array if it hasn't been already. Errors aren't kept, so the next call
+    /// tries again.
+    pub fn get(&self) -> Result<&[T], DeJsonErr> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = match self.json {
+            Some(json) => self.deserialize(json)?,
+            None => Vec::new(),
+        };
+
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    pub fn into_vec(self) -> Result<Vec<T>, DeJsonErr> {
+        self.get()?;
+        Ok(self.value.into_inner().unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T: DeJson + Send> LazyArray<'a, T> {
+    /// Like [`into_vec`](Self::into_vec), but with the elements deserialized in parallel. The
+    /// positions in errors aren't relative to the start of the document.
+    pub fn into_vec_parallel(self) -> Result<Vec<T>, DeJsonErr> {
+        use rayon::prelude::*;
+
+        let json = match (self.value.get(), self.json) {
+            (Some(_), _) => return Ok(self.value.into_inner().unwrap_or_default()),
+            (None, Some(json)) => json,
+            (None, None) => return Ok(Vec::new()),
+        };
+
+        let elements = json::element_spans(json)
+            .ok_or_else(|| DeJsonState::default().err_parse("a JSON array"))?;
+
+        let chunks = elements
+            .par_chunks(256)
+            .map(|chunk| {
+                // Setting up a state allocates, so one is shared by the elements of a chunk.
+                let mut state = DeJsonState::default();
+
+                chunk
+                    .iter()
+                    .map(|element| {
+                        let mut input = element.chars();
+                        state.next(&mut input);
+                        state.next_tok(&mut input)?;
+                        T::de_json(&mut state, &mut input)
+                    })
+                    .collect::<Result<Vec<T>, DeJsonErr>>()
+            })
+            .collect::<Result<Vec<_>, DeJsonErr>>()?;
+
+        let value = chunks.into_iter().flatten().collect::<Vec<_>>();
+        self.check_enum_values(&value)?;
+        Ok(value)
+    }
+}
+
+macro_rules! lazy_gltf {
+    ($($field:ident: $ty:ident = $key:literal,)*) => {
+        /// A document where only the small top-level properties are deserialized upfront. The
+        /// arrays that get large in big scenes are only found in the JSON, and are deserialized
+        /// the first time they're used.
+        ///
+        /// This suits tools that only look at e.g. the asset info, extensions or scene names of
+        /// a lot of files.
+        ///
+        /// ```
+        /// # use goth_gltf::{lazy::LazyGltf, AnyExtensions};
+        /// let json = r#"{"asset": {"version": "2.0"}, "nodes": [{"mesh": 0}]}"#;
+        /// let gltf = LazyGltf::<AnyExtensions>::from_json_string(json).unwrap();
+        ///
+        /// assert_eq!(gltf.asset.version, "2.0");
+        /// assert!(!gltf.nodes.is_parsed());
+        /// assert_eq!(gltf.nodes.get().unwrap()[0].mesh, Some(0));
+        /// ```
+        #[derive(Debug)]
+        pub struct LazyGltf<'a, E: Extensions> {
+            pub asset: Asset<E>,
+            pub extensions_used: Vec<String>,
+            pub extensions_required: Vec<String>,
+            pub scenes: Vec<Scene<E>>,
+            pub scene: Option<usize>,
+            pub extensions: E::RootExtensions,
+            pub extras: E::Extras,
+            $(pub $field: LazyArray<'a, $ty<E>>,)*
+        }
+
+        impl<'a, E: Extensions> LazyGltf<'a, E> {
+            /// Load a document from either a gltf or a glb file, along with the binary buffer
+            /// chunk of a .glb.
+            pub fn from_bytes(bytes: &'a [u8]) -> Result<(Self, Option<&'a [u8]>), GlbError> {
+                let (json_bytes, binary_buffer) = glb::split_chunks(bytes)?;
+
+                Ok((Self::from_json_string(json_str(json_bytes)?)?, binary_buffer))
+            }
+
+            pub fn from_json_string(json: &'a str) -> Result<Self, DeJsonErr> {
+                Self::from_json_string_with_options(json, &ParseOptions::default())
+            }
+
+            /// Like [`from_json_string`](Self::from_json_string), but with control over unknown
+            /// enum values and limits. Missing fields aren't checked, as the arrays aren't
+            /// deserialized upfront.
+            pub fn from_json_string_with_options(
+                json: &'a str,
+                options: &ParseOptions,
+            ) -> Result<Self, DeJsonErr> {
+                if let Some(limits) = options.get_limits() {
+                    limits.check(json)?;
+                }
+
+                let members = json::member_spans(json)
+                    .ok_or_else(|| DeJsonState::default().err_parse("a JSON object"))?;
+                let member = |key: &str| {
+                    members
+                        .iter()
+                        .rev()
+                        .find(|(member, _)| *member == key)
+                        .map(|(_, value)| *value)
+                };
+
+                Ok(Self {
+                    asset: match member("asset") {
+                        Some(asset) => Asset::deserialize_json(asset)?,
+                        None => return Err(DeJsonState::default().err_nf("asset")),
+                    },
+                    extensions_used: parse_or_default(member("extensionsUsed"))?,
+                    extensions_required: parse_or_default(member("extensionsRequired"))?,
+                    scenes: parse_or_default(member("scenes"))?,
+                    scene: member("scene").map(usize::deserialize_json).transpose()?,
+                    extensions: parse_or_default(member("extensions"))?,
+                    extras: parse_or_default(member("extras"))?,
+                    $($field: LazyArray::new($key, member($key), options),)*
+                })
+            }
+
+            /// Deserialize every array that hasn't been yet.
+            pub fn into_gltf(self) -> Result<Gltf<E>, DeJsonErr> {
+                Ok(Gltf {
+                    asset: self.asset,
+                    extensions_used: self.extensions_used,
+                    extensions_required: self.extensions_required,
+                    scenes: self.scenes,
+                    scene: self.scene,
+                    unknown_json: None,
+                    extensions: self.extensions,
+                    extras: self.extras,
+                    $($field: self.$field.into_vec()?,)*
+                })
+            }
+
+            /// Like [`into_gltf`](Self::into_gltf), but with the elements of each array
+            /// deserialized in parallel.
+            #[cfg(feature = "rayon")]
+            pub fn into_gltf_parallel(self) -> Result<Gltf<E>, DeJsonErr>
+            where
+                $($ty<E>: Send,)*
+            {
+                Ok(Gltf {
+                    asset: self.asset,
+                    extensions_used: self.extensions_used,
+                    extensions_required: self.extensions_required,
+                    scenes: self.scenes,
+                    scene: self.scene,
+                    unknown_json: None,
+                    extensions: self.extensions,
+                    extras: self.extras,
+                    $($field: self.$field.into_vec_parallel()?,)*
+                })
+            }
+        }
+
+        #[cfg(feature = "rayon")]
+        impl<E: Extensions> Gltf<E>
+        where
+            $($ty<E>: Send,)*
+        {
+            /// Like [`Gltf::from_json_string`], but with the elements of the top-level arrays
+            /// deserialized in parallel on the rayon thread pool, which speeds up documents that
+            /// are tens of megabytes.
+            pub fn from_json_string_parallel(json: &str) -> Result<Self, DeJsonErr> {
+                Self::from_json_string_parallel_with_options(json, &ParseOptions::default())
+            }
+
+            /// Like [`from_json_string_parallel`](Self::from_json_string_parallel), but with
+            /// control over how strict parsing is.
+            pub fn from_json_string_parallel_with_options(
+                json: &str,
+                options: &ParseOptions,
+            ) -> Result<Self, DeJsonErr> {
+                let gltf =
+                    LazyGltf::from_json_string_with_options(json, options)?.into_gltf_parallel()?;
+
+                if !options.defaults_missing_fields() {
+                    gltf.check_required_fields()?;
+                }
+
+                Ok(gltf)
+            }
+
+            /// Like [`Gltf::from_bytes`], but parsed with
+            /// [`from_json_string_parallel`](Self::from_json_string_parallel).
+            pub fn from_bytes_parallel(bytes: &[u8]) -> Result<(Self, Option<&[u8]>), GlbError> {
+                Self::from_bytes_parallel_with_options(bytes, &ParseOptions::default())
+            }
+
+            /// Like [`from_bytes_parallel`](Self::from_bytes_parallel), but with control over
+            /// how strict parsing is.
+            pub fn from_bytes_parallel_with_options<'a>(
+                bytes: &'a [u8],
+                options: &ParseOptions,
+            ) -> Result<(Self, Option<&'a [u8]>), GlbError> {
+                let (json_bytes, binary_buffer) = glb::split_chunks(bytes)?;
+
+                Ok((
+                    Self::from_json_string_parallel_with_options(json_str(json_bytes)?, options)?,
+                    binary_buffer,
+                ))
+            }
+        }
+    };
+}
+
+lazy_gltf! {
+    accessors: Accessor = "accessors",
+    animations: Animation = "animations",
+    buffers: Buffer = "buffers",
+    buffer_views: BufferView = "bufferViews",
+    cameras: Camera = "cameras",
+    images: Image = "images",
+    materials: Material = "materials",
+    meshes: Mesh = "meshes",
+    nodes: Node = "nodes",
+    samplers: Sampler = "samplers",
+    skins: Skin = "skins",
+    textures: Texture = "textures",
+}
+
+fn parse_or_default<T: DeJson + Default>(json: Option<&str>) -> Result<T, DeJsonErr> {
+    json.map_or_else(|| Ok(T::default()), T::deserialize_json)
+}
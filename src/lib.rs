@@ -20,57 +20,257 @@
 //!
 //! # Extensions Implemented
 //!
+//! - `KHR_animation_pointer`
+//! - `KHR_draco_mesh_compression`
 //! - `KHR_lights_punctual`
 //! - `KHR_materials_emissive_strength`
 //! - `KHR_materials_ior`
 //! - `KHR_materials_sheen`
 //! - `KHR_materials_unlit`
+//! - `KHR_mesh_quantization`
 //! - `KHR_texture_basisu`
 //! - `KHR_texture_transform`
+//! - `ADOBE_materials_thin_transparency`
 //! - `EXT_mesh_gpu_instancing`
 //! - `EXT_meshopt_compression`
+//! - `EXT_texture_astc`
+//! - `EXT_texture_avif`
+//! - `EXT_texture_webp`
+//! - `FB_ngon_encoding`
+//! - `MPEG_media`
+//! - `MPEG_texture_video`
 //! - `MSFT_lod`
 //! - `MSFT_screencoverage`
+//! - `MSFT_texture_dds`
+//! - `OMI_collider`
+//! - `OMI_physics_body`
+//! - `OMI_physics_shape`
+//! - `VRMC_springBone`
+//! - `VRMC_vrm`
+//!
+//! The structs for each group of extensions are behind a cargo feature (e.g. `khr_lights`,
+//! `ext_meshopt` or `vrm`), all of which are enabled by default through `all_extensions`.
+//! Extensions that are disabled are skipped over when parsing.
+//!
+//! `KHR_draco_mesh_compression` is parsed, but there's no Draco decoder bundled: the primitive
+//! reader only decodes compressed primitives with a `DracoDecoder` provided by the application.
+//!
+//! Parsing and writing also work without `std`, only needing `alloc`, with the `no_std` feature.
+//! This currently needs a nightly compiler, as nanoserde's `no_std` support does.
 //!
 //! [gltf-rs]: https://github.com/gltf-rs/gltf
 
+#![cfg_attr(feature = "no_std", no_std)]
 #![allow(clippy::question_mark)]
 
+extern crate alloc;
+
+#[cfg(all(feature = "no_std", feature = "primitive_reader"))]
+compile_error!("the `primitive_reader` feature needs `std`, so it can't be used with `no_std`");
+#[cfg(all(feature = "no_std", feature = "mmap"))]
+compile_error!("the `mmap` feature needs `std`, so it can't be used with `no_std`");
+
+/// Keyframe evaluation and resampling.
+#[cfg(not(feature = "no_std"))]
+pub mod animation;
+/// Resolution of `KHR_animation_pointer` JSON pointers into typed targets.
+pub mod animation_pointer;
+/// Merging primitives into combined vertex and index buffers for each material.
+#[cfg(feature = "primitive_reader")]
+pub mod batch;
+/// Building the map of buffer view data that the readers take.
+#[cfg(feature = "primitive_reader")]
+pub mod buffers;
+mod compose;
+mod construct;
+/// Borrowing wrappers that resolve the indices between objects.
+pub mod document;
+/// Adding, moving and removing objects while keeping the indices between them consistent.
+pub mod edit;
 pub mod extensions;
+/// Geometry analysis for decoded primitive data.
+pub mod geometry;
+mod get;
+mod glb;
+/// Assembling interleaved vertex buffers from primitives.
+#[cfg(feature = "primitive_reader")]
+pub mod interleave;
+mod json;
+/// Deferring the deserialization of the large top-level arrays until they're used.
+pub mod lazy;
+mod limits;
+mod math;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod named;
+mod options;
+/// Parsing only selected top-level sections of a document.
+pub mod partial;
+mod passthrough;
 /// Basic support for reading primitive data from buffer views and accessors.
 #[cfg(feature = "primitive_reader")]
 pub mod primitive_reader;
+/// In-place repair of common problems in real-world files.
+pub mod sanitize;
+/// Reading strings such as names and uris borrowed from the JSON, without deserializing it.
+pub mod scan;
+/// Walking the node hierarchy and primitives of a scene.
+pub mod scene;
+/// A reference CPU implementation of vertex skinning.
+#[cfg(not(feature = "no_std"))]
+pub mod skinning;
+/// Helpers for the uris of buffers and images.
+pub mod uri;
+mod value;
+/// Conversions of accessor formats, primitive modes and samplers into `wgpu` types.
+#[cfg(feature = "wgpu-types")]
+pub mod wgpu;
+mod write;
+
+pub use compose::Compose;
+use core::fmt::Debug;
+pub use get::IndexError;
+pub use glb::GlbError;
+pub use limits::ParseLimits;
+#[cfg(feature = "mmap")]
+pub use mmap::{FromPathError, MappedBinary};
+pub use named::Named;
+use nanoserde::{DeJson, SerJson};
+pub use options::{ParseOptions, UnknownName};
+pub use passthrough::Passthrough;
+
+// nanoserde deserializes maps into hashbrown's `HashMap` without `std`.
+#[cfg(feature = "no_std")]
+use hashbrown::HashMap;
+#[cfg(not(feature = "no_std"))]
+use std::collections::HashMap;
+
+// The parts of the `std` prelude that come from `alloc`, for `no_std` builds.
+#[cfg(feature = "no_std")]
+mod prelude {
+    pub use alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
+#[cfg(feature = "no_std")]
+use prelude::*;
 
-use nanoserde::DeJson;
-use std::fmt::Debug;
-
-pub trait Extensions: DeJson {
-    type RootExtensions: DeJson + Default + Debug + Clone;
-    type TextureExtensions: DeJson + Default + Debug + Clone;
-    type TextureInfoExtensions: DeJson + Default + Debug + Clone;
-    type MaterialExtensions: DeJson + Default + Debug + Clone;
-    type BufferExtensions: DeJson + Default + Debug + Clone;
-    type NodeExtensions: DeJson + Default + Debug + Clone;
-    type NodeExtras: DeJson + Default + Debug + Clone;
-    type BufferViewExtensions: DeJson + Default + Debug + Clone;
+#[doc(hidden)]
+pub mod __private {
+    pub use nanoserde;
+}
+pub use value::{JsonMap, JsonValue};
+pub use write::{split_glb, SplitGlb, UnknownJson};
+
+pub trait Extensions: DeJson + SerJson {
+    type RootExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type ImageExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type SamplerExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type TextureExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type TextureInfoExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type MaterialExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type MaterialExtras: DeJson + SerJson + Default + Debug + Clone;
+    type BufferExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type NodeExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type NodeExtras: DeJson + SerJson + Default + Debug + Clone;
+    type MeshExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type SceneExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type SkinExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type BufferViewExtensions: DeJson + SerJson + Default + Debug + Clone;
+    /// Used for accessors as well as their sparse storage.
+    type AccessorExtensions: DeJson + SerJson + Default + Debug + Clone;
+    /// Used for mesh primitives, e.g. for `KHR_draco_mesh_compression` or
+    /// `KHR_materials_variants`.
+    type PrimitiveExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type AnimationExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type ChannelExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type TargetExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type AnimationSamplerExtensions: DeJson + SerJson + Default + Debug + Clone;
+    /// Used for cameras as well as their perspective and orthographic projections.
+    type CameraExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type AssetExtensions: DeJson + SerJson + Default + Debug + Clone;
+    type AssetExtras: DeJson + SerJson + Default + Debug + Clone;
+    /// The `extras` of every object without a more specific hook, such as `NodeExtras`.
+    type Extras: DeJson + SerJson + Default + Debug + Clone;
 }
 
 impl Extensions for () {
     type RootExtensions = ();
+    type ImageExtensions = ();
+    type SamplerExtensions = ();
     type TextureExtensions = ();
     type TextureInfoExtensions = ();
     type MaterialExtensions = ();
+    type MaterialExtras = ();
     type BufferExtensions = ();
     type NodeExtensions = ();
     type NodeExtras = ();
+    type MeshExtensions = ();
+    type SceneExtensions = ();
+    type SkinExtensions = ();
     type BufferViewExtensions = ();
+    type AccessorExtensions = ();
+    type PrimitiveExtensions = ();
+    type AnimationExtensions = ();
+    type ChannelExtensions = ();
+    type TargetExtensions = ();
+    type AnimationSamplerExtensions = ();
+    type CameraExtensions = ();
+    type AssetExtensions = ();
+    type AssetExtras = ();
+    type Extras = ();
+}
+
+/// An extension set that keeps every extension and extras object as dynamic JSON.
+///
+/// Useful for tools that inspect or rewrite files without caring about specific extensions.
+#[derive(Debug, Default, Clone, Copy, DeJson, SerJson)]
+pub struct AnyExtensions;
+
+impl Extensions for AnyExtensions {
+    type RootExtensions = JsonMap;
+    type ImageExtensions = JsonMap;
+    type SamplerExtensions = JsonMap;
+    type TextureExtensions = JsonMap;
+    type TextureInfoExtensions = JsonMap;
+    type MaterialExtensions = JsonMap;
+    type MaterialExtras = JsonValue;
+    type BufferExtensions = JsonMap;
+    type NodeExtensions = JsonMap;
+    type NodeExtras = JsonValue;
+    type MeshExtensions = JsonMap;
+    type SceneExtensions = JsonMap;
+    type SkinExtensions = JsonMap;
+    type BufferViewExtensions = JsonMap;
+    type AccessorExtensions = JsonMap;
+    type PrimitiveExtensions = JsonMap;
+    type AnimationExtensions = JsonMap;
+    type ChannelExtensions = JsonMap;
+    type TargetExtensions = JsonMap;
+    type AnimationSamplerExtensions = JsonMap;
+    type CameraExtensions = JsonMap;
+    type AssetExtensions = JsonMap;
+    type AssetExtras = JsonValue;
+    type Extras = JsonValue;
 }
 
 /// A parsed gltf document.
-#[derive(Debug, DeJson)]
+#[derive(Debug, DeJson, SerJson)]
 pub struct Gltf<E: Extensions> {
+    #[nserde(default_with = "Asset::missing")]
+    pub asset: Asset<E>,
+    #[nserde(rename = "extensionsUsed")]
+    #[nserde(default)]
+    pub extensions_used: Vec<String>,
+    #[nserde(rename = "extensionsRequired")]
+    #[nserde(default)]
+    pub extensions_required: Vec<String>,
     #[nserde(default)]
-    pub images: Vec<Image>,
+    pub images: Vec<Image<E>>,
     #[nserde(default)]
     pub textures: Vec<Texture<E>>,
     #[nserde(default)]
@@ -81,124 +281,332 @@ pub struct Gltf<E: Extensions> {
     #[nserde(default)]
     pub buffer_views: Vec<BufferView<E>>,
     #[nserde(default)]
-    pub accessors: Vec<Accessor>,
+    pub accessors: Vec<Accessor<E>>,
     #[nserde(default)]
-    pub meshes: Vec<Mesh>,
+    pub meshes: Vec<Mesh<E>>,
     #[nserde(default)]
-    pub animations: Vec<Animation>,
+    pub animations: Vec<Animation<E>>,
     #[nserde(default)]
     pub nodes: Vec<Node<E>>,
     #[nserde(default)]
-    pub skins: Vec<Skin>,
+    pub skins: Vec<Skin<E>>,
     #[nserde(default)]
-    pub samplers: Vec<Sampler>,
+    pub samplers: Vec<Sampler<E>>,
     #[nserde(default)]
-    pub cameras: Vec<Camera>,
+    pub cameras: Vec<Camera<E>>,
     #[nserde(default)]
     pub extensions: E::RootExtensions,
     #[nserde(default)]
-    pub scenes: Vec<Scene>,
+    pub extras: E::Extras,
     #[nserde(default)]
-    pub scene: usize,
+    pub scenes: Vec<Scene<E>>,
+    /// The scene to display when the document is loaded, if specified.
+    pub scene: Option<usize>,
+    /// The members of the source JSON that the document doesn't model, if they were kept with
+    /// [`Gltf::keep_unknown_json`].
+    #[nserde(skip)]
+    pub unknown_json: Option<UnknownJson>,
 }
 
 impl<E: Extensions> Gltf<E> {
+    pub fn uses_extension(&self, name: &str) -> bool {
+        self.extensions_used.iter().any(|used| used == name)
+    }
+
+    pub fn requires_extension(&self, name: &str) -> bool {
+        self.extensions_required
+            .iter()
+            .any(|required| required == name)
+    }
+
+    /// Whether `KHR_mesh_quantization` is required, which allows vertex attributes to use
+    /// integer component types, e.g. normalized shorts for normals or unnormalized bytes for
+    /// positions.
+    pub fn mesh_quantization(&self) -> bool {
+        self.requires_extension("KHR_mesh_quantization")
+    }
+
+    /// The scene referenced by `scene`, if it's set and in range.
+    pub fn default_scene(&self) -> Option<&Scene<E>> {
+        self.scene.and_then(|scene| self.scenes.get(scene))
+    }
+
     /// Load a gltf from either a gltf or a glb file.
     ///
     /// In the case of a .glb, the binary buffer chunk will be returned as well.
-    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, Option<&[u8]>), nanoserde::DeJsonErr> {
-        // Check for the 4-byte magic.
-        if !bytes.starts_with(b"glTF") {
-            return Ok((Self::from_json_bytes(bytes)?, None));
-        }
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, Option<&[u8]>), GlbError> {
+        let (json_bytes, binary_buffer) = glb::split_chunks(bytes)?;
 
-        // There's always a json chunk at the start:
-        // https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#structured-json-content
+        Ok((Self::from_json_bytes(json_bytes)?, binary_buffer))
+    }
+
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, nanoserde::DeJsonErr> {
+        Self::from_json_string(json_str(bytes)?)
+    }
 
-        let json_chunk_length = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+    pub fn from_json_string(string: &str) -> Result<Self, nanoserde::DeJsonErr> {
+        Self::from_json_string_with_options(string, &ParseOptions::default())
+    }
 
-        let json_chunk_end = 20 + json_chunk_length as usize;
+    /// Like [`Gltf::from_bytes`], but rejects input that goes over `limits` before
+    /// deserializing it.
+    pub fn from_bytes_with_limits<'a>(
+        bytes: &'a [u8],
+        limits: &ParseLimits,
+    ) -> Result<(Self, Option<&'a [u8]>), GlbError> {
+        Self::from_bytes_with_options(bytes, &ParseOptions::default().limits(*limits))
+    }
 
-        let json_chunk_bytes = &bytes[20..20 + json_chunk_length as usize];
+    pub fn from_json_string_with_limits(
+        string: &str,
+        limits: &ParseLimits,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        Self::from_json_string_with_options(string, &ParseOptions::default().limits(*limits))
+    }
 
-        let json = Self::from_json_bytes(json_chunk_bytes)?;
+    /// Like [`Gltf::from_bytes`], but with control over how strict parsing is.
+    pub fn from_bytes_with_options<'a>(
+        bytes: &'a [u8],
+        options: &ParseOptions,
+    ) -> Result<(Self, Option<&'a [u8]>), GlbError> {
+        let (json_bytes, binary_buffer) = glb::split_chunks(bytes)?;
+
+        Ok((
+            Self::from_json_string_with_options(json_str(json_bytes)?, options)?,
+            binary_buffer,
+        ))
+    }
 
-        let binary_buffer = if bytes.len() != json_chunk_end {
-            Some(&bytes[json_chunk_end + 8..])
-        } else {
-            None
-        };
+    pub fn from_json_string_with_options(
+        string: &str,
+        options: &ParseOptions,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        if let Some(limits) = options.get_limits() {
+            limits.check(string)?;
+        }
 
-        Ok((json, binary_buffer))
+        let gltf = Self::deserialize_json(string)?;
+
+        if !options.tolerates_unknown_enum_values() {
+            gltf.check_enum_values()?;
+        }
+
+        if !options.defaults_missing_fields() {
+            gltf.check_required_fields()?;
+        }
+
+        Ok(gltf)
     }
 
-    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, nanoserde::DeJsonErr> {
-        match std::str::from_utf8(bytes) {
-            Ok(string) => Self::from_json_string(string),
-            Err(error) => Err(nanoserde::DeJsonState::default().err_parse(&error.to_string())),
+    // Some required fields are parsed with a default so that lenient parsing can accept them
+    // being missing. The spec requires arrays to be non-empty, so an empty array means either
+    // missing or invalid.
+    fn check_required_fields(&self) -> Result<(), nanoserde::DeJsonErr> {
+        let missing = |path: &str| Err(nanoserde::DeJsonState::default().err_nf(path));
+
+        if self.asset.version.is_empty() {
+            return missing("asset.version");
+        }
+
+        for (index, mesh) in self.meshes.iter().enumerate() {
+            if mesh.primitives.is_empty() {
+                return missing(&format!("meshes[{}].primitives", index));
+            }
+        }
+
+        for (index, animation) in self.animations.iter().enumerate() {
+            if animation.channels.is_empty() {
+                return missing(&format!("animations[{}].channels", index));
+            }
+
+            if animation.samplers.is_empty() {
+                return missing(&format!("animations[{}].samplers", index));
+            }
+        }
+
+        for (index, skin) in self.skins.iter().enumerate() {
+            if skin.joints.is_empty() {
+                return missing(&format!("skins[{}].joints", index));
+            }
         }
+
+        Ok(())
     }
 
-    pub fn from_json_string(string: &str) -> Result<Self, nanoserde::DeJsonErr> {
-        Self::deserialize_json(string)
+    pub(crate) fn check_enum_values(&self) -> Result<(), nanoserde::DeJsonErr> {
+        use options::{check_unknown_enum_values as check, UnknownEnumValues};
+
+        check(
+            "accessors",
+            &self.accessors,
+            Accessor::first_unknown_enum_value,
+        )?;
+        check(
+            "animations",
+            &self.animations,
+            Animation::first_unknown_enum_value,
+        )?;
+        check(
+            "materials",
+            &self.materials,
+            Material::first_unknown_enum_value,
+        )?;
+        check("meshes", &self.meshes, Mesh::first_unknown_enum_value)?;
+        check(
+            "samplers",
+            &self.samplers,
+            Sampler::first_unknown_enum_value,
+        )
+    }
+}
+
+fn json_str(bytes: &[u8]) -> Result<&str, nanoserde::DeJsonErr> {
+    core::str::from_utf8(bytes)
+        .map_err(|error| nanoserde::DeJsonState::default().err_parse(&error.to_string()))
+}
+
+/// Metadata about the document, such as the glTF version and the exporter that produced it.
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct Asset<E: Extensions> {
+    /// The glTF version, e.g. `2.0`.
+    #[nserde(default)]
+    pub version: String,
+    /// The minimum glTF version a loader must support to load this document.
+    #[nserde(rename = "minVersion")]
+    pub min_version: Option<String>,
+    /// The tool that produced the document, e.g. `Khronos glTF Blender I/O v3.6.27`.
+    pub generator: Option<String>,
+    pub copyright: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::AssetExtensions,
+    #[nserde(default)]
+    pub extras: E::AssetExtras,
+}
+
+impl<E: Extensions> Asset<E> {
+    // Used when the `asset` block is missing entirely. The empty version is rejected when
+    // parsing strictly.
+    fn missing() -> Self {
+        Self {
+            version: String::new(),
+            min_version: None,
+            generator: None,
+            copyright: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
     }
 }
 
-#[derive(Debug, DeJson)]
-pub struct Skin {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Skin<E: Extensions> {
     #[nserde(rename = "inverseBindMatrices")]
     pub inverse_bind_matrices: Option<usize>,
     pub skeleton: Option<usize>,
+    #[nserde(default)]
     pub joints: Vec<usize>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::SkinExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Animation {
-    pub channels: Vec<Channel>,
-    pub samplers: Vec<AnimationSampler>,
+#[derive(Debug, DeJson, SerJson)]
+pub struct Animation<E: Extensions> {
+    #[nserde(default)]
+    pub channels: Vec<Channel<E>>,
+    #[nserde(default)]
+    pub samplers: Vec<AnimationSampler<E>>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::AnimationExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Channel {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Channel<E: Extensions> {
     pub sampler: usize,
-    pub target: Target,
+    pub target: Target<E>,
+    #[nserde(default)]
+    pub extensions: E::ChannelExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Target {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Target<E: Extensions> {
     pub node: Option<usize>,
     pub path: TargetPath,
+    #[nserde(default)]
+    pub extensions: E::TargetExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct AnimationSampler {
+#[derive(Debug, DeJson, SerJson)]
+pub struct AnimationSampler<E: Extensions> {
     pub input: usize,
     #[nserde(default)]
     pub interpolation: Interpolation,
     pub output: usize,
+    #[nserde(default)]
+    pub extensions: E::AnimationSamplerExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Interpolation {
-    #[nserde(rename = "LINEAR")]
+    #[default]
     Linear,
-    #[nserde(rename = "STEP")]
     Step,
-    #[nserde(rename = "CUBICSPLINE")]
     CubicSpline,
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Other(UnknownName),
 }
 
-impl Default for Interpolation {
-    fn default() -> Self {
-        Self::Linear
+impl DeJson for Interpolation {
+    fn de_json(
+        state: &mut nanoserde::DeJsonState,
+        input: &mut core::str::Chars,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        let ty = match &state.tok {
+            nanoserde::DeJsonTok::Str => match state.strbuf.as_str() {
+                "LINEAR" => Self::Linear,
+                "STEP" => Self::Step,
+                "CUBICSPLINE" => Self::CubicSpline,
+                other => match UnknownName::new(other) {
+                    Some(name) => Self::Other(name),
+                    None => return Err(state.err_enum(other)),
+                },
+            },
+            _ => return Err(state.err_token("String")),
+        };
+
+        state.next_tok(input)?;
+
+        Ok(ty)
     }
 }
 
-#[derive(Debug, DeJson)]
+impl SerJson for Interpolation {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        match self {
+            Self::Linear => "LINEAR",
+            Self::Step => "STEP",
+            Self::CubicSpline => "CUBICSPLINE",
+            Self::Other(other) => other.as_str(),
+        }
+        .ser_json(d, s)
+    }
+}
+
+#[derive(Debug, DeJson, SerJson, Clone, Copy, PartialEq, Eq)]
 pub enum TargetPath {
     #[nserde(rename = "translation")]
     Translation,
@@ -208,9 +616,13 @@ pub enum TargetPath {
     Scale,
     #[nserde(rename = "weights")]
     Weights,
+    /// The target is given by a `KHR_animation_pointer` JSON pointer in the target's
+    /// extensions.
+    #[nserde(rename = "pointer")]
+    Pointer,
 }
 
-#[derive(Debug, DeJson)]
+#[derive(Debug, DeJson, SerJson)]
 pub struct Buffer<E: Extensions> {
     pub uri: Option<String>,
     #[nserde(rename = "byteLength")]
@@ -219,9 +631,11 @@ pub struct Buffer<E: Extensions> {
     pub name: Option<String>,
     #[nserde(default)]
     pub extensions: E::BufferExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
+#[derive(Debug, DeJson, SerJson)]
 pub struct Node<E: Extensions> {
     pub camera: Option<usize>,
     #[nserde(default)]
@@ -266,6 +680,7 @@ impl<E: Extensions> Node<E> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NodeTransform {
     Matrix([f32; 16]),
     Set {
@@ -275,39 +690,103 @@ pub enum NodeTransform {
     },
 }
 
-#[derive(Debug, DeJson)]
-pub struct Mesh {
-    pub primitives: Vec<Primitive>,
+impl NodeTransform {
+    /// The transform as a column-major matrix.
+    pub fn to_matrix(&self) -> [f32; 16] {
+        match *self {
+            Self::Matrix(matrix) => matrix,
+            Self::Set {
+                translation,
+                rotation,
+                scale,
+            } => math::from_trs(translation, rotation, scale),
+        }
+    }
+
+    /// The transform as a translation, rotation and scale. Matrices are assumed to have no
+    /// shear, as the spec requires, and mirroring is folded into a negative x scale.
+    #[cfg(not(feature = "no_std"))]
+    pub fn to_trs(&self) -> ([f32; 3], [f32; 4], [f32; 3]) {
+        match *self {
+            Self::Matrix(matrix) => math::decompose(&matrix),
+            Self::Set {
+                translation,
+                rotation,
+                scale,
+            } => (translation, rotation, scale),
+        }
+    }
+
+    /// Convert a `Matrix` transform into a `Set` one with [`to_trs`](Self::to_trs).
+    #[cfg(not(feature = "no_std"))]
+    pub fn decomposed(&self) -> Self {
+        let (translation, rotation, scale) = self.to_trs();
+
+        Self::Set {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+}
+
+impl Default for NodeTransform {
+    fn default() -> Self {
+        Self::Matrix(math::IDENTITY)
+    }
+}
+
+/// Compose a parent transform with a child one, giving the child's transform in the parent's
+/// space. The result is a matrix, as the composition of two scaled and rotated transforms can
+/// have shear.
+impl core::ops::Mul for NodeTransform {
+    type Output = Self;
+
+    fn mul(self, child: Self) -> Self {
+        Self::Matrix(math::mul(&self.to_matrix(), &child.to_matrix()))
+    }
+}
+
+#[derive(Debug, DeJson, SerJson)]
+pub struct Mesh<E: Extensions> {
+    #[nserde(default)]
+    pub primitives: Vec<Primitive<E>>,
     pub weights: Option<Vec<f32>>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::MeshExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Primitive {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Primitive<E: Extensions> {
     pub attributes: Attributes,
     pub indices: Option<usize>,
     pub material: Option<usize>,
     #[nserde(default)]
     pub mode: PrimitiveMode,
     pub targets: Option<Vec<Attributes>>,
+    #[nserde(default)]
+    pub extensions: E::PrimitiveExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PrimitiveMode {
     Points,
     Lines,
     LineLoop,
     LineStrip,
+    #[default]
     Triangles,
     TriangleStrip,
     TriangleFan,
-}
-
-impl Default for PrimitiveMode {
-    fn default() -> Self {
-        Self::Triangles
-    }
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Unknown(u64),
 }
 
 impl DeJson for PrimitiveMode {
@@ -324,7 +803,7 @@ impl DeJson for PrimitiveMode {
                 4 => Self::Triangles,
                 5 => Self::TriangleStrip,
                 6 => Self::TriangleFan,
-                _ => return Err(state.err_range(&ty.to_string())),
+                _ => Self::Unknown(*ty),
             },
             _ => return Err(state.err_token("U64")),
         };
@@ -335,26 +814,147 @@ impl DeJson for PrimitiveMode {
     }
 }
 
-#[derive(Debug, DeJson)]
+impl SerJson for PrimitiveMode {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        let ty: u64 = match self {
+            Self::Points => 0,
+            Self::Lines => 1,
+            Self::LineLoop => 2,
+            Self::LineStrip => 3,
+            Self::Triangles => 4,
+            Self::TriangleStrip => 5,
+            Self::TriangleFan => 6,
+            Self::Unknown(ty) => *ty,
+        };
+
+        ty.ser_json(d, s)
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct Attributes {
-    #[nserde(rename = "POSITION")]
     pub position: Option<usize>,
-    #[nserde(rename = "TANGENT")]
     pub tangent: Option<usize>,
-    #[nserde(rename = "NORMAL")]
     pub normal: Option<usize>,
-    #[nserde(rename = "TEXCOORD_0")]
     pub texcoord_0: Option<usize>,
-    #[nserde(rename = "TEXCOORD_1")]
     pub texcoord_1: Option<usize>,
-    #[nserde(rename = "JOINTS_0")]
     pub joints_0: Option<usize>,
-    #[nserde(rename = "WEIGHTS_0")]
     pub weights_0: Option<usize>,
+    pub joints_1: Option<usize>,
+    pub weights_1: Option<usize>,
+    /// Every other attribute, such as `TEXCOORD_2`, `COLOR_0` or application-specific
+    /// `_SEMANTIC`s, sorted by semantic.
+    pub other: Vec<(String, usize)>,
 }
 
-#[derive(Debug, DeJson, Clone)]
-pub struct Image {
+impl Attributes {
+    /// The attributes that have their own fields, along with their semantics.
+    pub fn known_mut(&mut self) -> [(&'static str, &mut Option<usize>); 9] {
+        [
+            ("POSITION", &mut self.position),
+            ("TANGENT", &mut self.tangent),
+            ("NORMAL", &mut self.normal),
+            ("TEXCOORD_0", &mut self.texcoord_0),
+            ("TEXCOORD_1", &mut self.texcoord_1),
+            ("JOINTS_0", &mut self.joints_0),
+            ("WEIGHTS_0", &mut self.weights_0),
+            ("JOINTS_1", &mut self.joints_1),
+            ("WEIGHTS_1", &mut self.weights_1),
+        ]
+    }
+
+    /// Iterate over every attribute as `(semantic, accessor)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        [
+            ("POSITION", self.position),
+            ("TANGENT", self.tangent),
+            ("NORMAL", self.normal),
+            ("TEXCOORD_0", self.texcoord_0),
+            ("TEXCOORD_1", self.texcoord_1),
+            ("JOINTS_0", self.joints_0),
+            ("WEIGHTS_0", self.weights_0),
+            ("JOINTS_1", self.joints_1),
+            ("WEIGHTS_1", self.weights_1),
+        ]
+        .into_iter()
+        .filter_map(|(semantic, accessor)| Some((semantic, accessor?)))
+        .chain(
+            self.other
+                .iter()
+                .map(|(semantic, accessor)| (semantic.as_str(), *accessor)),
+        )
+    }
+
+    /// The accessor for an attribute semantic, e.g. `TEXCOORD_2`.
+    pub fn get(&self, semantic: &str) -> Option<usize> {
+        self.iter()
+            .find(|(name, _)| *name == semantic)
+            .map(|(_, accessor)| accessor)
+    }
+
+    /// The `JOINTS_n` accessor for influence set `set`.
+    pub fn joints(&self, set: usize) -> Option<usize> {
+        match set {
+            0 => self.joints_0,
+            1 => self.joints_1,
+            _ => self.get(&format!("JOINTS_{}", set)),
+        }
+    }
+
+    /// The `WEIGHTS_n` accessor for influence set `set`.
+    pub fn weights(&self, set: usize) -> Option<usize> {
+        match set {
+            0 => self.weights_0,
+            1 => self.weights_1,
+            _ => self.get(&format!("WEIGHTS_{}", set)),
+        }
+    }
+}
+
+impl DeJson for Attributes {
+    fn de_json(
+        state: &mut nanoserde::DeJsonState,
+        input: &mut core::str::Chars,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        let mut attributes = Self::default();
+
+        for (semantic, accessor) in HashMap::<String, usize>::de_json(state, input)? {
+            let field = attributes
+                .known_mut()
+                .into_iter()
+                .find(|(name, _)| *name == semantic)
+                .map(|(_, field)| field);
+
+            match field {
+                Some(field) => *field = Some(accessor),
+                None => attributes.other.push((semantic, accessor)),
+            }
+        }
+
+        attributes.other.sort();
+
+        Ok(attributes)
+    }
+}
+
+impl SerJson for Attributes {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        s.st_pre();
+
+        for (i, (semantic, accessor)) in self.iter().enumerate() {
+            if i > 0 {
+                s.conl();
+            }
+            s.field(d + 1, semantic);
+            accessor.ser_json(d + 1, s);
+        }
+
+        s.st_post(d);
+    }
+}
+
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct Image<E: Extensions> {
     pub uri: Option<String>,
     #[nserde(rename = "mimeType")]
     pub mime_type: Option<String>,
@@ -362,9 +962,13 @@ pub struct Image {
     pub buffer_view: Option<usize>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::ImageExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
+#[derive(Debug, DeJson, SerJson)]
 pub struct Texture<E: Extensions> {
     pub sampler: Option<usize>,
     pub source: Option<usize>,
@@ -372,9 +976,84 @@ pub struct Texture<E: Extensions> {
     pub name: Option<String>,
     #[nserde(default)]
     pub extensions: E::TextureExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
+}
+
+/// Texture extensions that provide an alternative image source to the core `source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureSourceKind {
+    KhrTextureBasisu,
+    ExtTextureAstc,
+    ExtTextureAvif,
+    ExtTextureWebp,
+    MsftTextureDds,
+}
+
+pub trait TextureSourceExtensions {
+    fn source(&self, kind: TextureSourceKind) -> Option<usize>;
 }
 
-#[derive(Debug, DeJson)]
+impl TextureSourceExtensions for () {
+    fn source(&self, _kind: TextureSourceKind) -> Option<usize> {
+        None
+    }
+}
+
+impl TextureSourceExtensions for JsonMap {
+    fn source(&self, kind: TextureSourceKind) -> Option<usize> {
+        let name = match kind {
+            TextureSourceKind::KhrTextureBasisu => "KHR_texture_basisu",
+            TextureSourceKind::ExtTextureAstc => "EXT_texture_astc",
+            TextureSourceKind::ExtTextureAvif => "EXT_texture_avif",
+            TextureSourceKind::ExtTextureWebp => "EXT_texture_webp",
+            TextureSourceKind::MsftTextureDds => "MSFT_texture_dds",
+        };
+
+        match self.get(name) {
+            Some(JsonValue::Object(extension)) => match extension.get("source") {
+                Some(&JsonValue::Number(source)) if source >= 0.0 => Some(source as usize),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureSource {
+    pub image: usize,
+    /// The extension the image came from, or `None` for the core `source`.
+    pub extension: Option<TextureSourceKind>,
+}
+
+impl<E: Extensions> Texture<E>
+where
+    E::TextureExtensions: TextureSourceExtensions,
+{
+    /// Pick the image to load for this texture.
+    ///
+    /// `supported` lists the extensions the caller can decode, in order of preference.
+    /// The first one present on the texture wins, otherwise the core `source` is used.
+    pub fn preferred_source(&self, supported: &[TextureSourceKind]) -> Option<TextureSource> {
+        supported
+            .iter()
+            .find_map(|&kind| {
+                self.extensions.source(kind).map(|image| TextureSource {
+                    image,
+                    extension: Some(kind),
+                })
+            })
+            .or_else(|| {
+                self.source.map(|image| TextureSource {
+                    image,
+                    extension: None,
+                })
+            })
+    }
+}
+
+#[derive(Debug, DeJson, SerJson)]
 pub struct BufferView<E: Extensions> {
     pub buffer: usize,
     #[nserde(rename = "byteOffset")]
@@ -388,10 +1067,12 @@ pub struct BufferView<E: Extensions> {
     pub name: Option<String>,
     #[nserde(default)]
     pub extensions: E::BufferViewExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Accessor {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Accessor<E: Extensions> {
     #[nserde(rename = "bufferView")]
     pub buffer_view: Option<usize>,
     #[nserde(rename = "byteOffset")]
@@ -404,16 +1085,20 @@ pub struct Accessor {
     pub count: usize,
     #[nserde(rename = "type")]
     pub accessor_type: AccessorType,
-    pub sparse: Option<Sparse>,
+    pub sparse: Option<Sparse<E>>,
     // todo: these could be changed to enum { Int, Float }.
-    pub min: Option<Vec<f32>>,
-    pub max: Option<Vec<f32>>,
+    pub min: Option<Vec<f64>>,
+    pub max: Option<Vec<f64>>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::AccessorExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-impl Accessor {
-    pub fn byte_length<E: Extensions>(&self, buffer_view: &BufferView<E>) -> usize {
+impl<E: Extensions> Accessor<E> {
+    pub fn byte_length(&self, buffer_view: &BufferView<E>) -> usize {
         self.count
             * buffer_view.byte_stride.unwrap_or_else(|| {
                 self.component_type.byte_size() * self.accessor_type.num_components()
@@ -421,15 +1106,19 @@ impl Accessor {
     }
 }
 
-#[derive(Debug, DeJson)]
-pub struct Sparse {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Sparse<E: Extensions> {
     pub count: usize,
-    pub indices: SparseIndices,
-    pub values: SparseValues,
+    pub indices: SparseIndices<E>,
+    pub values: SparseValues<E>,
+    #[nserde(default)]
+    pub extensions: E::AccessorExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct SparseIndices {
+#[derive(Debug, DeJson, SerJson)]
+pub struct SparseIndices<E: Extensions> {
     #[nserde(rename = "bufferView")]
     pub buffer_view: usize,
     #[nserde(rename = "byteOffset")]
@@ -437,15 +1126,23 @@ pub struct SparseIndices {
     pub byte_offset: usize,
     #[nserde(rename = "componentType")]
     pub component_type: ComponentType,
+    #[nserde(default)]
+    pub extensions: E::AccessorExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct SparseValues {
+#[derive(Debug, DeJson, SerJson)]
+pub struct SparseValues<E: Extensions> {
     #[nserde(rename = "bufferView")]
     pub buffer_view: usize,
     #[nserde(rename = "byteOffset")]
     #[nserde(default)]
     pub byte_offset: usize,
+    #[nserde(default)]
+    pub extensions: E::AccessorExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -456,6 +1153,9 @@ pub enum ComponentType {
     Short,
     UnsignedInt,
     Float,
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Unknown(u64),
 }
 
 impl ComponentType {
@@ -464,6 +1164,8 @@ impl ComponentType {
             Self::UnsignedByte | Self::Byte => 1,
             Self::UnsignedShort | Self::Short => 2,
             Self::UnsignedInt | Self::Float => 4,
+            // Readers reject unknown component types before using their size.
+            Self::Unknown(_) => 0,
         }
     }
 }
@@ -481,7 +1183,7 @@ impl DeJson for ComponentType {
                 5123 => Self::UnsignedShort,
                 5125 => Self::UnsignedInt,
                 5126 => Self::Float,
-                _ => return Err(state.err_range(&ty.to_string())),
+                _ => Self::Unknown(*ty),
             },
             _ => return Err(state.err_token("U64")),
         };
@@ -492,7 +1194,23 @@ impl DeJson for ComponentType {
     }
 }
 
-#[derive(Debug, DeJson, PartialEq)]
+impl SerJson for ComponentType {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        let ty: u64 = match self {
+            Self::Byte => 5120,
+            Self::UnsignedByte => 5121,
+            Self::Short => 5122,
+            Self::UnsignedShort => 5123,
+            Self::UnsignedInt => 5125,
+            Self::Float => 5126,
+            Self::Unknown(ty) => *ty,
+        };
+
+        ty.ser_json(d, s)
+    }
+}
+
+#[derive(Debug, DeJson, SerJson, PartialEq)]
 pub enum AccessorType {
     #[nserde(rename = "SCALAR")]
     Scalar,
@@ -523,7 +1241,7 @@ impl AccessorType {
     }
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct Material<E: Extensions> {
     #[nserde(rename = "pbrMetallicRoughness")]
     #[nserde(default)]
@@ -550,25 +1268,78 @@ pub struct Material<E: Extensions> {
     pub name: Option<String>,
     #[nserde(default)]
     pub extensions: E::MaterialExtensions,
+    #[nserde(default)]
+    pub extras: E::MaterialExtras,
+}
+
+/// The default material from the spec, to be used for primitives without one.
+impl<E: Extensions> Default for Material<E> {
+    fn default() -> Self {
+        Self {
+            pbr_metallic_roughness: Default::default(),
+            normal_texture: None,
+            occlusion_texture: None,
+            emissive_texture: None,
+            emissive_factor: [0.0; 3],
+            alpha_mode: AlphaMode::Opaque,
+            alpha_cutoff: 0.5,
+            double_sided: false,
+            #[cfg(feature = "names")]
+            name: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AlphaMode {
-    #[nserde(rename = "OPAQUE")]
+    #[default]
     Opaque,
-    #[nserde(rename = "MASK")]
     Mask,
-    #[nserde(rename = "BLEND")]
     Blend,
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Other(UnknownName),
 }
 
-impl Default for AlphaMode {
-    fn default() -> Self {
-        Self::Opaque
+impl DeJson for AlphaMode {
+    fn de_json(
+        state: &mut nanoserde::DeJsonState,
+        input: &mut core::str::Chars,
+    ) -> Result<Self, nanoserde::DeJsonErr> {
+        let ty = match &state.tok {
+            nanoserde::DeJsonTok::Str => match state.strbuf.as_str() {
+                "OPAQUE" => Self::Opaque,
+                "MASK" => Self::Mask,
+                "BLEND" => Self::Blend,
+                other => match UnknownName::new(other) {
+                    Some(name) => Self::Other(name),
+                    None => return Err(state.err_enum(other)),
+                },
+            },
+            _ => return Err(state.err_token("String")),
+        };
+
+        state.next_tok(input)?;
+
+        Ok(ty)
+    }
+}
+
+impl SerJson for AlphaMode {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        match self {
+            Self::Opaque => "OPAQUE",
+            Self::Mask => "MASK",
+            Self::Blend => "BLEND",
+            Self::Other(other) => other.as_str(),
+        }
+        .ser_json(d, s)
     }
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct PbrMetallicRoughness<E: Extensions> {
     #[nserde(rename = "baseColorFactor")]
     #[nserde(default = "[1.0, 1.0, 1.0, 1.0]")]
@@ -583,6 +1354,8 @@ pub struct PbrMetallicRoughness<E: Extensions> {
     pub roughness_factor: f32,
     #[nserde(rename = "metallicRoughnessTexture")]
     pub metallic_roughness_texture: Option<TextureInfo<E>>,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
 impl<E: Extensions> Default for PbrMetallicRoughness<E> {
@@ -593,11 +1366,12 @@ impl<E: Extensions> Default for PbrMetallicRoughness<E> {
             metallic_factor: 1.0,
             roughness_factor: 1.0,
             metallic_roughness_texture: None,
+            extras: Default::default(),
         }
     }
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct TextureInfo<E: Extensions> {
     pub index: usize,
     #[nserde(rename = "texCoord")]
@@ -605,9 +1379,11 @@ pub struct TextureInfo<E: Extensions> {
     pub tex_coord: usize,
     #[nserde(default)]
     pub extensions: E::TextureInfoExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct NormalTextureInfo<E: Extensions> {
     pub index: usize,
     #[nserde(rename = "texCoord")]
@@ -617,9 +1393,11 @@ pub struct NormalTextureInfo<E: Extensions> {
     pub scale: f32,
     #[nserde(default)]
     pub extensions: E::TextureInfoExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson, Clone)]
+#[derive(Debug, DeJson, SerJson, Clone)]
 pub struct OcclusionTextureInfo<E: Extensions> {
     pub index: usize,
     #[nserde(rename = "texCoord")]
@@ -629,10 +1407,12 @@ pub struct OcclusionTextureInfo<E: Extensions> {
     pub strength: f32,
     #[nserde(default)]
     pub extensions: E::TextureInfoExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct Sampler {
+#[derive(Debug, DeJson, SerJson)]
+pub struct Sampler<E: Extensions> {
     #[nserde(rename = "magFilter")]
     pub mag_filter: Option<FilterMode>,
     #[nserde(rename = "minFilter")]
@@ -645,12 +1425,20 @@ pub struct Sampler {
     pub wrap_t: SamplerWrap,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::SamplerExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum FilterMode {
     Nearest,
+    #[default]
     Linear,
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Unknown(u64),
 }
 
 impl DeJson for FilterMode {
@@ -662,7 +1450,7 @@ impl DeJson for FilterMode {
             nanoserde::DeJsonTok::U64(ty) => match ty {
                 9728 => Self::Nearest,
                 9729 => Self::Linear,
-                _ => return Err(state.err_range(&ty.to_string())),
+                _ => Self::Unknown(*ty),
             },
             _ => return Err(state.err_token("U64")),
         };
@@ -673,12 +1461,33 @@ impl DeJson for FilterMode {
     }
 }
 
+impl SerJson for FilterMode {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        let ty: u64 = match self {
+            Self::Nearest => 9728,
+            Self::Linear => 9729,
+            Self::Unknown(ty) => *ty,
+        };
+
+        ty.ser_json(d, s)
+    }
+}
+
 #[derive(Debug)]
 pub struct MinFilter {
     pub mode: FilterMode,
     pub mipmap: Option<FilterMode>,
 }
 
+impl Default for MinFilter {
+    fn default() -> Self {
+        Self {
+            mode: FilterMode::Linear,
+            mipmap: None,
+        }
+    }
+}
+
 impl DeJson for MinFilter {
     fn de_json(
         state: &mut nanoserde::DeJsonState,
@@ -710,7 +1519,10 @@ impl DeJson for MinFilter {
                     mode: FilterMode::Linear,
                     mipmap: Some(FilterMode::Linear),
                 },
-                _ => return Err(state.err_range(&ty.to_string())),
+                _ => Self {
+                    mode: FilterMode::Unknown(*ty),
+                    mipmap: None,
+                },
             },
             _ => return Err(state.err_token("U64")),
         };
@@ -721,11 +1533,31 @@ impl DeJson for MinFilter {
     }
 }
 
-#[derive(Debug)]
+impl SerJson for MinFilter {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        let ty: u64 = match (&self.mode, &self.mipmap) {
+            (FilterMode::Nearest, None) => 9728,
+            (FilterMode::Linear, None) => 9729,
+            (FilterMode::Nearest, Some(FilterMode::Nearest)) => 9984,
+            (FilterMode::Linear, Some(FilterMode::Nearest)) => 9985,
+            (FilterMode::Nearest, Some(FilterMode::Linear)) => 9986,
+            (FilterMode::Linear, Some(FilterMode::Linear)) => 9987,
+            (FilterMode::Unknown(ty), _) | (_, Some(FilterMode::Unknown(ty))) => *ty,
+        };
+
+        ty.ser_json(d, s)
+    }
+}
+
+#[derive(Debug, Default)]
 pub enum SamplerWrap {
     ClampToEdge,
     MirroredRepeat,
+    #[default]
     Repeat,
+    /// An unrecognised value. Parsing a [`Gltf`] rejects these unless
+    /// [`ParseOptions::tolerate_unknown_enum_values`] is set.
+    Unknown(u64),
 }
 
 impl DeJson for SamplerWrap {
@@ -738,7 +1570,7 @@ impl DeJson for SamplerWrap {
                 33071 => Self::ClampToEdge,
                 33648 => Self::MirroredRepeat,
                 10497 => Self::Repeat,
-                _ => return Err(state.err_range(&ty.to_string())),
+                _ => Self::Unknown(*ty),
             },
             _ => return Err(state.err_token("U64")),
         };
@@ -749,40 +1581,59 @@ impl DeJson for SamplerWrap {
     }
 }
 
-impl Default for SamplerWrap {
-    fn default() -> Self {
-        Self::Repeat
+impl SerJson for SamplerWrap {
+    fn ser_json(&self, d: usize, s: &mut nanoserde::SerJsonState) {
+        let ty: u64 = match self {
+            Self::ClampToEdge => 33071,
+            Self::MirroredRepeat => 33648,
+            Self::Repeat => 10497,
+            Self::Unknown(ty) => *ty,
+        };
+
+        ty.ser_json(d, s)
     }
 }
 
-#[derive(Debug, DeJson)]
-pub struct Camera {
-    pub perspective: Option<CameraPerspective>,
-    pub orthographic: Option<CameraOrthographic>,
+#[derive(Debug, DeJson, SerJson)]
+pub struct Camera<E: Extensions> {
+    pub perspective: Option<CameraPerspective<E>>,
+    pub orthographic: Option<CameraOrthographic<E>>,
     #[nserde(rename = "type")]
     pub ty: CameraType,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::CameraExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
-pub struct CameraPerspective {
+#[derive(Debug, DeJson, SerJson)]
+pub struct CameraPerspective<E: Extensions> {
     pub yfov: f32,
     pub znear: f32,
     pub zfar: Option<f32>,
     #[nserde(rename = "aspectRatio")]
     pub aspect_ratio: Option<f32>,
+    #[nserde(default)]
+    pub extensions: E::CameraExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson, Clone, Copy)]
-pub struct CameraOrthographic {
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct CameraOrthographic<E: Extensions> {
     pub xmag: f32,
     pub ymag: f32,
     pub zfar: f32,
     pub znear: f32,
+    #[nserde(default)]
+    pub extensions: E::CameraExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
-#[derive(Debug, DeJson)]
+#[derive(Debug, DeJson, SerJson)]
 pub enum CameraType {
     #[nserde(rename = "perspective")]
     Perspective,
@@ -790,88 +1641,358 @@ pub enum CameraType {
     Orthographic,
 }
 
-#[derive(Debug, DeJson, Clone)]
-pub struct Scene {
+#[derive(Debug, DeJson, SerJson, Clone)]
+pub struct Scene<E: Extensions> {
+    #[nserde(default)]
     pub nodes: Vec<usize>,
     #[cfg(feature = "names")]
     pub name: Option<String>,
+    #[nserde(default)]
+    pub extensions: E::SceneExtensions,
+    #[nserde(default)]
+    pub extras: E::Extras,
 }
 
 pub mod default_extensions {
+    // Unused when every extension feature is disabled.
+    #[allow(unused_imports)]
     use crate::extensions;
-    use nanoserde::DeJson;
+    #[cfg(feature = "no_std")]
+    #[allow(unused_imports)]
+    use crate::prelude::*;
+    use nanoserde::{DeJson, SerJson};
 
-    #[derive(Debug, Default, Clone, Copy, DeJson)]
+    #[derive(Debug, Default, Clone, Copy, DeJson, SerJson)]
     pub struct Extensions;
 
     impl super::Extensions for Extensions {
         type RootExtensions = RootExtensions;
+        type ImageExtensions = ImageExtensions;
+        type SamplerExtensions = SamplerExtensions;
         type TextureExtensions = TextureExtensions;
         type TextureInfoExtensions = TextureInfoExtensions;
         type MaterialExtensions = MaterialExtensions<Self>;
+        type MaterialExtras = MaterialExtras;
         type BufferExtensions = BufferExtensions;
         type NodeExtensions = NodeExtensions;
         type NodeExtras = NodeExtras;
+        type MeshExtensions = MeshExtensions;
+        type SceneExtensions = SceneExtensions;
+        type SkinExtensions = SkinExtensions;
         type BufferViewExtensions = BufferViewExtensions;
+        type AccessorExtensions = AccessorExtensions;
+        type PrimitiveExtensions = PrimitiveExtensions;
+        type AnimationExtensions = AnimationExtensions;
+        type ChannelExtensions = ChannelExtensions;
+        type TargetExtensions = TargetExtensions;
+        type AnimationSamplerExtensions = AnimationSamplerExtensions;
+        type CameraExtensions = CameraExtensions;
+        type AssetExtensions = AssetExtensions;
+        type AssetExtras = AssetExtras;
+        type Extras = Extras;
+    }
+
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
+    pub struct PrimitiveExtensions {
+        #[cfg(feature = "khr_draco")]
+        #[nserde(rename = "KHR_draco_mesh_compression")]
+        pub khr_draco_mesh_compression: Option<extensions::KhrDracoMeshCompression>,
+        #[cfg(feature = "fb_ngon_encoding")]
+        #[nserde(rename = "FB_ngon_encoding")]
+        pub fb_ngon_encoding: Option<extensions::FbNgonEncoding>,
+        #[cfg(not(any(feature = "khr_draco", feature = "fb_ngon_encoding")))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
+    }
+
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
+    pub struct TargetExtensions {
+        #[cfg(feature = "khr_animation_pointer")]
+        #[nserde(rename = "KHR_animation_pointer")]
+        pub khr_animation_pointer: Option<extensions::KhrAnimationPointer>,
+        #[cfg(not(feature = "khr_animation_pointer"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
+    }
+
+    // Implements `DeJson` for empty structs by skipping over whatever object is present, as the
+    // derived implementation errors on any field. Structs whose fields all depend on features
+    // instead get a skipped `_unsupported` field when they're all disabled, which has the same
+    // effect.
+    macro_rules! skip_de_json {
+        ($($ty:ident),*) => {
+            $(
+                impl DeJson for $ty {
+                    fn de_json(
+                        state: &mut nanoserde::DeJsonState,
+                        input: &mut core::str::Chars,
+                    ) -> Result<Self, nanoserde::DeJsonErr> {
+                        crate::json::skip_object(state, input)?;
+                        Ok(Self {})
+                    }
+                }
+            )*
+        };
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    skip_de_json!(
+        AssetExtensions,
+        AssetExtras,
+        MeshExtensions,
+        SceneExtensions,
+        AnimationExtensions,
+        ChannelExtensions,
+        AnimationSamplerExtensions,
+        CameraExtensions,
+        SkinExtensions,
+        ImageExtensions,
+        SamplerExtensions,
+        AccessorExtensions,
+        Extras
+    );
+
+    /// No asset extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct AssetExtensions {}
+
+    /// Asset extras are commonly written by exporters but have no standard contents, so they're
+    /// skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct AssetExtras {}
+
+    /// No mesh extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct MeshExtensions {}
+
+    /// No scene extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct SceneExtensions {}
+
+    /// No animation extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct AnimationExtensions {}
+
+    /// No animation channel extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct ChannelExtensions {}
+
+    /// No animation sampler extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct AnimationSamplerExtensions {}
+
+    /// No camera extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct CameraExtensions {}
+
+    /// No skin extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct SkinExtensions {}
+
+    /// No image extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct ImageExtensions {}
+
+    /// No sampler extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct SamplerExtensions {}
+
+    /// No accessor extensions are currently supported, so their contents are skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct AccessorExtensions {}
+
+    /// Extras have no standard contents, so they're skipped.
+    #[derive(Debug, SerJson, Default, Clone)]
+    pub struct Extras {}
+
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct RootExtensions {
+        #[cfg(feature = "khr_lights")]
         #[nserde(rename = "KHR_lights_punctual")]
         pub khr_lights_punctual: Option<extensions::KhrLightsPunctual>,
+        #[cfg(feature = "omi")]
+        #[nserde(rename = "OMI_collider")]
+        pub omi_collider: Option<extensions::OmiCollider>,
+        #[cfg(feature = "omi")]
+        #[nserde(rename = "OMI_physics_shape")]
+        pub omi_physics_shape: Option<extensions::OmiPhysicsShape>,
+        #[cfg(feature = "vrm")]
+        #[nserde(rename = "VRMC_vrm")]
+        pub vrmc_vrm: Option<extensions::VrmcVrm>,
+        #[cfg(feature = "vrm")]
+        #[nserde(rename = "VRMC_springBone")]
+        pub vrmc_spring_bone: Option<extensions::VrmcSpringBone>,
+        #[cfg(feature = "mpeg")]
+        #[nserde(rename = "MPEG_media")]
+        pub mpeg_media: Option<extensions::MpegMedia>,
+        #[cfg(not(any(
+            feature = "khr_lights",
+            feature = "omi",
+            feature = "vrm",
+            feature = "mpeg"
+        )))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct BufferExtensions {
+        #[cfg(feature = "ext_meshopt")]
         #[nserde(rename = "EXT_meshopt_compression")]
         pub ext_meshopt_compression: Option<extensions::ExtMeshoptCompressionBuffer>,
+        #[cfg(not(feature = "ext_meshopt"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct NodeExtensions {
+        #[cfg(feature = "khr_lights")]
+        #[nserde(rename = "KHR_lights_punctual")]
+        pub khr_lights_punctual: Option<extensions::KhrLightsPunctualNode>,
+        #[cfg(feature = "ext_mesh_gpu_instancing")]
         #[nserde(rename = "EXT_mesh_gpu_instancing")]
         pub ext_mesh_gpu_instancing: Option<extensions::ExtMeshGpuInstancing>,
+        #[cfg(feature = "msft_lod")]
         #[nserde(rename = "MSFT_lod")]
         pub msft_lod: Option<extensions::MsftLod>,
+        #[cfg(feature = "omi")]
+        #[nserde(rename = "OMI_collider")]
+        pub omi_collider: Option<extensions::OmiColliderNode>,
+        #[cfg(feature = "omi")]
+        #[nserde(rename = "OMI_physics_body")]
+        pub omi_physics_body: Option<extensions::OmiPhysicsBody>,
+        #[cfg(not(any(
+            feature = "khr_lights",
+            feature = "ext_mesh_gpu_instancing",
+            feature = "msft_lod",
+            feature = "omi"
+        )))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct NodeExtras {
+        #[cfg(feature = "msft_lod")]
         #[nserde(rename = "MSFT_screencoverage")]
         pub msft_screencoverage: Option<Vec<f32>>,
+        #[cfg(not(feature = "msft_lod"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, Default, DeJson, Clone)]
+    #[derive(Debug, Default, DeJson, SerJson, Clone)]
     pub struct TextureExtensions {
+        #[cfg(feature = "texture_formats")]
         #[nserde(rename = "KHR_texture_basisu")]
         pub khr_texture_basisu: Option<extensions::KhrTextureBasisu>,
+        #[cfg(feature = "texture_formats")]
+        #[nserde(rename = "EXT_texture_astc")]
+        pub ext_texture_astc: Option<extensions::ExtTextureAstc>,
+        #[cfg(feature = "texture_formats")]
+        #[nserde(rename = "EXT_texture_avif")]
+        pub ext_texture_avif: Option<extensions::ExtTextureAvif>,
+        #[cfg(feature = "texture_formats")]
+        #[nserde(rename = "EXT_texture_webp")]
+        pub ext_texture_webp: Option<extensions::ExtTextureWebp>,
+        #[cfg(feature = "texture_formats")]
+        #[nserde(rename = "MSFT_texture_dds")]
+        pub msft_texture_dds: Option<extensions::MsftTextureDds>,
+        #[cfg(feature = "mpeg")]
+        #[nserde(rename = "MPEG_texture_video")]
+        pub mpeg_texture_video: Option<extensions::MpegTextureVideo>,
+        #[cfg(not(any(feature = "texture_formats", feature = "mpeg")))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
+    }
+
+    #[cfg(feature = "texture_formats")]
+    impl super::TextureSourceExtensions for TextureExtensions {
+        fn source(&self, kind: super::TextureSourceKind) -> Option<usize> {
+            use super::TextureSourceKind;
+
+            match kind {
+                TextureSourceKind::KhrTextureBasisu => {
+                    self.khr_texture_basisu.map(|ext| ext.source)
+                }
+                TextureSourceKind::ExtTextureAstc => self.ext_texture_astc.map(|ext| ext.source),
+                TextureSourceKind::ExtTextureAvif => self.ext_texture_avif.map(|ext| ext.source),
+                TextureSourceKind::ExtTextureWebp => self.ext_texture_webp.map(|ext| ext.source),
+                TextureSourceKind::MsftTextureDds => self.msft_texture_dds.map(|ext| ext.source),
+            }
+        }
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct BufferViewExtensions {
+        #[cfg(feature = "ext_meshopt")]
         #[nserde(rename = "EXT_meshopt_compression")]
         pub ext_meshopt_compression: Option<extensions::ExtMeshoptCompression>,
+        #[cfg(not(feature = "ext_meshopt"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, DeJson, Default, Clone)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
     pub struct MaterialExtensions<E: super::Extensions> {
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_sheen")]
         pub khr_materials_sheen: Option<extensions::KhrMaterialsSheen<E>>,
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_emissive_strength")]
         pub khr_materials_emissive_strength: Option<extensions::KhrMaterialsEmissiveStrength>,
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_unlit")]
         pub khr_materials_unlit: Option<extensions::KhrMaterialsUnlit>,
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_ior")]
         pub khr_materials_ior: Option<extensions::KhrMaterialsIor>,
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_specular")]
         pub khr_materials_specular: Option<extensions::KhrMaterialsSpecular<E>>,
+        #[cfg(feature = "khr_materials")]
         #[nserde(rename = "KHR_materials_transmission")]
         pub khr_materials_transmission: Option<extensions::KhrMaterialsTransmission<E>>,
+        #[cfg(feature = "adobe_materials_thin_transparency")]
+        #[nserde(rename = "ADOBE_materials_thin_transparency")]
+        pub adobe_materials_thin_transparency:
+            Option<extensions::AdobeMaterialsThinTransparency<E>>,
+        #[cfg(feature = "msft_lod")]
+        #[nserde(rename = "MSFT_lod")]
+        pub msft_lod: Option<extensions::MsftLod>,
+        #[cfg(not(any(
+            feature = "khr_materials",
+            feature = "adobe_materials_thin_transparency",
+            feature = "msft_lod"
+        )))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
+        // `MSFT_lod` isn't generic, so `E` still needs using without the other extensions.
+        #[cfg(not(any(
+            feature = "khr_materials",
+            feature = "adobe_materials_thin_transparency"
+        )))]
+        #[nserde(skip)]
+        _extensions: Option<core::marker::PhantomData<E>>,
+    }
+
+    #[derive(Debug, DeJson, SerJson, Default, Clone)]
+    pub struct MaterialExtras {
+        #[cfg(feature = "msft_lod")]
+        #[nserde(rename = "MSFT_screencoverage")]
+        pub msft_screencoverage: Option<Vec<f32>>,
+        #[cfg(not(feature = "msft_lod"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 
-    #[derive(Debug, DeJson, Default, Clone, Copy)]
+    #[derive(Debug, DeJson, SerJson, Default, Clone, Copy)]
     pub struct TextureInfoExtensions {
+        #[cfg(feature = "khr_texture_transform")]
         #[nserde(rename = "KHR_texture_transform")]
         pub khr_texture_transform: Option<extensions::KhrTextureTransform>,
+        #[cfg(not(feature = "khr_texture_transform"))]
+        #[nserde(skip)]
+        _unsupported: Option<()>,
     }
 }
@@ -0,0 +1,108 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use nanoserde::{DeJsonErr, DeJsonState, DeJsonTok};
+
+/// Limits on the shape of the json that are checked before any deserialization happens.
+///
+/// Used to protect against malicious input that would otherwise cause huge allocations or deep
+/// recursion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// The maximum nesting depth of arrays and objects.
+    pub max_depth: usize,
+    /// The maximum number of elements in a single array.
+    pub max_array_length: usize,
+    /// The maximum length of a single string, in bytes.
+    pub max_string_length: usize,
+    /// The maximum number of values in the whole document, including nested ones.
+    pub max_total_elements: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: crate::json::MAX_DEPTH,
+            max_array_length: 1 << 20,
+            max_string_length: 16 << 20,
+            max_total_elements: 1 << 24,
+        }
+    }
+}
+
+impl ParseLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_array_length: usize::MAX,
+            max_string_length: usize::MAX,
+            max_total_elements: usize::MAX,
+        }
+    }
+
+    /// Tokenize `json` and check it against the limits, without building any values.
+    pub fn check(&self, json: &str) -> Result<(), DeJsonErr> {
+        let mut state = DeJsonState::default();
+        let mut input = json.chars();
+        state.next(&mut input);
+        state.next_tok(&mut input)?;
+
+        // The number of elements seen so far in each open array, or `None` for objects.
+        let mut stack: Vec<Option<usize>> = Vec::new();
+        let mut total_elements = 0usize;
+        // Whether the current token starts a value, as opposed to being an object key.
+        let mut expecting_value = true;
+
+        loop {
+            if expecting_value && !matches!(state.tok, DeJsonTok::BlockClose | DeJsonTok::Eof) {
+                total_elements += 1;
+                if total_elements > self.max_total_elements {
+                    return Err(exceeded(&state, "total elements", self.max_total_elements));
+                }
+
+                if let Some(Some(length)) = stack.last_mut() {
+                    *length += 1;
+                    if *length > self.max_array_length {
+                        return Err(exceeded(&state, "array length", self.max_array_length));
+                    }
+                }
+            }
+
+            match state.tok {
+                DeJsonTok::CurlyOpen | DeJsonTok::BlockOpen => {
+                    if stack.len() == self.max_depth {
+                        return Err(exceeded(&state, "nesting depth", self.max_depth));
+                    }
+
+                    let is_array = state.tok == DeJsonTok::BlockOpen;
+                    stack.push(if is_array { Some(0) } else { None });
+                    expecting_value = is_array;
+                }
+                DeJsonTok::CurlyClose | DeJsonTok::BlockClose => {
+                    stack.pop();
+                    expecting_value = false;
+                }
+                DeJsonTok::Str => {
+                    if state.strbuf.len() > self.max_string_length {
+                        return Err(exceeded(&state, "string length", self.max_string_length));
+                    }
+                    expecting_value = false;
+                }
+                DeJsonTok::Colon => expecting_value = true,
+                // Inside objects, a comma is followed by a key.
+                DeJsonTok::Comma => expecting_value = matches!(stack.last(), Some(Some(_))),
+                DeJsonTok::Eof => return Ok(()),
+                _ => expecting_value = false,
+            }
+
+            state.next_tok(&mut input)?;
+        }
+    }
+}
+
+pub(crate) fn exceeded(state: &DeJsonState, what: &str, limit: usize) -> DeJsonErr {
+    DeJsonErr {
+        msg: format!("Parse limit exceeded: {} is over {}", what, limit),
+        line: state.line,
+        col: state.col,
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(not(feature = "no_std"))]
+pub fn dot4(a: [f32; 4], b: [f32; 4]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+}
+
+#[cfg(not(feature = "no_std"))]
+pub fn normalize4(q: [f32; 4]) -> [f32; 4] {
+    let length = dot4(q, q).sqrt();
+
+    if length > 0.0 {
+        q.map(|v| v / length)
+    } else {
+        [0.0, 0.0, 0.0, 1.0]
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+pub fn slerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    let mut cos_theta = dot4(a, b);
+
+    // Take the shortest path.
+    let b = if cos_theta < 0.0 {
+        cos_theta = -cos_theta;
+        b.map(|v| -v)
+    } else {
+        b
+    };
+
+    // Fall back to a normalized lerp when the quaternions are nearly parallel.
+    if cos_theta > 0.9995 {
+        return normalize4(core::array::from_fn(|i| a[i] + (b[i] - a[i]) * t));
+    }
+
+    let theta = cos_theta.acos();
+    let sin_theta = theta.sin();
+    let weight_a = ((1.0 - t) * theta).sin() / sin_theta;
+    let weight_b = (t * theta).sin() / sin_theta;
+
+    core::array::from_fn(|i| a[i] * weight_a + b[i] * weight_b)
+}
+
+pub type Mat4 = [f32; 16];
+
+pub const IDENTITY: Mat4 = [
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+];
+
+// Matrices are column-major, as in gltf.
+pub fn mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    core::array::from_fn(|i| {
+        let column = i / 4;
+        let row = i % 4;
+        (0..4).map(|k| a[k * 4 + row] * b[column * 4 + k]).sum()
+    })
+}
+
+#[cfg(not(feature = "no_std"))]
+pub fn transform_point(m: &Mat4, p: [f32; 3]) -> [f32; 3] {
+    core::array::from_fn(|row| m[row] * p[0] + m[4 + row] * p[1] + m[8 + row] * p[2] + m[12 + row])
+}
+
+/// The cofactor matrix of the upper 3x3, which transforms normals correctly even under
+/// non-uniform scaling (up to a scale factor).
+#[cfg(not(feature = "no_std"))]
+pub fn transform_normal(m: &Mat4, n: [f32; 3]) -> [f32; 3] {
+    let x = [m[0], m[1], m[2]];
+    let y = [m[4], m[5], m[6]];
+    let z = [m[8], m[9], m[10]];
+
+    let cofactor = [cross(y, z), cross(z, x), cross(x, y)];
+
+    core::array::from_fn(|row| {
+        cofactor[0][row] * n[0] + cofactor[1][row] * n[1] + cofactor[2][row] * n[2]
+    })
+}
+
+pub fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(not(feature = "no_std"))]
+pub fn normalize3(v: [f32; 3]) -> [f32; 3] {
+    let length = dot3(v, v).sqrt();
+
+    if length > 0.0 {
+        v.map(|c| c / length)
+    } else {
+        v
+    }
+}
+
+/// Build a matrix from a translation, rotation and scale.
+pub fn from_trs(translation: [f32; 3], rotation: [f32; 4], scale: [f32; 3]) -> Mat4 {
+    let [x, y, z, w] = rotation;
+
+    [
+        (1.0 - 2.0 * (y * y + z * z)) * scale[0],
+        (2.0 * (x * y + z * w)) * scale[0],
+        (2.0 * (x * z - y * w)) * scale[0],
+        0.0,
+        (2.0 * (x * y - z * w)) * scale[1],
+        (1.0 - 2.0 * (x * x + z * z)) * scale[1],
+        (2.0 * (y * z + x * w)) * scale[1],
+        0.0,
+        (2.0 * (x * z + y * w)) * scale[2],
+        (2.0 * (y * z - x * w)) * scale[2],
+        (1.0 - 2.0 * (x * x + y * y)) * scale[2],
+        0.0,
+        translation[0],
+        translation[1],
+        translation[2],
+        1.0,
+    ]
+}
+
+#[cfg(not(feature = "no_std"))]
+/// Split a matrix without shear into translation, rotation and scale.
+pub fn decompose(m: &Mat4) -> ([f32; 3], [f32; 4], [f32; 3]) {
+    let translation = [m[12], m[13], m[14]];
+
+    let x = [m[0], m[1], m[2]];
+    let y = [m[4], m[5], m[6]];
+    let z = [m[8], m[9], m[10]];
+
+    let mut scale = [dot3(x, x).sqrt(), dot3(y, y).sqrt(), dot3(z, z).sqrt()];
+
+    // A negative determinant means the matrix mirrors, which is folded into the x scale.
+    if dot3(cross(x, y), z) < 0.0 {
+        scale[0] = -scale[0];
+    }
+
+    let axis = |v: [f32; 3], s: f32| {
+        if s != 0.0 {
+            v.map(|c| c / s)
+        } else {
+            v
+        }
+    };
+
+    let x = axis(x, scale[0]);
+    let y = axis(y, scale[1]);
+    let z = axis(z, scale[2]);
+
+    let trace = x[0] + y[1] + z[2];
+
+    let rotation = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [
+            (y[2] - z[1]) / s,
+            (z[0] - x[2]) / s,
+            (x[1] - y[0]) / s,
+            s / 4.0,
+        ]
+    } else if x[0] > y[1] && x[0] > z[2] {
+        let s = (1.0 + x[0] - y[1] - z[2]).sqrt() * 2.0;
+        [
+            s / 4.0,
+            (y[0] + x[1]) / s,
+            (z[0] + x[2]) / s,
+            (y[2] - z[1]) / s,
+        ]
+    } else if y[1] > z[2] {
+        let s = (1.0 + y[1] - x[0] - z[2]).sqrt() * 2.0;
+        [
+            (y[0] + x[1]) / s,
+            s / 4.0,
+            (z[1] + y[2]) / s,
+            (z[0] - x[2]) / s,
+        ]
+    } else {
+        let s = (1.0 + z[2] - x[0] - y[1]).sqrt() * 2.0;
+        [
+            (z[0] + x[2]) / s,
+            (z[1] + y[2]) / s,
+            s / 4.0,
+            (x[1] - y[0]) / s,
+        ]
+    };
+
+    (translation, normalize4(rotation), scale)
+}
@@ -0,0 +1,92 @@
+use crate::{glb, json_str, Extensions, GlbError, Gltf};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::{Deref, Range};
+use std::path::Path;
+
+/// Why a file couldn't be loaded by [`Gltf::from_path`].
+#[derive(Debug)]
+pub enum FromPathError {
+    Io(std::io::Error),
+    Glb(GlbError),
+}
+
+impl core::fmt::Display for FromPathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Io(error) => error.fmt(f),
+            Self::Glb(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FromPathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Glb(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for FromPathError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<GlbError> for FromPathError {
+    fn from(error: GlbError) -> Self {
+        Self::Glb(error)
+    }
+}
+
+/// The binary chunk of a memory-mapped .glb. It derefs to the chunk's bytes, and keeps the file
+/// mapped until it's dropped.
+#[derive(Debug)]
+pub struct MappedBinary {
+    mmap: Mmap,
+    range: Range<usize>,
+}
+
+impl Deref for MappedBinary {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap[self.range.clone()]
+    }
+}
+
+impl AsRef<[u8]> for MappedBinary {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Load a gltf or glb file by memory-mapping it, so that the binary chunk of a large .glb
+    /// isn't read into memory upfront or copied. Only the pages that get used are read.
+    ///
+    /// # Safety
+    ///
+    /// The file mustn't be modified or truncated, by this or another process, while the returned
+    /// [`MappedBinary`] is alive. See [`memmap2::Mmap::map`].
+    pub unsafe fn from_path(
+        path: impl AsRef<Path>,
+    ) -> Result<(Self, Option<MappedBinary>), FromPathError> {
+        let file = File::open(path)?;
+        let mmap = Mmap::map(&file)?;
+
+        let (json_bytes, binary_buffer) = glb::split_chunks(&mmap)?;
+        let gltf = Self::from_json_string(json_str(json_bytes).map_err(GlbError::from)?)
+            .map_err(GlbError::from)?;
+
+        // The chunk borrows from the map, so store its position instead.
+        let range = binary_buffer.map(|binary| {
+            let start = binary.as_ptr() as usize - mmap.as_ptr() as usize;
+            start..start + binary.len()
+        });
+
+        Ok((gltf, range.map(|range| MappedBinary { mmap, range })))
+    }
+}
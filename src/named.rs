@@ -0,0 +1,33 @@
+use crate::*;
+
+/// Objects that can have a name.
+///
+/// The `name` fields only exist with the `names` feature, so this lets code compile either way.
+pub trait Named {
+    /// The name of the object, or `None` if it doesn't have one or the `names` feature is
+    /// disabled.
+    fn name(&self) -> Option<&str>;
+}
+
+macro_rules! named {
+    ($($ty:ident),*) => {
+        $(
+            impl<E: Extensions> Named for $ty<E> {
+                #[cfg(feature = "names")]
+                fn name(&self) -> Option<&str> {
+                    self.name.as_deref()
+                }
+
+                #[cfg(not(feature = "names"))]
+                fn name(&self) -> Option<&str> {
+                    None
+                }
+            }
+        )*
+    };
+}
+
+named!(
+    Accessor, Animation, Buffer, BufferView, Camera, Image, Material, Mesh, Node, Sampler, Scene,
+    Skin, Texture
+);
@@ -0,0 +1,239 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{
+    Accessor, AlphaMode, Animation, Buffer, BufferView, Camera, ComponentType, Extensions,
+    FilterMode, Image, Interpolation, Material, Mesh, Node, ParseLimits, PrimitiveMode, Sampler,
+    SamplerWrap, Skin, Texture,
+};
+use nanoserde::{DeJsonErr, DeJsonState};
+
+/// Controls how forgiving parsing is.
+///
+/// The default rejects unknown enum values but doesn't check that required fields are present,
+/// which is what parsing has always done. Validators usually want [`ParseOptions::strict`], while
+/// runtimes that would rather load a slightly broken file than nothing can use
+/// [`ParseOptions::lenient`]. Individual settings can be changed builder-style:
+///
+/// ```
+/// let options = goth_gltf::ParseOptions::strict()
+///     .tolerate_unknown_enum_values(true)
+///     .limits(goth_gltf::ParseLimits::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    tolerate_unknown_enum_values: bool,
+    default_missing_fields: bool,
+    limits: Option<ParseLimits>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::strict().default_missing_fields(true)
+    }
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        Self {
+            tolerate_unknown_enum_values: false,
+            default_missing_fields: false,
+            limits: None,
+        }
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            tolerate_unknown_enum_values: true,
+            default_missing_fields: true,
+            limits: None,
+        }
+    }
+
+    /// Keep unrecognised values for enums, such as ones from future versions of the spec or
+    /// vendor extensions, as `Unknown` or `Other` variants instead of failing. This covers
+    /// component types, primitive modes, alpha modes, interpolation, sampler filters and
+    /// wrapping. The values are written back out unchanged.
+    ///
+    /// Unrecognised values are always deserialized, and are rejected afterwards with the path of
+    /// the first one unless this is set. Strings longer than [`UnknownName::CAPACITY`] bytes
+    /// can't be kept, so they're an error either way.
+    pub fn tolerate_unknown_enum_values(mut self, tolerate: bool) -> Self {
+        self.tolerate_unknown_enum_values = tolerate;
+        self
+    }
+
+    /// Accept a missing `asset` block and missing or empty required arrays (mesh primitives,
+    /// animation channels and samplers, skin joints) instead of failing. When this is off, the
+    /// error names the first offending field, e.g. `meshes[3].primitives`.
+    pub fn default_missing_fields(mut self, default: bool) -> Self {
+        self.default_missing_fields = default;
+        self
+    }
+
+    /// Check the input against `limits` before deserializing it.
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    pub fn get_limits(&self) -> Option<&ParseLimits> {
+        self.limits.as_ref()
+    }
+
+    pub fn tolerates_unknown_enum_values(&self) -> bool {
+        self.tolerate_unknown_enum_values
+    }
+
+    pub fn defaults_missing_fields(&self) -> bool {
+        self.default_missing_fields
+    }
+}
+
+/// An unrecognised value of a string enum, e.g. an alpha mode from a newer version of the spec.
+///
+/// It's stored inline so that the enums holding it can stay `Copy`, which limits it to
+/// [`CAPACITY`](Self::CAPACITY) bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnknownName {
+    len: u8,
+    bytes: [u8; Self::CAPACITY],
+}
+
+impl UnknownName {
+    pub const CAPACITY: usize = 31;
+
+    /// Returns `None` if `name` is longer than [`CAPACITY`](Self::CAPACITY) bytes.
+    pub fn new(name: &str) -> Option<Self> {
+        let mut bytes = [0; Self::CAPACITY];
+        bytes
+            .get_mut(..name.len())?
+            .copy_from_slice(name.as_bytes());
+
+        Some(Self {
+            len: name.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // The bytes are only ever copied from a whole `&str`.
+        core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+impl core::fmt::Debug for UnknownName {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Objects that can hold enum values, which are checked after deserializing unless the options
+/// tolerate unknown ones.
+pub(crate) trait UnknownEnumValues {
+    /// The path and value of the first unrecognised enum value, relative to the object.
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Accessor<E> {
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        if let ComponentType::Unknown(ty) = self.component_type {
+            return Some(format!("componentType: {}", ty));
+        }
+
+        match self.sparse.as_ref()?.indices.component_type {
+            ComponentType::Unknown(ty) => Some(format!("sparse.indices.componentType: {}", ty)),
+            _ => None,
+        }
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Animation<E> {
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        self.samplers
+            .iter()
+            .enumerate()
+            .find_map(|(index, sampler)| match sampler.interpolation {
+                Interpolation::Other(name) => {
+                    Some(format!("samplers[{}].interpolation: {}", index, name))
+                }
+                _ => None,
+            })
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Material<E> {
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        match self.alpha_mode {
+            AlphaMode::Other(name) => Some(format!("alphaMode: {}", name)),
+            _ => None,
+        }
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Mesh<E> {
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        self.primitives
+            .iter()
+            .enumerate()
+            .find_map(|(index, primitive)| match primitive.mode {
+                PrimitiveMode::Unknown(ty) => Some(format!("primitives[{}].mode: {}", index, ty)),
+                _ => None,
+            })
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Sampler<E> {
+    fn first_unknown_enum_value(&self) -> Option<String> {
+        let min_filter =
+            self.min_filter
+                .as_ref()
+                .and_then(|filter| match (&filter.mode, &filter.mipmap) {
+                    (FilterMode::Unknown(ty), _) | (_, Some(FilterMode::Unknown(ty))) => Some(*ty),
+                    _ => None,
+                });
+
+        if let Some(FilterMode::Unknown(ty)) = &self.mag_filter {
+            Some(format!("magFilter: {}", ty))
+        } else if let Some(ty) = min_filter {
+            Some(format!("minFilter: {}", ty))
+        } else if let SamplerWrap::Unknown(ty) = &self.wrap_s {
+            Some(format!("wrapS: {}", ty))
+        } else if let SamplerWrap::Unknown(ty) = &self.wrap_t {
+            Some(format!("wrapT: {}", ty))
+        } else {
+            None
+        }
+    }
+}
+
+impl<E: Extensions> UnknownEnumValues for Buffer<E> {}
+impl<E: Extensions> UnknownEnumValues for BufferView<E> {}
+impl<E: Extensions> UnknownEnumValues for Camera<E> {}
+impl<E: Extensions> UnknownEnumValues for Image<E> {}
+impl<E: Extensions> UnknownEnumValues for Node<E> {}
+impl<E: Extensions> UnknownEnumValues for Skin<E> {}
+impl<E: Extensions> UnknownEnumValues for Texture<E> {}
+
+/// Reject the first unrecognised enum value in the top-level array `key`, naming its path, e.g.
+/// `meshes[3].primitives[0].mode`.
+pub(crate) fn check_unknown_enum_values<T>(
+    key: &str,
+    values: &[T],
+    first_unknown: impl Fn(&T) -> Option<String>,
+) -> Result<(), DeJsonErr> {
+    for (index, value) in values.iter().enumerate() {
+        if let Some(unknown) = first_unknown(value) {
+            return Err(DeJsonState::default().err_enum(&format!("{}[{}].{}", key, index, unknown)));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{json, ParseLimits};
+use core::str::Chars;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok};
+
+type Handler<'a> = Box<dyn FnMut(&mut DeJsonState, &mut Chars) -> Result<(), DeJsonErr> + 'a>;
+
+/// Parses only the top-level sections the caller asks for, skipping over everything else
+/// without deserializing it. Large arrays can also be streamed an element at a time with
+/// [`each`](Self::each).
+///
+/// Unrecognised enum values come through as their `Unknown` or `Other` variants, as they're only
+/// checked against [`ParseOptions`](crate::ParseOptions) when parsing a whole document.
+///
+/// ```
+/// let json = r#"{"images": [{"uri": "a.png"}], "nodes": [{"mesh": 0}]}"#;
+///
+/// let mut images: Option<Vec<goth_gltf::Image<()>>> = None;
+///
+/// goth_gltf::partial::PartialParser::new()
+///     .section("images", &mut images)
+///     .parse_str(json)
+///     .unwrap();
+///
+/// assert_eq!(images.unwrap().len(), 1);
+/// ```
+#[derive(Default)]
+pub struct PartialParser<'a> {
+    handlers: Vec<(&'a str, Handler<'a>)>,
+    limits: Option<ParseLimits>,
+}
+
+impl<'a> PartialParser<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deserialize the top-level `key` into `output` if it's present.
+    pub fn section<T: DeJson + 'a>(self, key: &'a str, output: &'a mut Option<T>) -> Self {
+        self.visit(key, move |state, input| {
+            *output = Some(T::de_json(state, input)?);
+            Ok(())
+        })
+    }
+
+    /// Deserialize the elements of the top-level array `key` one at a time, calling `callback`
+    /// with the index and value of each, so the whole array is never in memory at once.
+    ///
+    /// ```
+    /// let json = r#"{"nodes": [{"mesh": 0}, {"children": [0]}, {"mesh": 1}]}"#;
+    ///
+    /// let mut meshes_used = Vec::new();
+    ///
+    /// goth_gltf::partial::PartialParser::new()
+    ///     .each("nodes", |_, node: goth_gltf::Node<()>| meshes_used.extend(node.mesh))
+    ///     .parse_str(json)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(meshes_used, [0, 1]);
+    /// ```
+    pub fn each<T: DeJson>(self, key: &'a str, mut callback: impl FnMut(usize, T) + 'a) -> Self {
+        self.visit(key, move |state, input| {
+            state.block_open(input)?;
+
+            let mut index = 0;
+
+            while state.tok != DeJsonTok::BlockClose {
+                callback(index, T::de_json(state, input)?);
+                index += 1;
+                state.eat_comma_block(input)?;
+            }
+
+            state.block_close(input)
+        })
+    }
+
+    /// Check the input against `limits` before parsing any of it. Unrecognised extensions that
+    /// are kept as raw JSON are limited to the default nesting depth either way.
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Call `visitor` with the parser positioned at the value of the top-level `key`.
+    ///
+    /// The visitor must consume exactly one value.
+    pub fn visit(
+        mut self,
+        key: &'a str,
+        visitor: impl FnMut(&mut DeJsonState, &mut Chars) -> Result<(), DeJsonErr> + 'a,
+    ) -> Self {
+        self.handlers.push((key, Box::new(visitor)));
+        self
+    }
+
+    /// Parse either a gltf or a glb file.
+    pub fn parse_bytes(self, bytes: &[u8]) -> Result<(), DeJsonErr> {
+        let (json_bytes, _) = crate::glb::split_chunks(bytes)
+            .map_err(|error| DeJsonState::default().err_parse(&error.to_string()))?;
+        self.parse_str(crate::json_str(json_bytes)?)
+    }
+
+    pub fn parse_str(mut self, json: &str) -> Result<(), DeJsonErr> {
+        if let Some(limits) = self.limits {
+            limits.check(json)?;
+        }
+
+        let mut state = DeJsonState::default();
+        let mut input = json.chars();
+        state.next(&mut input);
+        state.next_tok(&mut input)?;
+
+        state.curly_open(&mut input)?;
+
+        while state.next_str().is_some() {
+            let handler = self
+                .handlers
+                .iter_mut()
+                .find(|(key, _)| *key == state.strbuf)
+                .map(|(_, handler)| handler);
+
+            state.next_colon(&mut input)?;
+
+            match handler {
+                Some(handler) => handler(&mut state, &mut input)?,
+                None => json::skip_value(&mut state, &mut input)?,
+            }
+
+            state.eat_comma_curly(&mut input)?;
+        }
+
+        if state.tok != DeJsonTok::CurlyClose {
+            return Err(state.err_token("}"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::json::{object_members, write_object, RawValue};
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::HashMap;
+use core::str::Chars;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok, SerJson, SerJsonState};
+
+/// Wraps a set of typed extensions, keeping any extension it doesn't parse as raw JSON.
+///
+/// This is opt-in, by using it for any of the [`Extensions`](crate::Extensions) associated
+/// types in a custom extension set, e.g.
+/// `type NodeExtensions = Passthrough<default_extensions::NodeExtensions>`. When serialized, the
+/// unknown extensions are written back out after the known ones.
+///
+/// `T` needs to deserialize from an object, so `()` doesn't work here. An extension counts as
+/// known if it's present when `T` is serialized again.
+#[derive(Debug, Clone, Default)]
+pub struct Passthrough<T> {
+    pub known: T,
+    /// The JSON of each unknown extension, keyed by extension name.
+    pub unknown: HashMap<String, String>,
+}
+
+impl<T: DeJson + SerJson> DeJson for Passthrough<T> {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Self, DeJsonErr> {
+        if state.tok != DeJsonTok::CurlyOpen {
+            return Err(state.err_token("{"));
+        }
+
+        let members = match RawValue::de_json(state, input)? {
+            RawValue::Object(members) => members,
+            _ => unreachable!(),
+        };
+
+        // Errors from the nested parse have positions within the extensions object, which
+        // aren't useful on their own.
+        let known = T::deserialize_json(&write_object(&members)).map_err(|err| DeJsonErr {
+            msg: err.msg,
+            line: state.line,
+            col: state.col,
+        })?;
+
+        let known_names = object_members(&known.serialize_json());
+
+        let unknown = members
+            .into_iter()
+            .filter(|(name, _)| !known_names.iter().any(|(known, _)| known == name))
+            .map(|(name, value)| {
+                let mut json = String::new();
+                value.write(&mut json);
+                (name, json)
+            })
+            .collect();
+
+        Ok(Self { known, unknown })
+    }
+}
+
+impl<T: SerJson> SerJson for Passthrough<T> {
+    fn ser_json(&self, _d: usize, s: &mut SerJsonState) {
+        let mut members = object_members(&self.known.serialize_json());
+
+        let mut unknown: Vec<_> = self.unknown.iter().collect();
+        unknown.sort_by_key(|(name, _)| *name);
+
+        for (name, json) in unknown {
+            if members.iter().any(|(known, _)| known == name) {
+                continue;
+            }
+
+            if let Some(value) = RawValue::parse(json) {
+                members.push((name.clone(), value));
+            }
+        }
+
+        s.out.push_str(&write_object(&members));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::buffers::BufferViewData;
 use crate::*;
 use std::borrow::Cow;
+use std::cell::{OnceCell, RefCell};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -19,24 +21,61 @@ impl MeshOptCompressionExtension for () {
     }
 }
 
-fn unsigned_short_to_float(short: u16) -> f32 {
-    short as f32 / 65535.0
+pub trait DracoCompressionExtension {
+    fn khr_draco_mesh_compression(&self) -> Option<&extensions::KhrDracoMeshCompression>;
 }
 
-fn unsigned_byte_to_float(byte: u8) -> f32 {
-    byte as f32 / 255.0
+impl DracoCompressionExtension for crate::default_extensions::PrimitiveExtensions {
+    fn khr_draco_mesh_compression(&self) -> Option<&extensions::KhrDracoMeshCompression> {
+        self.khr_draco_mesh_compression.as_ref()
+    }
+}
+
+impl DracoCompressionExtension for () {
+    fn khr_draco_mesh_compression(&self) -> Option<&extensions::KhrDracoMeshCompression> {
+        None
+    }
+}
+
+pub trait MeshGpuInstancingExtension {
+    fn ext_mesh_gpu_instancing(&self) -> Option<&extensions::ExtMeshGpuInstancing>;
+}
+
+impl MeshGpuInstancingExtension for crate::default_extensions::NodeExtensions {
+    fn ext_mesh_gpu_instancing(&self) -> Option<&extensions::ExtMeshGpuInstancing> {
+        self.ext_mesh_gpu_instancing.as_ref()
+    }
+}
+
+impl MeshGpuInstancingExtension for () {
+    fn ext_mesh_gpu_instancing(&self) -> Option<&extensions::ExtMeshGpuInstancing> {
+        None
+    }
 }
 
-fn signed_byte_to_float(byte: i8) -> f32 {
-    (byte as f32 / 127.0).max(-1.0)
+/// The output of a [`DracoDecoder`].
+#[derive(Debug, Clone, Default)]
+pub struct DracoMesh {
+    pub indices: Vec<u32>,
+    /// Attribute values keyed by Draco attribute id, as referenced by
+    /// [`extensions::KhrDracoMeshCompression::attributes`]. Values should already be
+    /// dequantized into the form the primitive's accessor describes, with all of an element's
+    /// components stored together.
+    pub attributes: HashMap<u32, Vec<f32>>,
 }
 
-fn signed_short_to_float(short: i16) -> f32 {
-    (short as f32 / 32767.0).max(-1.0)
+/// Decodes `KHR_draco_mesh_compression` buffer views.
+///
+/// This is only a hook: there's no Draco decoder bundled with this crate, and no feature that
+/// enables one. One has to be provided (e.g. by wrapping the Draco C++ library or a wasm build
+/// of it) with [`PrimitiveReader::with_draco_decoder`] to read compressed primitives. Without
+/// one, reading them fails with [`Error::DracoDecoderMissing`].
+pub trait DracoDecoder {
+    fn decode(&self, bytes: &[u8]) -> Result<DracoMesh, Box<dyn std::error::Error + Send + Sync>>;
 }
 
 fn byte_stride<E: Extensions>(
-    accessor: &crate::Accessor,
+    accessor: &crate::Accessor<E>,
     buffer_view: &crate::BufferView<E>,
 ) -> usize
 where
@@ -47,255 +86,1577 @@ where
         .ext_meshopt_compression()
         .map(|ext| ext.byte_stride)
         .or(buffer_view.byte_stride)
-        .unwrap_or_else(|| {
-            accessor.component_type.byte_size() * accessor.accessor_type.num_components()
-        })
+        .unwrap_or_else(|| element_size(accessor))
 }
 
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Accessor is missing buffer view")]
-    AccessorMissingBufferView,
     #[error("Buffer view index {0} out of bounds")]
     BufferViewIndexOutOfBounds(usize),
     #[error("Accessor index {0} out of bounds")]
     AccessorIndexOutOfBounds(usize),
+    #[error("Animation channel index {0} out of bounds")]
+    ChannelIndexOutOfBounds(usize),
+    #[error("Animation sampler index {0} out of bounds")]
+    SamplerIndexOutOfBounds(usize),
+    #[error("Accessor needs {0} bytes but the buffer view only has {1}")]
+    AccessorOutOfBounds(usize, usize),
+    #[error("Sparse index {0} is past the end of the accessor")]
+    SparseIndexOutOfBounds(usize),
+    #[error("Expected {0} components per element but the accessor has {1}")]
+    ComponentCountMismatch(usize, usize),
+    #[error("Primitive has no POSITION attribute")]
+    MissingPositions,
+    #[error("{0} has {2} elements but there are {1} vertices")]
+    VertexCountMismatch(&'static str, usize, usize),
+    #[error("Index {0} is out of bounds for {1} vertices")]
+    IndexOutOfBounds(u32, usize),
+    #[error("Accessor is sparse or has no buffer view, so it can't be viewed without decoding it")]
+    AccessorNotViewable,
+    #[error("Primitive is Draco-compressed but no decoder was provided")]
+    DracoDecoderMissing,
+    #[error("Failed to decode Draco data: {0}")]
+    DracoDecode(Box<dyn std::error::Error + Send + Sync>),
+    #[error("Accessor with {0} elements of {1} bytes is too large to read")]
+    AccessorTooLarge(usize, usize),
+    #[error("Unknown component type {0}")]
+    UnknownComponentType(u64),
     #[error("{0}: Unsupported combination of component type, normalized and byte stride: {1:?}")]
     UnsupportedCombination(u32, (ComponentType, bool, Option<usize>)),
 }
 
+/// How problems in the data that can be worked around are handled while reading.
+///
+/// Reading is strict by default, as working around these can hide truncated files. Used by
+/// [`PrimitiveReader::with_policy`] and [`read_buffer_with_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadPolicy {
+    truncate_accessors: bool,
+    max_accessor_bytes: usize,
+}
+
+impl Default for ReadPolicy {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl ReadPolicy {
+    pub fn strict() -> Self {
+        Self {
+            truncate_accessors: false,
+            max_accessor_bytes: 1 << 30,
+        }
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            truncate_accessors: true,
+            max_accessor_bytes: 1 << 30,
+        }
+    }
+
+    /// Read the elements that fit of accessors that extend past the end of their buffer view,
+    /// with a [`ReadWarning::AccessorTruncated`], instead of failing.
+    pub fn truncate_accessors(mut self, truncate: bool) -> Self {
+        self.truncate_accessors = truncate;
+        self
+    }
+
+    /// The most bytes that an accessor that has to be copied, because it's sparse or has no
+    /// buffer view, can be read into. Its size comes from the file, so this stops a tiny file
+    /// from allocating gigabytes. Defaults to 1 GiB. Accessors that are borrowed from the buffer
+    /// views aren't limited, as their data already exists.
+    pub fn max_accessor_bytes(mut self, bytes: usize) -> Self {
+        self.max_accessor_bytes = bytes;
+        self
+    }
+
+    pub fn truncates_accessors(&self) -> bool {
+        self.truncate_accessors
+    }
+
+    pub fn get_max_accessor_bytes(&self) -> usize {
+        self.max_accessor_bytes
+    }
+}
+
+/// A problem that was worked around while reading, as allowed by a [`ReadPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadWarning {
+    /// An accessor needs `required` bytes of its buffer view, but there are only `available`,
+    /// so only the elements that fit were read.
+    AccessorTruncated { required: usize, available: usize },
+}
+
+// Unknown component types can only come from lenient parsing. They have no size, so they have to
+// be rejected before any byte offsets are worked out.
+fn check_component_type(component_type: ComponentType) -> Result<(), Error> {
+    match component_type {
+        ComponentType::Unknown(ty) => Err(Error::UnknownComponentType(ty)),
+        _ => Ok(()),
+    }
+}
+
+/// Read the bytes of an accessor, along with the byte stride they should be read with.
+///
+/// Data is borrowed from `buffer_view_map` where possible. Accessors without a buffer view are
+/// all zeros, and sparse accessors are returned as a tightly packed copy with the sparse values
+/// substituted in.
 pub fn read_buffer_with_accessor<'a, E: Extensions>(
-    buffer_view_map: &'a HashMap<usize, Vec<u8>>,
+    buffer_view_map: &'a dyn BufferViewData,
+    gltf: &'a crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+) -> Result<(Cow<'a, [u8]>, Option<usize>), Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let read = read_buffer_with_policy(buffer_view_map, gltf, accessor, &ReadPolicy::strict())?;
+    Ok((read.bytes, read.byte_stride))
+}
+
+/// The bytes of an accessor, as read by [`read_buffer_with_policy`].
+#[derive(Debug, Clone)]
+pub struct AccessorBytes<'a> {
+    pub bytes: Cow<'a, [u8]>,
+    pub byte_stride: Option<usize>,
+    /// The problems that were worked around.
+    pub warnings: Vec<ReadWarning>,
+}
+
+/// Like [`read_buffer_with_accessor`], but with the problems that `policy` allows worked around
+/// and returned as warnings.
+pub fn read_buffer_with_policy<'a, E: Extensions>(
+    buffer_view_map: &'a dyn BufferViewData,
     gltf: &'a crate::Gltf<E>,
-    accessor: &crate::Accessor,
+    accessor: &crate::Accessor<E>,
+    policy: &ReadPolicy,
+) -> Result<AccessorBytes<'a>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let mut warnings = Vec::new();
+
+    check_component_type(accessor.component_type)?;
+
+    if let Some(sparse) = &accessor.sparse {
+        check_component_type(sparse.indices.component_type)?;
+    }
+
+    if let (Some(buffer_view_index), None) = (accessor.buffer_view, &accessor.sparse) {
+        let (slice, byte_stride) = read_buffer_view_slice(
+            buffer_view_map,
+            gltf,
+            accessor,
+            buffer_view_index,
+            policy,
+            &mut warnings,
+        )?;
+        return Ok(AccessorBytes {
+            bytes: Cow::Borrowed(slice),
+            byte_stride,
+            warnings,
+        });
+    }
+
+    let element_size = element_size(accessor);
+
+    let length = accessor
+        .count
+        .checked_mul(element_size)
+        .filter(|&length| length <= policy.max_accessor_bytes)
+        .ok_or(Error::AccessorTooLarge(accessor.count, element_size))?;
+
+    let mut bytes = vec![0; length];
+
+    if let Some(buffer_view_index) = accessor.buffer_view {
+        let (slice, byte_stride) = read_buffer_view_slice(
+            buffer_view_map,
+            gltf,
+            accessor,
+            buffer_view_index,
+            policy,
+            &mut warnings,
+        )?;
+        let stride = byte_stride.unwrap_or(element_size);
+
+        for (element, output) in bytes.chunks_exact_mut(element_size).enumerate() {
+            let start = element * stride;
+
+            match slice.get(start..start + element_size) {
+                Some(input) => output.copy_from_slice(input),
+                None => break,
+            }
+        }
+    }
+
+    let sparse = match &accessor.sparse {
+        Some(sparse) => sparse,
+        None => {
+            return Ok(AccessorBytes {
+                bytes: Cow::Owned(bytes),
+                byte_stride: None,
+                warnings,
+            })
+        }
+    };
+
+    let index_size = sparse.indices.component_type.byte_size();
+    let indices = sparse_bytes(
+        buffer_view_map,
+        sparse.indices.buffer_view,
+        sparse.indices.byte_offset,
+        sparse
+            .count
+            .checked_mul(index_size)
+            .ok_or(Error::AccessorTooLarge(sparse.count, index_size))?,
+    )?;
+    let values = sparse_bytes(
+        buffer_view_map,
+        sparse.values.buffer_view,
+        sparse.values.byte_offset,
+        sparse
+            .count
+            .checked_mul(element_size)
+            .ok_or(Error::AccessorTooLarge(sparse.count, element_size))?,
+    )?;
+
+    for (index, value) in indices
+        .chunks_exact(index_size)
+        .zip(values.chunks_exact(element_size))
+    {
+        let index = read_index(index, sparse.indices.component_type);
+        let start = index.saturating_mul(element_size);
+
+        bytes
+            .get_mut(start..start.saturating_add(element_size))
+            .ok_or(Error::SparseIndexOutOfBounds(index))?
+            .copy_from_slice(value);
+    }
+
+    Ok(AccessorBytes {
+        bytes: Cow::Owned(bytes),
+        byte_stride: None,
+        warnings,
+    })
+}
+
+fn read_buffer_view_slice<'a, E: Extensions>(
+    buffer_view_map: &'a dyn BufferViewData,
+    gltf: &'a crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+    buffer_view_index: usize,
+    policy: &ReadPolicy,
+    warnings: &mut Vec<ReadWarning>,
 ) -> Result<(&'a [u8], Option<usize>), Error>
 where
     E::BufferViewExtensions: MeshOptCompressionExtension,
 {
-    let buffer_view_index = accessor
-        .buffer_view
-        .ok_or(Error::AccessorMissingBufferView)?;
     let buffer_view = gltf
         .buffer_views
         .get(buffer_view_index)
         .ok_or(Error::BufferViewIndexOutOfBounds(buffer_view_index))?;
 
+    let element_size = element_size(accessor);
+    let stride = byte_stride(accessor, buffer_view);
+
+    // In an interleaved buffer view, `byte_offset` is the offset of the accessor's first element
+    // within the first stride, so the last element ends `element_size` bytes after its start
+    // rather than at a stride boundary.
     let start = accessor.byte_offset;
-    let end = start + accessor.count * byte_stride(accessor, buffer_view);
+    let end = elements_end(start, accessor.count, stride, element_size)
+        .ok_or(Error::AccessorTooLarge(accessor.count, element_size))?;
 
     let buffer_view_bytes = buffer_view_map
-        .get(&buffer_view_index)
+        .buffer_view_bytes(buffer_view_index)
         .ok_or(Error::BufferViewIndexOutOfBounds(buffer_view_index))?;
 
-    // Force the end of the slice to be in-bounds as either the maths for calculating
-    // `end` is wrong or some files are a little odd.
+    if start > buffer_view_bytes.len() {
+        return Err(Error::AccessorOutOfBounds(end, buffer_view_bytes.len()));
+    }
+
+    if end > buffer_view_bytes.len() {
+        if !policy.truncate_accessors {
+            return Err(Error::AccessorOutOfBounds(end, buffer_view_bytes.len()));
+        }
+
+        warnings.push(ReadWarning::AccessorTruncated {
+            required: end,
+            available: buffer_view_bytes.len(),
+        });
+    }
+
     let end = end.min(buffer_view_bytes.len());
 
     let slice = &buffer_view_bytes[start..end];
 
-    Ok((slice, buffer_view.byte_stride))
+    // The readers' fast paths expect tightly packed data to have no stride.
+    let byte_stride = if stride == element_size {
+        None
+    } else {
+        Some(stride)
+    };
+
+    Ok((slice, byte_stride))
+}
+
+// Sparse indices and values are always tightly packed, so unlike the base data they have to be
+// fully in-bounds.
+fn sparse_bytes(
+    buffer_view_map: &dyn BufferViewData,
+    buffer_view_index: usize,
+    byte_offset: usize,
+    length: usize,
+) -> Result<&[u8], Error> {
+    let buffer_view_bytes = buffer_view_map
+        .buffer_view_bytes(buffer_view_index)
+        .ok_or(Error::BufferViewIndexOutOfBounds(buffer_view_index))?;
+
+    let end = byte_offset.saturating_add(length);
+
+    buffer_view_bytes
+        .get(byte_offset..end)
+        .ok_or(Error::AccessorOutOfBounds(end, buffer_view_bytes.len()))
+}
+
+// Where `count` elements that are `stride` apart end, or `None` if that overflows. The counts and
+// offsets come from the file, so they can be anything.
+fn elements_end(start: usize, count: usize, stride: usize, element_size: usize) -> Option<usize> {
+    match count {
+        0 => Some(start),
+        count => (count - 1)
+            .checked_mul(stride)?
+            .checked_add(element_size)?
+            .checked_add(start),
+    }
+}
+
+// Going through `read_component` would lose precision on large `u32` indices.
+fn read_index(bytes: &[u8], component_type: ComponentType) -> usize {
+    match component_type {
+        ComponentType::UnsignedByte | ComponentType::Byte => bytes[0] as usize,
+        ComponentType::UnsignedShort | ComponentType::Short => {
+            u16::from_le_bytes([bytes[0], bytes[1]]) as usize
+        }
+        ComponentType::UnsignedInt | ComponentType::Float => {
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+        }
+        ComponentType::Unknown(_) => 0,
+    }
+}
+
+// The typed readers borrow from their input where they can, which patched sparse data can't be
+// borrowed for, so their output is made owned in that case.
+fn read_bytes_with<'a, T: ToOwned + ?Sized + 'a>(
+    bytes: Cow<'a, [u8]>,
+    read: impl for<'b> FnOnce(&'b [u8]) -> Result<Cow<'b, T>, Error>,
+) -> Result<Cow<'a, T>, Error> {
+    match bytes {
+        Cow::Borrowed(slice) => read(slice),
+        Cow::Owned(vec) => read(&vec).map(|value| Cow::Owned(value.into_owned())),
+    }
+}
+
+fn read_component(bytes: &[u8], component_type: ComponentType) -> f32 {
+    match component_type {
+        ComponentType::UnsignedByte => bytes[0] as f32,
+        ComponentType::Byte => bytes[0] as i8 as f32,
+        ComponentType::UnsignedShort => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+        ComponentType::Short => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+        ComponentType::UnsignedInt => {
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32
+        }
+        ComponentType::Float => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        ComponentType::Unknown(_) => 0.0,
+    }
+}
+
+// Matrix columns are padded to 4-byte boundaries, so the offset of a component isn't
+// always `index * component_size`.
+pub(crate) fn component_byte_offset<E: Extensions>(
+    accessor: &crate::Accessor<E>,
+    index: usize,
+) -> usize {
+    let component_size = accessor.component_type.byte_size();
+
+    let rows = match accessor.accessor_type {
+        AccessorType::Mat2 => 2,
+        AccessorType::Mat3 => 3,
+        AccessorType::Mat4 => 4,
+        _ => return index * component_size,
+    };
+
+    let column_stride = (rows * component_size + 3) & !3;
+
+    (index / rows) * column_stride + (index % rows) * component_size
+}
+
+pub(crate) fn element_size<E: Extensions>(accessor: &crate::Accessor<E>) -> usize {
+    component_byte_offset(accessor, accessor.accessor_type.num_components() - 1)
+        + accessor.component_type.byte_size()
+}
+
+/// Iterate over the raw bytes of each element of an accessor.
+///
+/// `buffer_view_bytes` should contain the whole buffer view, as `accessor.byte_offset` is applied
+/// here. Elements are `byte_stride` apart, or tightly packed if there is no stride.
+pub fn iter_element_bytes<'a, E: Extensions>(
+    accessor: &crate::Accessor<E>,
+    byte_stride: Option<usize>,
+    buffer_view_bytes: &'a [u8],
+) -> Result<impl ExactSizeIterator<Item = &'a [u8]> + 'a, Error> {
+    check_component_type(accessor.component_type)?;
+
+    let element_size = element_size(accessor);
+    let stride = byte_stride.unwrap_or(element_size);
+    let start = accessor.byte_offset;
+
+    let required = elements_end(start, accessor.count, stride, element_size)
+        .ok_or(Error::AccessorTooLarge(accessor.count, element_size))?;
+
+    if required > buffer_view_bytes.len() {
+        return Err(Error::AccessorOutOfBounds(
+            required,
+            buffer_view_bytes.len(),
+        ));
+    }
+
+    Ok((0..accessor.count).map(move |i| {
+        let offset = start + i * stride;
+        &buffer_view_bytes[offset..offset + element_size]
+    }))
+}
+
+/// The component-wise bounds of an accessor's data, from [`compute_accessor_bounds`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessorBounds {
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+/// Compute the component-wise minimum and maximum of the data an accessor points to.
+///
+/// As per the spec, the values are the ones stored in the buffer, so `normalized` is ignored.
+/// They're computed as `f64`s so that large `u32` values stay exact. Returns `None` if the
+/// accessor has no elements, and an error if the slice is too short for all of them.
+pub fn compute_accessor_bounds<E: Extensions>(
+    slice: &[u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Result<Option<AccessorBounds>, Error> {
+    check_component_type(accessor.component_type)?;
+
+    let num_components = accessor.accessor_type.num_components();
+    let element_size = element_size(accessor);
+    let stride = byte_stride.unwrap_or(element_size);
+
+    let required = elements_end(0, accessor.count, stride, element_size)
+        .ok_or(Error::AccessorTooLarge(accessor.count, element_size))?;
+
+    if required > slice.len() {
+        return Err(Error::AccessorOutOfBounds(required, slice.len()));
+    }
+
+    if accessor.count == 0 {
+        return Ok(None);
+    }
+
+    let mut min = vec![f64::INFINITY; num_components];
+    let mut max = vec![f64::NEG_INFINITY; num_components];
+
+    for element in 0..accessor.count {
+        let bytes = &slice[element * stride..element * stride + element_size];
+
+        for i in 0..num_components {
+            let value = read_bound_component(
+                &bytes[component_byte_offset(accessor, i)..],
+                accessor.component_type,
+            );
+            min[i] = min[i].min(value);
+            max[i] = max[i].max(value);
+        }
+    }
+
+    Ok(Some(AccessorBounds { min, max }))
+}
+
+// `read_component` would round `u32` values above 2^24.
+fn read_bound_component(bytes: &[u8], component_type: ComponentType) -> f64 {
+    match component_type {
+        ComponentType::UnsignedInt => {
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64
+        }
+        other => read_component(bytes, other) as f64,
+    }
+}
+
+/// Recompute `min` and `max` for every accessor, filling them in where they're missing and
+/// correcting them where they disagree with the buffer data.
+///
+/// Returns the indices of the accessors that were changed.
+pub fn update_accessor_bounds<E: Extensions>(
+    gltf: &mut crate::Gltf<E>,
+    buffer_view_map: &dyn BufferViewData,
+) -> Result<Vec<usize>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let mut bounds = Vec::new();
+
+    for (index, accessor) in gltf.accessors.iter().enumerate() {
+        let (slice, byte_stride) = read_buffer_with_accessor(buffer_view_map, gltf, accessor)?;
+
+        if let Some(AccessorBounds { min, max }) =
+            compute_accessor_bounds(&slice, byte_stride, accessor)?
+        {
+            bounds.push((index, min, max));
+        }
+    }
+
+    let mut changed = Vec::new();
+
+    for (index, min, max) in bounds {
+        let accessor = &mut gltf.accessors[index];
+
+        if accessor.min.as_ref() != Some(&min) || accessor.max.as_ref() != Some(&max) {
+            accessor.min = Some(min);
+            accessor.max = Some(max);
+            changed.push(index);
+        }
+    }
+
+    Ok(changed)
+}
+
+fn normalize_component(value: f32, component_type: ComponentType) -> f32 {
+    match component_type {
+        ComponentType::UnsignedByte => value / 255.0,
+        ComponentType::Byte => (value / 127.0).max(-1.0),
+        ComponentType::UnsignedShort => value / 65535.0,
+        ComponentType::Short => (value / 32767.0).max(-1.0),
+        ComponentType::UnsignedInt | ComponentType::Float | ComponentType::Unknown(_) => value,
+    }
+}
+
+/// Read every component of an accessor as a float, for any combination of component type,
+/// normalization and byte stride.
+///
+/// This is slower than the typed readers but doesn't fail on unusual layouts. Incomplete trailing
+/// elements are ignored.
+pub fn read_floats<E: Extensions>(
+    slice: &[u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Vec<f32> {
+    read_components(slice, byte_stride, accessor)
+}
+
+fn read_components<C: Component, E: Extensions>(
+    slice: &[u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Vec<C> {
+    let num_components = accessor.accessor_type.num_components();
+    let element_size = element_size(accessor);
+    let stride = byte_stride.unwrap_or(element_size);
+
+    // `count` comes from the file, so it's only trusted as far as the slice goes.
+    let count = accessor
+        .count
+        .min(complete_elements(slice, stride, element_size));
+    let mut components = Vec::with_capacity(count * num_components);
+
+    for element in 0..count {
+        let start = element * stride;
+
+        let bytes = match slice.get(start..start + element_size) {
+            Some(bytes) => bytes,
+            None => break,
+        };
+
+        for i in 0..num_components {
+            components.push(C::read(
+                &bytes[component_byte_offset(accessor, i)..],
+                accessor.component_type,
+                accessor.normalized,
+            ));
+        }
+    }
+
+    components
+}
+
+fn read_elements<C: Component, const N: usize, E: Extensions>(
+    slice: &[u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Vec<[C; N]> {
+    read_components(slice, byte_stride, accessor)
+        .chunks_exact(N)
+        .map(<[C; N]>::from_components)
+        .collect()
+}
+
+/// A single component type that accessor data can be converted to.
+pub trait Component: Copy + Default {
+    fn read(bytes: &[u8], component_type: ComponentType, normalized: bool) -> Self;
+}
+
+impl Component for f32 {
+    fn read(bytes: &[u8], component_type: ComponentType, normalized: bool) -> Self {
+        let value = read_component(bytes, component_type);
+
+        if normalized {
+            normalize_component(value, component_type)
+        } else {
+            value
+        }
+    }
+}
+
+// Integers are read as they're stored, ignoring `normalized`.
+impl Component for u32 {
+    fn read(bytes: &[u8], component_type: ComponentType, _normalized: bool) -> Self {
+        match component_type {
+            ComponentType::Byte => bytes[0] as i8 as u32,
+            ComponentType::Short => i16::from_le_bytes([bytes[0], bytes[1]]) as u32,
+            ComponentType::Float => read_component(bytes, component_type) as u32,
+            _ => read_index(bytes, component_type) as u32,
+        }
+    }
+}
+
+impl Component for u16 {
+    fn read(bytes: &[u8], component_type: ComponentType, normalized: bool) -> Self {
+        u32::read(bytes, component_type, normalized) as u16
+    }
+}
+
+impl Component for u8 {
+    fn read(bytes: &[u8], component_type: ComponentType, normalized: bool) -> Self {
+        u32::read(bytes, component_type, normalized) as u8
+    }
+}
+
+/// An element type for [`read_accessor`], either a single [`Component`] or an array of them.
+///
+/// Matrices are read as flat arrays in column-major order, e.g. `[f32; 16]` for a `MAT4`.
+pub trait AccessorElement: Sized {
+    type Component: Component;
+    const COMPONENTS: usize;
+
+    fn from_components(components: &[Self::Component]) -> Self;
+}
+
+impl<C: Component> AccessorElement for C {
+    type Component = C;
+    const COMPONENTS: usize = 1;
+
+    fn from_components(components: &[C]) -> Self {
+        components[0]
+    }
+}
+
+impl<C: Component, const N: usize> AccessorElement for [C; N] {
+    type Component = C;
+    const COMPONENTS: usize = N;
+
+    fn from_components(components: &[C]) -> Self {
+        std::array::from_fn(|i| components[i])
+    }
+}
+
+/// The accessor's `min` and `max` in the same space as the values that [`read_floats`] returns,
+/// i.e. with normalization applied, as the spec stores the raw values.
+pub fn dequantized_bounds<E: Extensions>(
+    accessor: &crate::Accessor<E>,
+) -> Option<(Vec<f32>, Vec<f32>)> {
+    let dequantize = |values: &Vec<f64>| {
+        values
+            .iter()
+            .map(|&value| value as f32)
+            .map(|value| match accessor.normalized {
+                true => normalize_component(value, accessor.component_type),
+                false => value,
+            })
+            .collect()
+    };
+
+    Some((
+        dequantize(accessor.min.as_ref()?),
+        dequantize(accessor.max.as_ref()?),
+    ))
+}
+
+/// Dequantize a `KHR_mesh_quantization` attribute, such as a byte or short position, normal or
+/// texture coordinate, into floats.
+///
+/// Normalized values are mapped into `[0, 1]` or `[-1, 1]` and everything else is converted as
+/// is. Values are then clamped to the accessor's bounds if it has them.
+pub fn dequantize<const N: usize, E: Extensions>(
+    buffer_view_map: &dyn BufferViewData,
+    gltf: &crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+) -> Result<Vec<[f32; N]>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let mut values: Vec<[f32; N]> = read_accessor(buffer_view_map, gltf, accessor)?;
+
+    if let Some((min, max)) = dequantized_bounds(accessor) {
+        if min.len() == N && max.len() == N {
+            for value in &mut values {
+                for i in 0..N {
+                    value[i] = value[i].clamp(min[i], max[i].max(min[i]));
+                }
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// The scale that turns the values stored in an accessor into the values it represents: the
+/// normalization factor for normalized integers and 1 otherwise.
+pub fn dequantization_scale<E: Extensions>(accessor: &crate::Accessor<E>) -> f32 {
+    match accessor.normalized {
+        true => normalize_component(1.0, accessor.component_type),
+        false => 1.0,
+    }
 }
 
-pub fn read_f32<'a>(
+/// A column-major transform for renderers that keep a quantized position attribute as it's
+/// stored and read it in the shader as unnormalized floats. Multiply it onto the end of the
+/// node's world transform to get the same result as with dequantized positions.
+///
+/// The signed normalized formats clamp their lowest value to -1, which this ignores.
+pub fn dequantization_matrix<E: Extensions>(accessor: &crate::Accessor<E>) -> [f32; 16] {
+    let scale = dequantization_scale(accessor);
+    crate::math::from_trs([0.0; 3], [0.0, 0.0, 0.0, 1.0], [scale; 3])
+}
+
+/// Read the elements of an accessor as `T`, converting from any component type and applying
+/// normalization and byte stride.
+///
+/// Float components are converted to integers with `as`, and integers are converted to narrower
+/// integer types with `as` as well. Incomplete trailing elements are ignored.
+pub fn read_accessor<T: AccessorElement, E: Extensions>(
+    buffer_view_map: &dyn BufferViewData,
+    gltf: &crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+) -> Result<Vec<T>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let num_components = accessor.accessor_type.num_components();
+
+    if num_components != T::COMPONENTS {
+        return Err(Error::ComponentCountMismatch(T::COMPONENTS, num_components));
+    }
+
+    let (slice, byte_stride) = read_buffer_with_accessor(buffer_view_map, gltf, accessor)?;
+
+    Ok(
+        read_components::<T::Component, E>(&slice, byte_stride, accessor)
+            .chunks_exact(num_components)
+            .map(T::from_components)
+            .collect(),
+    )
+}
+
+/// Iterate over the elements of an accessor as `T`, decoding each one as it's reached.
+///
+/// This converts the same way as [`read_accessor`] but without allocating the output, which is
+/// useful for data that's only streamed through once.
+pub fn iter_accessor<'a, T: AccessorElement, E: Extensions>(
+    buffer_view_map: &'a dyn BufferViewData,
+    gltf: &'a crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+) -> Result<AccessorIter<'a, T>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    check_component_count::<T, E>(accessor)?;
+
+    let (bytes, byte_stride) = read_buffer_with_accessor(buffer_view_map, gltf, accessor)?;
+
+    Ok(accessor_iter(bytes, byte_stride, accessor))
+}
+
+fn check_component_count<T: AccessorElement, E: Extensions>(
+    accessor: &crate::Accessor<E>,
+) -> Result<(), Error> {
+    let num_components = accessor.accessor_type.num_components();
+
+    if num_components != T::COMPONENTS {
+        return Err(Error::ComponentCountMismatch(T::COMPONENTS, num_components));
+    }
+
+    Ok(())
+}
+
+fn accessor_iter<'a, T: AccessorElement, E: Extensions>(
+    bytes: Cow<'a, [u8]>,
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> AccessorIter<'a, T> {
+    let element_size = element_size(accessor);
+
+    AccessorIter::new(
+        bytes,
+        byte_stride.unwrap_or(element_size),
+        element_size,
+        std::array::from_fn(|i| component_byte_offset(accessor, i)),
+        accessor.component_type,
+        accessor.normalized,
+        accessor.count,
+    )
+}
+
+/// An iterator over the elements of an accessor, as returned by [`iter_accessor`].
+#[derive(Debug, Clone)]
+pub struct AccessorIter<'a, T> {
+    bytes: Cow<'a, [u8]>,
+    stride: usize,
+    element_size: usize,
+    // Enough for a `MAT4`.
+    component_offsets: [usize; 16],
+    component_type: ComponentType,
+    normalized: bool,
+    index: usize,
+    count: usize,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<'a, T: AccessorElement> AccessorIter<'a, T> {
+    fn new(
+        bytes: Cow<'a, [u8]>,
+        stride: usize,
+        element_size: usize,
+        component_offsets: [usize; 16],
+        component_type: ComponentType,
+        normalized: bool,
+        count: usize,
+    ) -> Self {
+        // Incomplete trailing elements are ignored, as with the other readers.
+        let complete = match bytes.len().checked_sub(element_size) {
+            Some(remaining) => remaining / stride.max(1) + 1,
+            None => 0,
+        };
+
+        Self {
+            bytes,
+            stride,
+            element_size,
+            component_offsets,
+            component_type,
+            normalized,
+            index: 0,
+            count: count.min(complete),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    // Used for Draco-decoded data, which is already in memory as tightly packed 4-byte values.
+    fn from_packed(bytes: Vec<u8>, component_type: ComponentType) -> Self {
+        let element_size = T::COMPONENTS * 4;
+        let count = bytes.len() / element_size;
+
+        Self::new(
+            Cow::Owned(bytes),
+            element_size,
+            element_size,
+            std::array::from_fn(|i| i * 4),
+            component_type,
+            false,
+            count,
+        )
+    }
+}
+
+impl<'a, T: AccessorElement> Iterator for AccessorIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index >= self.count {
+            return None;
+        }
+
+        let start = self.index * self.stride;
+        let bytes = &self.bytes[start..start + self.element_size];
+        self.index += 1;
+
+        Some(read_element(
+            bytes,
+            &self.component_offsets,
+            self.component_type,
+            self.normalized,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: AccessorElement> ExactSizeIterator for AccessorIter<'a, T> {}
+
+fn read_element<T: AccessorElement>(
+    bytes: &[u8],
+    component_offsets: &[usize; 16],
+    component_type: ComponentType,
+    normalized: bool,
+) -> T {
+    let mut components = [T::Component::default(); 16];
+
+    for (component, &offset) in components
+        .iter_mut()
+        .zip(component_offsets)
+        .take(T::COMPONENTS)
+    {
+        *component = T::Component::read(&bytes[offset..], component_type, normalized);
+    }
+
+    T::from_components(&components[..T::COMPONENTS])
+}
+
+/// A view of the elements of an accessor as `T`, which are decoded only when they're accessed.
+///
+/// Unlike [`AccessorIter`], this always borrows the buffer view data and allows random access.
+#[derive(Debug)]
+pub struct AccessorView<'a, T> {
+    bytes: &'a [u8],
+    stride: usize,
+    element_size: usize,
+    component_offsets: [usize; 16],
+    component_type: ComponentType,
+    normalized: bool,
+    len: usize,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<'a, T> Clone for AccessorView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for AccessorView<'a, T> {}
+
+impl<'a, T: AccessorElement> AccessorView<'a, T> {
+    /// View the data of `accessor` in `slice`, as returned by [`read_buffer_with_accessor`].
+    ///
+    /// Incomplete trailing elements are ignored.
+    pub fn new<E: Extensions>(
+        slice: &'a [u8],
+        byte_stride: Option<usize>,
+        accessor: &crate::Accessor<E>,
+    ) -> Result<Self, Error> {
+        let num_components = accessor.accessor_type.num_components();
+
+        if num_components != T::COMPONENTS {
+            return Err(Error::ComponentCountMismatch(T::COMPONENTS, num_components));
+        }
+
+        check_component_type(accessor.component_type)?;
+
+        let element_size = element_size(accessor);
+        let stride = byte_stride.unwrap_or(element_size);
+
+        let complete = match slice.len().checked_sub(element_size) {
+            Some(remaining) => remaining / stride.max(1) + 1,
+            None => 0,
+        };
+
+        Ok(Self {
+            bytes: slice,
+            stride,
+            element_size,
+            component_offsets: std::array::from_fn(|i| component_byte_offset(accessor, i)),
+            component_type: accessor.component_type,
+            normalized: accessor.normalized,
+            len: accessor.count.min(complete),
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of bytes between the start of each element.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let start = index * self.stride;
+
+        Some(read_element(
+            &self.bytes[start..start + self.element_size],
+            &self.component_offsets,
+            self.component_type,
+            self.normalized,
+        ))
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + DoubleEndedIterator + 'a
+    where
+        T: 'a,
+    {
+        let view = *self;
+        (0..self.len).map(move |index| view.get(index).unwrap())
+    }
+}
+
+/// View the elements of an accessor as `T` without copying or decoding anything upfront.
+///
+/// Sparse accessors and accessors without a buffer view have to be decoded, so they return
+/// [`Error::AccessorNotViewable`]. Use [`iter_accessor`] or [`read_accessor`] for those.
+pub fn view_accessor<'a, T: AccessorElement, E: Extensions>(
+    buffer_view_map: &'a dyn BufferViewData,
+    gltf: &'a crate::Gltf<E>,
+    accessor: &crate::Accessor<E>,
+) -> Result<AccessorView<'a, T>, Error>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    let buffer_view_index = match (accessor.buffer_view, &accessor.sparse) {
+        (Some(buffer_view_index), None) => buffer_view_index,
+        _ => return Err(Error::AccessorNotViewable),
+    };
+
+    let (slice, byte_stride) = read_buffer_view_slice(
+        buffer_view_map,
+        gltf,
+        accessor,
+        buffer_view_index,
+        &ReadPolicy::strict(),
+        &mut Vec::new(),
+    )?;
+
+    AccessorView::new(slice, byte_stride, accessor)
+}
+
+// Buffers loaded from GLB files or passed in by the user aren't necessarily aligned, so data is
+// only borrowed when the cast succeeds and is read component by component otherwise.
+fn cast_or_read_components<'a, C: Component + bytemuck::Pod, E: Extensions>(
+    slice: &'a [u8],
+    accessor: &crate::Accessor<E>,
+) -> Cow<'a, [C]> {
+    match bytemuck::try_cast_slice(slice) {
+        Ok(slice) => Cow::Borrowed(slice),
+        Err(_) => Cow::Owned(read_components(slice, None, accessor)),
+    }
+}
+
+fn cast_or_read_elements<'a, C: Component, const N: usize, E: Extensions>(
+    slice: &'a [u8],
+    accessor: &crate::Accessor<E>,
+) -> Cow<'a, [[C; N]]>
+where
+    [C; N]: bytemuck::Pod,
+{
+    match bytemuck::try_cast_slice(slice) {
+        Ok(slice) => Cow::Borrowed(slice),
+        Err(_) => Cow::Owned(read_elements(slice, None, accessor)),
+    }
+}
+
+// Reads integer elements with the component type and normalization known up front, rather than
+// matching on them for every component as `read_elements` does. This matters for
+// `KHR_mesh_quantization` files, which are mostly made up of these.
+fn read_integer_elements<const N: usize, E: Extensions>(
+    slice: &[u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Option<Vec<[f32; N]>> {
+    let is_vector = matches!(
+        accessor.accessor_type,
+        AccessorType::Scalar | AccessorType::Vec2 | AccessorType::Vec3 | AccessorType::Vec4
+    );
+
+    if !is_vector || accessor.accessor_type.num_components() != N {
+        return None;
+    }
+
+    let stride = byte_stride.unwrap_or(element_size(accessor));
+    let count = accessor.count;
+
+    // The same as `normalize_component`, with the match hoisted out. Dividing by 1 and taking the
+    // maximum with negative infinity leave the value unchanged.
+    let (divisor, min) = match (accessor.normalized, accessor.component_type) {
+        (false, _) => (1.0, f32::NEG_INFINITY),
+        (true, ComponentType::UnsignedByte) => (255.0, f32::NEG_INFINITY),
+        (true, ComponentType::Byte) => (127.0, -1.0),
+        (true, ComponentType::UnsignedShort) => (65535.0, f32::NEG_INFINITY),
+        (true, ComponentType::Short) => (32767.0, -1.0),
+        _ => return None,
+    };
+    let normalize = |value: f32| (value / divisor).max(min);
+
+    Some(match accessor.component_type {
+        ComponentType::UnsignedByte => {
+            convert_elements::<N, 1>(slice, stride, count, |[byte]| normalize(byte as f32))
+        }
+        ComponentType::Byte => {
+            convert_elements::<N, 1>(slice, stride, count, |[byte]| normalize(byte as i8 as f32))
+        }
+        ComponentType::UnsignedShort => convert_elements::<N, 2>(slice, stride, count, |bytes| {
+            normalize(u16::from_le_bytes(bytes) as f32)
+        }),
+        ComponentType::Short => convert_elements::<N, 2>(slice, stride, count, |bytes| {
+            normalize(i16::from_le_bytes(bytes) as f32)
+        }),
+        _ => return None,
+    })
+}
+
+// The number of whole elements in `slice`.
+fn complete_elements(slice: &[u8], stride: usize, element_size: usize) -> usize {
+    match slice.len().checked_sub(element_size) {
+        Some(remaining) => remaining / stride.max(1) + 1,
+        None => 0,
+    }
+}
+
+fn convert_elements<const N: usize, const SIZE: usize>(
+    slice: &[u8],
+    stride: usize,
+    count: usize,
+    convert: impl Fn([u8; SIZE]) -> f32,
+) -> Vec<[f32; N]> {
+    let element_size = N * SIZE;
+    let count = count.min(complete_elements(slice, stride, element_size));
+
+    if stride == element_size {
+        // Tightly packed data is converted as one flat run of components, with a fixed size and
+        // no per-element offsets or bounds checks, so that it compiles down to SIMD loads,
+        // conversions and divisions.
+        let mut elements = vec![[0.0; N]; count];
+
+        for (output, bytes) in elements
+            .as_flattened_mut()
+            .iter_mut()
+            .zip(slice.chunks_exact(SIZE))
+        {
+            *output = convert(bytes.try_into().unwrap());
+        }
+
+        return elements;
+    }
+
+    (0..count)
+        .map(|element| {
+            let bytes = &slice[element * stride..element * stride + element_size];
+            std::array::from_fn(|i| convert(bytes[i * SIZE..(i + 1) * SIZE].try_into().unwrap()))
+        })
+        .collect()
+}
+
+pub fn read_f32<'a, E: Extensions>(
     slice: &'a [u8],
     byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
+    accessor: &crate::Accessor<E>,
 ) -> Result<Cow<'a, [f32]>, Error> {
     Ok(
         match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::Float, false, None) => Cow::Borrowed(bytemuck::cast_slice(slice)),
+            (ComponentType::Float, false, None) => cast_or_read_components(slice, accessor),
+            // Floats and normalized integers, as allowed for animation samplers.
+            (ComponentType::Float, false, _)
+            | (
+                ComponentType::Byte
+                | ComponentType::UnsignedByte
+                | ComponentType::Short
+                | ComponentType::UnsignedShort,
+                true,
+                _,
+            ) => Cow::Owned(read_components(slice, byte_stride, accessor)),
             other => return Err(Error::UnsupportedCombination(std::line!(), other)),
         },
     )
 }
 
-pub fn read_f32x3<'a>(
+pub fn read_f32x3<'a, E: Extensions>(
     slice: &'a [u8],
     byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
+    accessor: &crate::Accessor<E>,
 ) -> Result<Cow<'a, [[f32; 3]]>, Error> {
     Ok(
         match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::Float, false, None | Some(12)) => {
-                let slice: &[f32] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(3)
-                        .map(|slice| <[f32; 3]>::try_from(slice).unwrap())
-                        .collect(),
-                )
-            }
-            (ComponentType::Short, true, Some(stride)) => {
-                let slice: &[i16] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(stride / 2)
-                        .map(|slice| std::array::from_fn(|i| signed_short_to_float(slice[i])))
-                        .collect(),
-                )
-            }
-            (ComponentType::UnsignedShort, false, Some(8)) => {
-                let slice: &[u16] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(4)
-                        .map(move |slice| std::array::from_fn(|i| slice[i] as f32))
-                        .collect(),
-                )
-            }
-            (ComponentType::UnsignedShort, true, Some(8)) => {
-                let slice: &[u16] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(4)
-                        .map(|slice| std::array::from_fn(|i| unsigned_short_to_float(slice[i])))
-                        .collect(),
-                )
-            }
-            (ComponentType::Byte, true, Some(stride)) => Cow::Owned(
-                slice
-                    .chunks(stride)
-                    .map(move |slice| std::array::from_fn(|i| signed_byte_to_float(slice[i] as i8)))
-                    .collect(),
+            (ComponentType::Float, false, None) => cast_or_read_elements(slice, accessor),
+            // `KHR_mesh_quantization` allows any 8 or 16-bit integers for positions, normalized
+            // or not, and normalized signed integers for normals.
+            (ComponentType::Float, false, _)
+            | (
+                ComponentType::Byte
+                | ComponentType::UnsignedByte
+                | ComponentType::Short
+                | ComponentType::UnsignedShort,
+                _,
+                _,
+            ) => Cow::Owned(
+                read_integer_elements(slice, byte_stride, accessor)
+                    .unwrap_or_else(|| read_elements(slice, byte_stride, accessor)),
             ),
             other => return Err(Error::UnsupportedCombination(std::line!(), other)),
         },
     )
 }
 
-fn read_f32x2<'a>(
+fn read_f32x2<'a, E: Extensions>(
     slice: &'a [u8],
     byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
+    accessor: &crate::Accessor<E>,
 ) -> Result<Cow<'a, [[f32; 2]]>, Error> {
     Ok(
         match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::Float, false, None | Some(8)) => {
-                Cow::Borrowed(bytemuck::cast_slice(slice))
-            }
-            (ComponentType::Float, false, Some(stride)) => {
-                let slice: &[f32] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(stride / 4)
-                        .map(move |slice| std::array::from_fn(|i| slice[i]))
-                        .collect(),
-                )
-            }
-            (ComponentType::UnsignedShort, true, Some(stride)) => {
-                let slice: &[u16] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .chunks(stride / 2)
-                        .map(move |slice| {
-                            std::array::from_fn(|i| unsigned_short_to_float(slice[i]))
-                        })
-                        .collect(),
-                )
-            }
+            (ComponentType::Float, false, None) => cast_or_read_elements(slice, accessor),
+            // Normalized unsigned integers are also allowed for texture coordinates.
+            (ComponentType::Float, false, _)
+            | (ComponentType::UnsignedByte | ComponentType::UnsignedShort, true, _) => Cow::Owned(
+                read_integer_elements(slice, byte_stride, accessor)
+                    .unwrap_or_else(|| read_elements(slice, byte_stride, accessor)),
+            ),
             other => return Err(Error::UnsupportedCombination(std::line!(), other)),
         },
     )
 }
 
-unsafe fn cast_slice<T>(bytes: &[u8]) -> &[T] {
-    std::slice::from_raw_parts(
-        bytes.as_ptr() as *const T,
-        bytes.len() / std::mem::size_of::<T>(),
+pub fn read_f32x4<'a, E: Extensions>(
+    slice: &'a [u8],
+    byte_stride: Option<usize>,
+    accessor: &crate::Accessor<E>,
+) -> Result<Cow<'a, [[f32; 4]]>, Error> {
+    Ok(
+        match (accessor.component_type, accessor.normalized, byte_stride) {
+            (ComponentType::Float, false, None) => cast_or_read_elements(slice, accessor),
+            // Floats and normalized integers, as allowed for weights, colors and rotations.
+            (ComponentType::Float, false, _)
+            | (
+                ComponentType::Byte
+                | ComponentType::UnsignedByte
+                | ComponentType::Short
+                | ComponentType::UnsignedShort,
+                true,
+                _,
+            ) => Cow::Owned(
+                read_integer_elements(slice, byte_stride, accessor)
+                    .unwrap_or_else(|| read_elements(slice, byte_stride, accessor)),
+            ),
+            other => return Err(Error::UnsupportedCombination(std::line!(), other)),
+        },
     )
 }
 
-pub fn read_f32x4<'a>(
+fn read_u32<'a, E: Extensions>(
     slice: &'a [u8],
     byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
-) -> Result<Cow<'a, [[f32; 4]]>, Error> {
+    accessor: &crate::Accessor<E>,
+) -> Result<Cow<'a, [u32]>, Error> {
     Ok(
         match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::Float, false, None) => {
-                // bytemuck::cast_slice panics with an alignment issue on wasm so we just use unsafe for this.
-                // todo: might be wrong.
-                Cow::Borrowed(unsafe { cast_slice(slice) })
-            }
-            (ComponentType::UnsignedByte, true, Some(4)) => Cow::Owned(
-                slice
-                    .chunks(4)
-                    .map(move |slice| std::array::from_fn(|i| unsigned_byte_to_float(slice[i])))
-                    .collect(),
-            ),
-            (ComponentType::Short, true, None) => {
-                let slice: &[[i16; 4]] = bytemuck::cast_slice(slice);
-                Cow::Owned(
-                    slice
-                        .iter()
-                        .map(|slice| std::array::from_fn(|i| signed_short_to_float(slice[i])))
-                        .collect(),
-                )
+            (ComponentType::UnsignedInt, false, None) => cast_or_read_components(slice, accessor),
+            (ComponentType::UnsignedByte, false, None) => {
+                Cow::Owned(slice.iter().map(|&index| index as u32).collect())
             }
+            (
+                ComponentType::UnsignedByte
+                | ComponentType::UnsignedShort
+                | ComponentType::UnsignedInt,
+                false,
+                _,
+            ) => Cow::Owned(read_components(slice, byte_stride, accessor)),
             other => return Err(Error::UnsupportedCombination(std::line!(), other)),
         },
     )
 }
 
-fn read_u32<'a>(
+fn read_u32x4<'a, E: Extensions>(
     slice: &'a [u8],
     byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
-) -> Result<Cow<'a, [u32]>, Error> {
+    accessor: &crate::Accessor<E>,
+) -> Result<Cow<'a, [[u32; 4]]>, Error> {
     Ok(
         match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::UnsignedShort, false, None) => {
-                let slice: &[u16] = bytemuck::cast_slice(slice);
-                Cow::Owned(slice.iter().map(|&i| i as u32).collect())
+            (ComponentType::UnsignedByte | ComponentType::UnsignedShort, false, _) => {
+                Cow::Owned(read_elements(slice, byte_stride, accessor))
             }
-            (ComponentType::UnsignedInt, false, None) => Cow::Borrowed(bytemuck::cast_slice(slice)),
             other => return Err(Error::UnsupportedCombination(std::line!(), other)),
         },
     )
 }
 
-fn read_u32x4<'a>(
-    slice: &'a [u8],
-    byte_stride: Option<usize>,
-    accessor: &crate::Accessor,
-) -> Result<Cow<'a, [[u32; 4]]>, Error> {
-    Ok(
-        match (accessor.component_type, accessor.normalized, byte_stride) {
-            (ComponentType::UnsignedByte, false, Some(4) | None) => Cow::Owned(
-                slice
-                    .chunks(4)
-                    .map(|slice| std::array::from_fn(|i| slice[i] as u32))
-                    .collect(),
-            ),
-            other => return Err(Error::UnsupportedCombination(std::line!(), other)),
-        },
-    )
+/// Index data in the narrowest type that holds it, so that it can be uploaded without conversion.
+#[derive(Debug, Clone)]
+pub enum Indices<'a> {
+    U16(Cow<'a, [u16]>),
+    U32(Cow<'a, [u32]>),
+}
+
+impl<'a> Indices<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::U16(indices) => indices.len(),
+            Self::U32(indices) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Widen the indices to `u32`, copying them if they're 16-bit.
+    pub fn into_u32(self) -> Cow<'a, [u32]> {
+        match self {
+            Self::U16(indices) => Cow::Owned(indices.iter().map(|&index| index as u32).collect()),
+            Self::U32(indices) => indices,
+        }
+    }
+}
+
+/// The standard attributes and indices of a primitive, as read by [`PrimitiveReader::read_all`].
+#[derive(Debug, Clone, Default)]
+pub struct PrimitiveData<'a> {
+    pub positions: Cow<'a, [[f32; 3]]>,
+    pub normals: Option<Cow<'a, [[f32; 3]]>>,
+    pub tangents: Option<Cow<'a, [[f32; 4]]>>,
+    pub uvs: Option<Cow<'a, [[f32; 2]]>>,
+    pub second_uvs: Option<Cow<'a, [[f32; 2]]>>,
+    pub colors: Option<Vec<[f32; 4]>>,
+    pub joints: Option<Cow<'a, [[u32; 4]]>>,
+    pub weights: Option<Cow<'a, [[f32; 4]]>>,
+    pub indices: Option<Indices<'a>>,
+}
+
+impl<'a> PrimitiveData<'a> {
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+/// Decoded accessor data that can be shared between readers, so that accessors used by several
+/// primitives are only decoded once.
+///
+/// Only data that has to be decoded is stored, as data that can be borrowed from the buffer views
+/// doesn't need to be. Each accessor is stored as the type it's first read as.
+pub struct AccessorCache {
+    accessors: Vec<OnceCell<Box<dyn std::any::Any>>>,
+}
+
+impl AccessorCache {
+    pub fn new<E: Extensions>(gltf: &crate::Gltf<E>) -> Self {
+        Self {
+            accessors: (0..gltf.accessors.len()).map(|_| OnceCell::new()).collect(),
+        }
+    }
+
+    fn get<T: 'static>(&self, accessor_index: usize) -> Option<&[T]> {
+        self.accessors
+            .get(accessor_index)?
+            .get()?
+            .downcast_ref::<Vec<T>>()
+            .map(|values| values.as_slice())
+    }
+
+    // Returns the values back if the accessor was already stored as a different type.
+    fn insert<T: 'static>(&self, accessor_index: usize, values: Vec<T>) -> Result<&[T], Vec<T>> {
+        match self.accessors.get(accessor_index) {
+            Some(cell) if cell.get().is_none() => {
+                let _ = cell.set(Box::new(values));
+                Ok(self.get(accessor_index).unwrap())
+            }
+            _ => Err(values),
+        }
+    }
 }
 
 pub struct PrimitiveReader<'a, E: Extensions> {
-    gltf: &'a crate::Gltf<E>,
-    pub primitive: &'a crate::Primitive,
-    buffer_view_map: &'a HashMap<usize, Vec<u8>>,
+    pub(crate) gltf: &'a crate::Gltf<E>,
+    pub primitive: &'a crate::Primitive<E>,
+    pub(crate) buffer_view_map: &'a dyn BufferViewData,
+    draco_decoder: Option<&'a dyn DracoDecoder>,
+    draco_mesh: OnceCell<DracoMesh>,
+    cache: Option<&'a AccessorCache>,
+    policy: ReadPolicy,
+    warnings: RefCell<Vec<ReadWarning>>,
 }
 
 impl<'a, E: Extensions> PrimitiveReader<'a, E>
 where
     E::BufferViewExtensions: MeshOptCompressionExtension,
+    E::PrimitiveExtensions: DracoCompressionExtension,
 {
     pub fn new(
         gltf: &'a crate::Gltf<E>,
-        primitive: &'a crate::Primitive,
-        buffer_view_map: &'a HashMap<usize, Vec<u8>>,
+        primitive: &'a crate::Primitive<E>,
+        buffer_view_map: &'a dyn BufferViewData,
     ) -> Self {
         Self {
             gltf,
             primitive,
             buffer_view_map,
+            draco_decoder: None,
+            draco_mesh: OnceCell::new(),
+            cache: None,
+            policy: ReadPolicy::strict(),
+            warnings: RefCell::new(Vec::new()),
         }
     }
 
-    pub fn read_indices(&self) -> Result<Option<Cow<'a, [u32]>>, Error> {
-        let accessor_index = match self.primitive.indices {
+    /// Work around the problems in the data that `policy` allows, instead of failing. They're
+    /// recorded as warnings, which [`take_warnings`](Self::take_warnings) returns.
+    pub fn with_policy(mut self, policy: ReadPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The problems that were worked around by the reads so far, which are cleared.
+    pub fn take_warnings(&self) -> Vec<ReadWarning> {
+        self.warnings.take()
+    }
+
+    pub(crate) fn read_buffer(
+        &self,
+        accessor: &crate::Accessor<E>,
+    ) -> Result<(Cow<'a, [u8]>, Option<usize>), Error> {
+        let read =
+            read_buffer_with_policy(self.buffer_view_map, self.gltf, accessor, &self.policy)?;
+        self.warnings.borrow_mut().extend(read.warnings);
+        Ok((read.bytes, read.byte_stride))
+    }
+
+    /// Store decoded accessor data in `cache`, and reuse any that's already there.
+    pub fn with_cache(mut self, cache: &'a AccessorCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn cached<T: Clone + 'static>(
+        &self,
+        accessor_index: usize,
+        read: impl FnOnce() -> Result<Cow<'a, [T]>, Error>,
+    ) -> Result<Cow<'a, [T]>, Error> {
+        let cache = match self.cache {
+            Some(cache) => cache,
+            None => return read(),
+        };
+
+        if let Some(values) = cache.get(accessor_index) {
+            return Ok(Cow::Borrowed(values));
+        }
+
+        Ok(match read()? {
+            Cow::Owned(values) => match cache.insert(accessor_index, values) {
+                Ok(values) => Cow::Borrowed(values),
+                Err(values) => Cow::Owned(values),
+            },
+            borrowed => borrowed,
+        })
+    }
+
+    /// Use `decoder` to read primitives compressed with `KHR_draco_mesh_compression`.
+    ///
+    /// The compressed data is decoded on the first read and reused after that.
+    pub fn with_draco_decoder(mut self, decoder: &'a dyn DracoDecoder) -> Self {
+        self.draco_decoder = Some(decoder);
+        self
+    }
+
+    // With `KHR_mesh_quantization`, any integer component type is allowed for these attributes,
+    // so combinations that the fast paths don't handle are read component by component.
+    fn or_quantized<'b, const N: usize>(
+        &self,
+        result: Result<Cow<'b, [[f32; N]]>, Error>,
+        slice: &[u8],
+        byte_stride: Option<usize>,
+        accessor: &crate::Accessor<E>,
+    ) -> Result<Cow<'b, [[f32; N]]>, Error> {
+        match result {
+            Err(Error::UnsupportedCombination(..)) if self.gltf.mesh_quantization() => {
+                Ok(Cow::Owned(
+                    read_integer_elements(slice, byte_stride, accessor).unwrap_or_else(|| {
+                        read_floats(slice, byte_stride, accessor)
+                            .chunks_exact(N)
+                            .map(|chunk| std::array::from_fn(|i| chunk[i]))
+                            .collect()
+                    }),
+                ))
+            }
+            result => result,
+        }
+    }
+
+    fn draco_mesh(&self) -> Result<Option<&DracoMesh>, Error> {
+        let extension = match self.primitive.extensions.khr_draco_mesh_compression() {
+            Some(extension) => extension,
+            None => return Ok(None),
+        };
+
+        if let Some(mesh) = self.draco_mesh.get() {
+            return Ok(Some(mesh));
+        }
+
+        let decoder = self.draco_decoder.ok_or(Error::DracoDecoderMissing)?;
+
+        let bytes = self
+            .buffer_view_map
+            .buffer_view_bytes(extension.buffer_view)
+            .ok_or(Error::BufferViewIndexOutOfBounds(extension.buffer_view))?;
+
+        let mesh = decoder.decode(bytes).map_err(Error::DracoDecode)?;
+
+        Ok(Some(self.draco_mesh.get_or_init(|| mesh)))
+    }
+
+    // Returns `None` if the primitive isn't Draco-compressed or the attribute isn't part of the
+    // compressed data, in which case it's read from its accessor as normal.
+    fn read_draco_attribute<const N: usize>(
+        &self,
+        semantic: &str,
+    ) -> Result<Option<Vec<[f32; N]>>, Error> {
+        Ok(self.draco_attribute_values(semantic)?.map(|values| {
+            values
+                .chunks_exact(N)
+                .map(|chunk| std::array::from_fn(|i| chunk[i]))
+                .collect()
+        }))
+    }
+
+    pub(crate) fn draco_attribute_values(&self, semantic: &str) -> Result<Option<&[f32]>, Error> {
+        let mesh = match self.draco_mesh()? {
+            Some(mesh) => mesh,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .primitive
+            .extensions
+            .khr_draco_mesh_compression()
+            .and_then(|extension| extension.attributes.get(semantic))
+            .and_then(|id| mesh.attributes.get(id))
+            .map(|values| values.as_slice()))
+    }
+
+    /// Read any attribute by its semantic, such as `TEXCOORD_2` or `_FEATURE_ID_0`.
+    ///
+    /// Every component of every element is returned as a float, in order, with normalization
+    /// applied. Integer values above 2^24 can't be represented exactly.
+    pub fn read_attribute(&self, semantic: &str) -> Result<Option<Vec<f32>>, Error> {
+        if let Some(values) = self.draco_attribute_values(semantic)? {
+            return Ok(Some(values.to_vec()));
+        }
+
+        let accessor_index = match self.primitive.attributes.get(semantic) {
             Some(index) => index,
             None => return Ok(None),
         };
@@ -305,14 +1666,18 @@ where
             .accessors
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
+        let (slice, byte_stride) = self.read_buffer(accessor)?;
 
-        Ok(Some(read_u32(slice, byte_stride, accessor)?))
+        Ok(Some(read_floats(&slice, byte_stride, accessor)))
     }
 
-    pub fn read_positions(&self) -> Result<Option<Cow<'a, [[f32; 3]]>>, Error> {
-        let accessor_index = match self.primitive.attributes.position {
+    /// Read the indices, keeping 16-bit indices as they are. 8-bit indices are widened to 16 bits.
+    pub fn read_indices(&self) -> Result<Option<Indices<'a>>, Error> {
+        if let Some(mesh) = self.draco_mesh()? {
+            return Ok(Some(Indices::U32(Cow::Owned(mesh.indices.clone()))));
+        }
+
+        let accessor_index = match self.primitive.indices {
             Some(index) => index,
             None => return Ok(None),
         };
@@ -322,48 +1687,346 @@ where
             .accessors
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
+        let read = || self.read_buffer(accessor);
+
+        Ok(Some(match (accessor.component_type, accessor.normalized) {
+            (ComponentType::UnsignedShort, false) => {
+                Indices::U16(self.cached(accessor_index, || {
+                    let (slice, byte_stride) = read()?;
+                    read_bytes_with(slice, |slice| {
+                        Ok(match byte_stride {
+                            None => cast_or_read_components(slice, accessor),
+                            Some(_) => Cow::Owned(read_components(slice, byte_stride, accessor)),
+                        })
+                    })
+                })?)
+            }
+            (ComponentType::UnsignedByte, false) => {
+                Indices::U16(self.cached(accessor_index, || {
+                    let (slice, byte_stride) = read()?;
+                    Ok(Cow::Owned(match byte_stride {
+                        None => slice.iter().map(|&index| index as u16).collect(),
+                        Some(_) => read_components(&slice, byte_stride, accessor),
+                    }))
+                })?)
+            }
+            _ => Indices::U32(self.cached(accessor_index, || {
+                let (slice, byte_stride) = read()?;
+                read_bytes_with(slice, |slice| read_u32(slice, byte_stride, accessor))
+            })?),
+        }))
+    }
+
+    pub fn read_positions(&self) -> Result<Option<Cow<'a, [[f32; 3]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<3>("POSITION")? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
+        let accessor_index = match self.primitive.attributes.position {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
 
-        Ok(Some(read_f32x3(slice, byte_stride, accessor)?))
+            read_bytes_with(slice, |slice| {
+                self.or_quantized(
+                    read_f32x3(slice, byte_stride, accessor),
+                    slice,
+                    byte_stride,
+                    accessor,
+                )
+            })
+        })
+        .map(Some)
     }
 
     pub fn read_normals(&self) -> Result<Option<Cow<'a, [[f32; 3]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<3>("NORMAL")? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
         let accessor_index = match self.primitive.attributes.normal {
             Some(index) => index,
             None => return Ok(None),
         };
 
-        let accessor = self
-            .gltf
-            .accessors
-            .get(accessor_index)
-            .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
+
+            read_bytes_with(slice, |slice| {
+                self.or_quantized(
+                    read_f32x3(slice, byte_stride, accessor),
+                    slice,
+                    byte_stride,
+                    accessor,
+                )
+            })
+        })
+        .map(Some)
+    }
+
+    pub fn read_tangents(&self) -> Result<Option<Cow<'a, [[f32; 4]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<4>("TANGENT")? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
+        let accessor_index = match self.primitive.attributes.tangent {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
 
-        Ok(Some(read_f32x3(slice, byte_stride, accessor)?))
+            read_bytes_with(slice, |slice| {
+                self.or_quantized(
+                    read_f32x4(slice, byte_stride, accessor),
+                    slice,
+                    byte_stride,
+                    accessor,
+                )
+            })
+        })
+        .map(Some)
     }
 
     pub fn read_uvs(&self) -> Result<Option<Cow<'a, [[f32; 2]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<2>("TEXCOORD_0")? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
         let accessor_index = match self.primitive.attributes.texcoord_0 {
             Some(index) => index,
             None => return Ok(None),
         };
 
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
+
+            read_bytes_with(slice, |slice| {
+                self.or_quantized(
+                    read_f32x2(slice, byte_stride, accessor),
+                    slice,
+                    byte_stride,
+                    accessor,
+                )
+            })
+        })
+        .map(Some)
+    }
+
+    pub fn read_second_uvs(&self) -> Result<Option<Cow<'a, [[f32; 2]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<2>("TEXCOORD_1")? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
+        let accessor_index = match self.primitive.attributes.texcoord_1 {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
+
+            read_bytes_with(slice, |slice| {
+                self.or_quantized(
+                    read_f32x2(slice, byte_stride, accessor),
+                    slice,
+                    byte_stride,
+                    accessor,
+                )
+            })
+        })
+        .map(Some)
+    }
+
+    /// Read the `COLOR_n` attribute for set `set` as RGBA, with an alpha of 1 for RGB colors.
+    pub fn read_colors(&self, set: usize) -> Result<Option<Vec<[f32; 4]>>, Error> {
+        let semantic = format!("COLOR_{}", set);
+
+        let accessor_index = match self.primitive.attributes.get(&semantic) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
         let accessor = self
             .gltf
             .accessors
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
 
-        Ok(Some(read_f32x2(slice, byte_stride, accessor)?))
+        let num_components = accessor.accessor_type.num_components();
+
+        if num_components != 3 && num_components != 4 {
+            return Err(Error::ComponentCountMismatch(4, num_components));
+        }
+
+        Ok(self.read_attribute(&semantic)?.map(|values| {
+            values
+                .chunks_exact(num_components)
+                .map(|color| [color[0], color[1], color[2], *color.get(3).unwrap_or(&1.0)])
+                .collect()
+        }))
     }
 
-    pub fn read_second_uvs(&self) -> Result<Option<Cow<'a, [[f32; 2]]>>, Error> {
-        let accessor_index = match self.primitive.attributes.texcoord_1 {
+    /// Read the `JOINTS_n` attribute for influence set `set`.
+    pub fn read_joints(&self, set: usize) -> Result<Option<Cow<'a, [[u32; 4]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<4>(&format!("JOINTS_{}", set))? {
+            return Ok(Some(Cow::Owned(
+                values
+                    .iter()
+                    .map(|joints| joints.map(|joint| joint as u32))
+                    .collect(),
+            )));
+        }
+
+        let accessor_index = match self.primitive.attributes.joints(set) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
+
+            read_bytes_with(slice, |slice| read_u32x4(slice, byte_stride, accessor))
+        })
+        .map(Some)
+    }
+
+    /// Read the `WEIGHTS_n` attribute for influence set `set`.
+    pub fn read_weights(&self, set: usize) -> Result<Option<Cow<'a, [[f32; 4]]>>, Error> {
+        if let Some(values) = self.read_draco_attribute::<4>(&format!("WEIGHTS_{}", set))? {
+            return Ok(Some(Cow::Owned(values)));
+        }
+
+        let accessor_index = match self.primitive.attributes.weights(set) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        self.cached(accessor_index, || {
+            let accessor = self
+                .gltf
+                .accessors
+                .get(accessor_index)
+                .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
+            let (slice, byte_stride) = self.read_buffer(accessor)?;
+
+            read_bytes_with(slice, |slice| read_f32x4(slice, byte_stride, accessor))
+        })
+        .map(Some)
+    }
+
+    /// Read positions, indices and every other standard attribute in one go, checking that each
+    /// attribute has an element per vertex and that the indices are in range.
+    ///
+    /// Only the first set of colors, joints and weights is read.
+    pub fn read_all(&self) -> Result<PrimitiveData<'a>, Error> {
+        let data = PrimitiveData {
+            positions: self.read_positions()?.ok_or(Error::MissingPositions)?,
+            normals: self.read_normals()?,
+            tangents: self.read_tangents()?,
+            uvs: self.read_uvs()?,
+            second_uvs: self.read_second_uvs()?,
+            colors: self.read_colors(0)?,
+            joints: self.read_joints(0)?,
+            weights: self.read_weights(0)?,
+            indices: self.read_indices()?,
+        };
+
+        let vertex_count = data.vertex_count();
+
+        let counts = [
+            ("NORMAL", data.normals.as_ref().map(|values| values.len())),
+            ("TANGENT", data.tangents.as_ref().map(|values| values.len())),
+            ("TEXCOORD_0", data.uvs.as_ref().map(|values| values.len())),
+            (
+                "TEXCOORD_1",
+                data.second_uvs.as_ref().map(|values| values.len()),
+            ),
+            ("COLOR_0", data.colors.as_ref().map(|values| values.len())),
+            ("JOINTS_0", data.joints.as_ref().map(|values| values.len())),
+            (
+                "WEIGHTS_0",
+                data.weights.as_ref().map(|values| values.len()),
+            ),
+        ];
+
+        for (semantic, count) in counts {
+            match count {
+                Some(count) if count != vertex_count => {
+                    return Err(Error::VertexCountMismatch(semantic, vertex_count, count))
+                }
+                _ => {}
+            }
+        }
+
+        let out_of_bounds = match &data.indices {
+            Some(Indices::U16(indices)) => indices
+                .iter()
+                .map(|&index| index as u32)
+                .find(|&index| index as usize >= vertex_count),
+            Some(Indices::U32(indices)) => indices
+                .iter()
+                .copied()
+                .find(|&index| index as usize >= vertex_count),
+            None => None,
+        };
+
+        if let Some(index) = out_of_bounds {
+            return Err(Error::IndexOutOfBounds(index, vertex_count));
+        }
+
+        Ok(data)
+    }
+
+    /// Iterate over any attribute by its semantic, decoding each element as it's reached.
+    pub fn iter_attribute<T: AccessorElement>(
+        &self,
+        semantic: &str,
+    ) -> Result<Option<AccessorIter<'a, T>>, Error> {
+        if let Some(values) = self.draco_attribute_values(semantic)? {
+            let bytes = values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect();
+            return Ok(Some(AccessorIter::from_packed(bytes, ComponentType::Float)));
+        }
+
+        let accessor_index = match self.primitive.attributes.get(semantic) {
             Some(index) => index,
             None => return Ok(None),
         };
@@ -373,14 +2036,27 @@ where
             .accessors
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
 
-        Ok(Some(read_f32x2(slice, byte_stride, accessor)?))
+        check_component_count::<T, E>(accessor)?;
+        let (bytes, byte_stride) = self.read_buffer(accessor)?;
+
+        Ok(Some(accessor_iter(bytes, byte_stride, accessor)))
     }
 
-    pub fn read_joints(&self) -> Result<Option<Cow<'a, [[u32; 4]]>>, Error> {
-        let accessor_index = match self.primitive.attributes.joints_0 {
+    pub fn iter_indices(&self) -> Result<Option<AccessorIter<'a, u32>>, Error> {
+        if let Some(mesh) = self.draco_mesh()? {
+            let bytes = mesh
+                .indices
+                .iter()
+                .flat_map(|index| index.to_le_bytes())
+                .collect();
+            return Ok(Some(AccessorIter::from_packed(
+                bytes,
+                ComponentType::UnsignedInt,
+            )));
+        }
+
+        let accessor_index = match self.primitive.indices {
             Some(index) => index,
             None => return Ok(None),
         };
@@ -391,26 +2067,215 @@ where
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
 
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
+        check_component_count::<u32, E>(accessor)?;
+        let (bytes, byte_stride) = self.read_buffer(accessor)?;
 
-        Ok(Some(read_u32x4(slice, byte_stride, accessor)?))
+        Ok(Some(accessor_iter(bytes, byte_stride, accessor)))
     }
 
-    pub fn read_weights(&self) -> Result<Option<Cow<'a, [[f32; 4]]>>, Error> {
-        let accessor_index = match self.primitive.attributes.weights_0 {
-            Some(index) => index,
+    pub fn iter_positions(&self) -> Result<Option<AccessorIter<'a, [f32; 3]>>, Error> {
+        self.iter_attribute("POSITION")
+    }
+
+    pub fn iter_normals(&self) -> Result<Option<AccessorIter<'a, [f32; 3]>>, Error> {
+        self.iter_attribute("NORMAL")
+    }
+
+    pub fn iter_uvs(&self, set: usize) -> Result<Option<AccessorIter<'a, [f32; 2]>>, Error> {
+        self.iter_attribute(&format!("TEXCOORD_{}", set))
+    }
+
+    pub fn iter_joints(&self, set: usize) -> Result<Option<AccessorIter<'a, [u32; 4]>>, Error> {
+        self.iter_attribute(&format!("JOINTS_{}", set))
+    }
+
+    pub fn iter_weights(&self, set: usize) -> Result<Option<AccessorIter<'a, [f32; 4]>>, Error> {
+        self.iter_attribute(&format!("WEIGHTS_{}", set))
+    }
+}
+
+/// The transform of a single `EXT_mesh_gpu_instancing` instance, relative to its node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstanceTransform {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+impl InstanceTransform {
+    /// The column-major transform matrix.
+    pub fn matrix(&self) -> [f32; 16] {
+        crate::math::from_trs(self.translation, self.rotation, self.scale)
+    }
+}
+
+/// Reads the per-instance transforms of a node with `EXT_mesh_gpu_instancing`.
+///
+/// Every method returns `None` if the node isn't instanced. Attributes that aren't given default
+/// to the identity transform, and quantized attributes are converted to floats.
+pub struct InstancingReader<'a, E: Extensions> {
+    gltf: &'a crate::Gltf<E>,
+    pub node: &'a crate::Node<E>,
+    buffer_view_map: &'a dyn BufferViewData,
+}
+
+impl<'a, E: Extensions> InstancingReader<'a, E>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+    E::NodeExtensions: MeshGpuInstancingExtension,
+{
+    pub fn new(
+        gltf: &'a crate::Gltf<E>,
+        node: &'a crate::Node<E>,
+        buffer_view_map: &'a dyn BufferViewData,
+    ) -> Self {
+        Self {
+            gltf,
+            node,
+            buffer_view_map,
+        }
+    }
+
+    fn attributes(&self) -> Option<extensions::ExtMeshGpuInstancingAttributes> {
+        self.node
+            .extensions
+            .ext_mesh_gpu_instancing()
+            .map(|extension| extension.attributes)
+    }
+
+    fn accessor(&self, accessor_index: usize) -> Result<&'a crate::Accessor<E>, Error> {
+        self.gltf
+            .accessors
+            .get(accessor_index)
+            .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))
+    }
+
+    /// The number of instances, taken from the first attribute that's given.
+    pub fn instance_count(&self) -> Result<Option<usize>, Error> {
+        let attributes = match self.attributes() {
+            Some(attributes) => attributes,
+            None => return Ok(None),
+        };
+
+        let accessor_index = attributes
+            .translation
+            .or(attributes.rotation)
+            .or(attributes.scale);
+
+        Ok(Some(match accessor_index {
+            Some(index) => self.accessor(index)?.count,
+            None => 0,
+        }))
+    }
+
+    fn read_or<T: AccessorElement + Clone>(
+        &self,
+        accessor_index: Option<usize>,
+        default: T,
+    ) -> Result<Option<Vec<T>>, Error> {
+        let count = match self.instance_count()? {
+            Some(count) => count,
             None => return Ok(None),
         };
 
+        Ok(Some(match accessor_index {
+            Some(index) => read_accessor(self.buffer_view_map, self.gltf, self.accessor(index)?)?,
+            None => vec![default; count],
+        }))
+    }
+
+    pub fn read_translations(&self) -> Result<Option<Vec<[f32; 3]>>, Error> {
+        let accessor_index = self
+            .attributes()
+            .and_then(|attributes| attributes.translation);
+        self.read_or(accessor_index, [0.0; 3])
+    }
+
+    /// Rotations as `[x, y, z, w]` quaternions, renormalized to undo quantization error.
+    pub fn read_rotations(&self) -> Result<Option<Vec<[f32; 4]>>, Error> {
+        let accessor_index = self.attributes().and_then(|attributes| attributes.rotation);
+        Ok(self
+            .read_or(accessor_index, [0.0, 0.0, 0.0, 1.0])?
+            .map(|rotations| rotations.into_iter().map(crate::math::normalize4).collect()))
+    }
+
+    pub fn read_scales(&self) -> Result<Option<Vec<[f32; 3]>>, Error> {
+        let accessor_index = self.attributes().and_then(|attributes| attributes.scale);
+        self.read_or(accessor_index, [1.0; 3])
+    }
+
+    /// The translation, rotation and scale of each instance.
+    pub fn read_transforms(&self) -> Result<Option<Vec<InstanceTransform>>, Error> {
+        let (translations, rotations, scales) = match (
+            self.read_translations()?,
+            self.read_rotations()?,
+            self.read_scales()?,
+        ) {
+            (Some(translations), Some(rotations), Some(scales)) => {
+                (translations, rotations, scales)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(
+            translations
+                .into_iter()
+                .zip(rotations)
+                .zip(scales)
+                .map(|((translation, rotation), scale)| InstanceTransform {
+                    translation,
+                    rotation,
+                    scale,
+                })
+                .collect(),
+        ))
+    }
+
+    /// The column-major transform matrix of each instance, relative to the node.
+    pub fn read_matrices(&self) -> Result<Option<Vec<[f32; 16]>>, Error> {
+        Ok(self
+            .read_transforms()?
+            .map(|transforms| transforms.iter().map(InstanceTransform::matrix).collect()))
+    }
+}
+
+/// Reads the joint data of a skin.
+pub struct SkinReader<'a, E: Extensions> {
+    gltf: &'a crate::Gltf<E>,
+    pub skin: &'a crate::Skin<E>,
+    buffer_view_map: &'a dyn BufferViewData,
+}
+
+impl<'a, E: Extensions> SkinReader<'a, E>
+where
+    E::BufferViewExtensions: MeshOptCompressionExtension,
+{
+    pub fn new(
+        gltf: &'a crate::Gltf<E>,
+        skin: &'a crate::Skin<E>,
+        buffer_view_map: &'a dyn BufferViewData,
+    ) -> Self {
+        Self {
+            gltf,
+            skin,
+            buffer_view_map,
+        }
+    }
+
+    /// The column-major inverse bind matrix of each joint. If the skin doesn't have any, every
+    /// joint gets the identity matrix, as the spec requires.
+    pub fn read_inverse_bind_matrices(&self) -> Result<Vec<[f32; 16]>, Error> {
+        let accessor_index = match self.skin.inverse_bind_matrices {
+            Some(accessor_index) => accessor_index,
+            None => return Ok(vec![crate::math::IDENTITY; self.skin.joints.len()]),
+        };
+
         let accessor = self
             .gltf
             .accessors
             .get(accessor_index)
             .ok_or(Error::AccessorIndexOutOfBounds(accessor_index))?;
-        let (slice, byte_stride) =
-            read_buffer_with_accessor(self.buffer_view_map, self.gltf, accessor)?;
 
-        Ok(Some(read_f32x4(slice, byte_stride, accessor)?))
+        read_accessor(self.buffer_view_map, self.gltf, accessor)
     }
 }
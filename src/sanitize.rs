@@ -0,0 +1,365 @@
+use crate::edit::remove_unknown;
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::write::PathSegment;
+use crate::{math, Extensions, Gltf};
+
+/// A single change made by [`Gltf::sanitize`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Repair {
+    /// An out-of-range index was removed from the given location, e.g. `nodes[3].mesh`.
+    DroppedReference { location: String, index: usize },
+    /// An animation channel was removed because its target node or sampler doesn't exist.
+    DroppedChannel { animation: usize, channel: usize },
+    /// A node rotation was renormalized to a unit quaternion. This isn't done with the `no_std`
+    /// feature, as it needs float math from `std`.
+    NormalizedRotation { node: usize },
+    /// A mesh's default morph weights didn't match its number of morph targets.
+    ResizedMorphWeights { mesh: usize, from: usize, to: usize },
+    /// A NaN or infinite value was replaced with its default.
+    ReplacedNonFinite { location: String },
+}
+
+/// Everything [`Gltf::sanitize`] changed, in the order the changes were made.
+#[derive(Debug, Clone, Default)]
+pub struct SanitizeReport {
+    pub repairs: Vec<Repair>,
+}
+
+impl SanitizeReport {
+    pub fn is_empty(&self) -> bool {
+        self.repairs.is_empty()
+    }
+
+    fn check(&mut self, reference: &mut Option<usize>, len: usize, location: impl Fn() -> String) {
+        if let Some(index) = *reference {
+            if index >= len {
+                *reference = None;
+                self.repairs.push(Repair::DroppedReference {
+                    location: location(),
+                    index,
+                });
+            }
+        }
+    }
+
+    fn retain(&mut self, references: &mut Vec<usize>, len: usize, location: impl Fn() -> String) {
+        references.retain(|&index| {
+            let valid = index < len;
+            if !valid {
+                self.repairs.push(Repair::DroppedReference {
+                    location: location(),
+                    index,
+                });
+            }
+            valid
+        });
+    }
+
+    fn finite(&mut self, values: &mut [f32], default: &[f32], location: impl Fn() -> String) {
+        if values.iter().any(|value| !value.is_finite()) {
+            values.copy_from_slice(default);
+            self.repairs.push(Repair::ReplacedNonFinite {
+                location: location(),
+            });
+        }
+    }
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Fix common problems in place rather than rejecting the document.
+    ///
+    /// Out-of-range references are removed, animation channels that can't be evaluated are
+    /// dropped, node rotations are renormalized and non-finite transform and material values
+    /// are replaced with their defaults. Extension data is left untouched.
+    pub fn sanitize(&mut self) -> SanitizeReport {
+        let mut report = SanitizeReport::default();
+
+        let num_accessors = self.accessors.len();
+        let num_buffer_views = self.buffer_views.len();
+        let num_images = self.images.len();
+        let num_materials = self.materials.len();
+        let num_meshes = self.meshes.len();
+        let num_nodes = self.nodes.len();
+        let num_samplers = self.samplers.len();
+        let num_skins = self.skins.len();
+        let num_cameras = self.cameras.len();
+        let num_textures = self.textures.len();
+
+        for (i, accessor) in self.accessors.iter_mut().enumerate() {
+            report.check(&mut accessor.buffer_view, num_buffer_views, || {
+                format!("accessors[{}].bufferView", i)
+            });
+
+            let sparse_is_invalid = matches!(
+                &accessor.sparse,
+                Some(sparse) if sparse.indices.buffer_view >= num_buffer_views
+                    || sparse.values.buffer_view >= num_buffer_views
+            );
+
+            if sparse_is_invalid {
+                let sparse = accessor.sparse.take().unwrap();
+                report.repairs.push(Repair::DroppedReference {
+                    location: format!("accessors[{}].sparse", i),
+                    index: sparse.indices.buffer_view.max(sparse.values.buffer_view),
+                });
+            }
+        }
+
+        for (i, image) in self.images.iter_mut().enumerate() {
+            report.check(&mut image.buffer_view, num_buffer_views, || {
+                format!("images[{}].bufferView", i)
+            });
+        }
+
+        for (i, texture) in self.textures.iter_mut().enumerate() {
+            report.check(&mut texture.sampler, num_samplers, || {
+                format!("textures[{}].sampler", i)
+            });
+            report.check(&mut texture.source, num_images, || {
+                format!("textures[{}].source", i)
+            });
+        }
+
+        for (i, material) in self.materials.iter_mut().enumerate() {
+            macro_rules! check_texture {
+                ($texture:expr, $name:expr) => {
+                    if let Some(index) = $texture.as_ref().map(|info| info.index) {
+                        if index >= num_textures {
+                            $texture = None;
+                            report.repairs.push(Repair::DroppedReference {
+                                location: format!("materials[{}].{}", i, $name),
+                                index,
+                            });
+                        }
+                    }
+                };
+            }
+
+            check_texture!(
+                material.pbr_metallic_roughness.base_color_texture,
+                "pbrMetallicRoughness.baseColorTexture"
+            );
+            check_texture!(
+                material.pbr_metallic_roughness.metallic_roughness_texture,
+                "pbrMetallicRoughness.metallicRoughnessTexture"
+            );
+            check_texture!(material.normal_texture, "normalTexture");
+            check_texture!(material.occlusion_texture, "occlusionTexture");
+            check_texture!(material.emissive_texture, "emissiveTexture");
+
+            let pbr = &mut material.pbr_metallic_roughness;
+            report.finite(&mut pbr.base_color_factor, &[1.0; 4], || {
+                format!("materials[{}].pbrMetallicRoughness.baseColorFactor", i)
+            });
+
+            report.finite(
+                core::slice::from_mut(&mut pbr.metallic_factor),
+                &[1.0],
+                || format!("materials[{}].pbrMetallicRoughness.metallicFactor", i),
+            );
+            report.finite(
+                core::slice::from_mut(&mut pbr.roughness_factor),
+                &[1.0],
+                || format!("materials[{}].pbrMetallicRoughness.roughnessFactor", i),
+            );
+
+            report.finite(&mut material.emissive_factor, &[0.0; 3], || {
+                format!("materials[{}].emissiveFactor", i)
+            });
+
+            report.finite(
+                core::slice::from_mut(&mut material.alpha_cutoff),
+                &[0.5],
+                || format!("materials[{}].alphaCutoff", i),
+            );
+        }
+
+        for (i, mesh) in self.meshes.iter_mut().enumerate() {
+            let mut num_targets = 0;
+
+            for (j, primitive) in mesh.primitives.iter_mut().enumerate() {
+                report.check(&mut primitive.indices, num_accessors, || {
+                    format!("meshes[{}].primitives[{}].indices", i, j)
+                });
+                report.check(&mut primitive.material, num_materials, || {
+                    format!("meshes[{}].primitives[{}].material", i, j)
+                });
+
+                let attribute_sets =
+                    core::iter::once(("attributes".to_string(), &mut primitive.attributes)).chain(
+                        primitive
+                            .targets
+                            .iter_mut()
+                            .flatten()
+                            .enumerate()
+                            .map(|(k, target)| (format!("targets[{}]", k), target)),
+                    );
+
+                for (name, attributes) in attribute_sets {
+                    for (semantic, accessor) in attributes.known_mut() {
+                        report.check(accessor, num_accessors, || {
+                            format!("meshes[{}].primitives[{}].{}.{}", i, j, name, semantic)
+                        });
+                    }
+
+                    attributes.other.retain(|(semantic, index)| {
+                        let valid = *index < num_accessors;
+                        if !valid {
+                            report.repairs.push(Repair::DroppedReference {
+                                location: format!(
+                                    "meshes[{}].primitives[{}].{}.{}",
+                                    i, j, name, semantic
+                                ),
+                                index: *index,
+                            });
+                        }
+                        valid
+                    });
+                }
+
+                num_targets = num_targets.max(primitive.targets.as_ref().map_or(0, Vec::len));
+            }
+
+            if let Some(weights) = mesh.weights.as_mut() {
+                if weights.len() != num_targets {
+                    report.repairs.push(Repair::ResizedMorphWeights {
+                        mesh: i,
+                        from: weights.len(),
+                        to: num_targets,
+                    });
+                    weights.resize(num_targets, 0.0);
+                }
+
+                for (j, weight) in weights.iter_mut().enumerate() {
+                    report.finite(core::slice::from_mut(weight), &[0.0], || {
+                        format!("meshes[{}].weights[{}]", i, j)
+                    });
+                }
+            }
+        }
+
+        for (i, skin) in self.skins.iter_mut().enumerate() {
+            report.check(&mut skin.inverse_bind_matrices, num_accessors, || {
+                format!("skins[{}].inverseBindMatrices", i)
+            });
+            report.check(&mut skin.skeleton, num_nodes, || {
+                format!("skins[{}].skeleton", i)
+            });
+        }
+
+        // Skins with missing joints can't be evaluated, and removing the joints would change the
+        // meaning of the JOINTS_n data, so the skin reference is dropped from nodes instead.
+        let skin_is_valid: Vec<bool> = self
+            .skins
+            .iter()
+            .map(|skin| skin.joints.iter().all(|&joint| joint < num_nodes))
+            .collect();
+
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            report.check(&mut node.camera, num_cameras, || {
+                format!("nodes[{}].camera", i)
+            });
+            report.check(&mut node.mesh, num_meshes, || format!("nodes[{}].mesh", i));
+            report.check(&mut node.skin, num_skins, || format!("nodes[{}].skin", i));
+
+            if let Some(skin) = node.skin {
+                if !skin_is_valid[skin] {
+                    node.skin = None;
+                    report.repairs.push(Repair::DroppedReference {
+                        location: format!("nodes[{}].skin", i),
+                        index: skin,
+                    });
+                }
+            }
+
+            report.retain(&mut node.children, num_nodes, || {
+                format!("nodes[{}].children", i)
+            });
+
+            if node.children.contains(&i) {
+                node.children.retain(|&child| child != i);
+                report.repairs.push(Repair::DroppedReference {
+                    location: format!("nodes[{}].children", i),
+                    index: i,
+                });
+            }
+
+            if let Some(translation) = node.translation.as_mut() {
+                report.finite(translation, &[0.0; 3], || {
+                    format!("nodes[{}].translation", i)
+                });
+            }
+
+            if let Some(scale) = node.scale.as_mut() {
+                report.finite(scale, &[1.0; 3], || format!("nodes[{}].scale", i));
+            }
+
+            if let Some(matrix) = node.matrix.as_mut() {
+                report.finite(matrix, &math::IDENTITY, || format!("nodes[{}].matrix", i));
+            }
+
+            if let Some(rotation) = node.rotation.as_mut() {
+                report.finite(rotation, &[0.0, 0.0, 0.0, 1.0], || {
+                    format!("nodes[{}].rotation", i)
+                });
+
+                #[cfg(not(feature = "no_std"))]
+                if (math::dot4(*rotation, *rotation) - 1.0).abs() > 1.0e-5 {
+                    *rotation = math::normalize4(*rotation);
+                    report.repairs.push(Repair::NormalizedRotation { node: i });
+                }
+            }
+        }
+
+        for (i, animation) in self.animations.iter_mut().enumerate() {
+            let samplers_are_valid: Vec<bool> = animation
+                .samplers
+                .iter()
+                .map(|sampler| sampler.input < num_accessors && sampler.output < num_accessors)
+                .collect();
+
+            let mut channel_index = 0;
+            let mut dropped = 0;
+
+            animation.channels.retain(|channel| {
+                let valid = samplers_are_valid
+                    .get(channel.sampler)
+                    .copied()
+                    .unwrap_or(false)
+                    && !matches!(channel.target.node, Some(node) if node >= num_nodes);
+
+                if !valid {
+                    report.repairs.push(Repair::DroppedChannel {
+                        animation: i,
+                        channel: channel_index,
+                    });
+                    remove_unknown(
+                        &mut self.unknown_json,
+                        &[
+                            PathSegment::key("animations"),
+                            PathSegment::Index(i),
+                            PathSegment::key("channels"),
+                        ],
+                        channel_index - dropped,
+                    );
+                    dropped += 1;
+                }
+
+                channel_index += 1;
+                valid
+            });
+        }
+
+        for (i, scene) in self.scenes.iter_mut().enumerate() {
+            report.retain(&mut scene.nodes, num_nodes, || {
+                format!("scenes[{}].nodes", i)
+            });
+        }
+
+        let num_scenes = self.scenes.len();
+        report.check(&mut self.scene, num_scenes, || "scene".to_string());
+
+        report
+    }
+}
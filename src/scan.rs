@@ -0,0 +1,139 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{glb, json, json_str, GlbError};
+use alloc::borrow::Cow;
+use core::str::Chars;
+use nanoserde::{DeJsonErr, DeJsonState};
+
+/// An object whose members are only scanned for their extent, so that strings can be borrowed
+/// from the source JSON instead of being deserialized into a `String` each. This suits tools
+/// that go through the names and uris of a lot of large files.
+///
+/// ```
+/// # use goth_gltf::scan::ScannedObject;
+/// let json = r#"{"images": [{"uri": "a.png", "mimeType": "image/png"}, {"name": "b"}]}"#;
+/// let document = ScannedObject::parse(json).unwrap();
+/// let images = document.objects("images").unwrap();
+///
+/// assert_eq!(images[0].uri().as_deref(), Some("a.png"));
+/// assert_eq!(images[1].name().as_deref(), Some("b"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScannedObject<'a> {
+    // The keys are still escaped.
+    members: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> ScannedObject<'a> {
+    /// Scan the top level of either a gltf or a glb file.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, GlbError> {
+        let (json_bytes, _) = glb::split_chunks(bytes)?;
+
+        Ok(Self::parse(json_str(json_bytes)?)?)
+    }
+
+    pub fn parse(json: &'a str) -> Result<Self, DeJsonErr> {
+        json::member_spans(json)
+            .map(|members| Self { members })
+            .ok_or_else(|| DeJsonState::default().err_parse("a JSON object"))
+    }
+
+    /// The source text of the member `key`, which is compared with the escaped keys.
+    pub fn raw(&self, key: &str) -> Option<&'a str> {
+        self.members
+            .iter()
+            .rev()
+            .find(|(member, _)| *member == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// The string member `key`, or `None` if it's missing or not a string. It's borrowed unless
+    /// it has escapes that need to be replaced.
+    pub fn str(&self, key: &str) -> Option<Cow<'a, str>> {
+        let escaped = self.raw(key)?.strip_prefix('"')?.strip_suffix('"')?;
+
+        unescape(escaped)
+    }
+
+    /// The object member `key`, or `None` if it's missing or not an object.
+    pub fn object(&self, key: &str) -> Option<Self> {
+        Self::parse(self.raw(key)?).ok()
+    }
+
+    /// The array of objects `key`, such as the nodes of a document. Returns `None` if it's
+    /// missing or has elements that aren't objects.
+    pub fn objects(&self, key: &str) -> Option<Vec<Self>> {
+        json::element_spans(self.raw(key)?)?
+            .into_iter()
+            .map(|element| Self::parse(element).ok())
+            .collect()
+    }
+
+    pub fn name(&self) -> Option<Cow<'a, str>> {
+        self.str("name")
+    }
+
+    pub fn uri(&self) -> Option<Cow<'a, str>> {
+        self.str("uri")
+    }
+
+    pub fn mime_type(&self) -> Option<Cow<'a, str>> {
+        self.str("mimeType")
+    }
+}
+
+// Most strings don't have any escapes, and those are borrowed as they are.
+fn unescape(escaped: &str) -> Option<Cow<'_, str>> {
+    if !escaped.contains('\\') {
+        return Some(Cow::Borrowed(escaped));
+    }
+
+    let mut output = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        output.push(match chars.next()? {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let code = hex4(&mut chars)?;
+
+                // Characters outside of the basic multilingual plane are escaped as a UTF-16
+                // surrogate pair.
+                if (0xD800..0xDC00).contains(&code) {
+                    if chars.next()? != '\\' || chars.next()? != 'u' {
+                        return None;
+                    }
+
+                    let low = hex4(&mut chars)?;
+
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return None;
+                    }
+
+                    char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))?
+                } else {
+                    char::from_u32(code)?
+                }
+            }
+            _ => return None,
+        });
+    }
+
+    Some(Cow::Owned(output))
+}
+
+fn hex4(chars: &mut Chars) -> Option<u32> {
+    (0..4).try_fold(0, |code, _| Some(code * 16 + chars.next()?.to_digit(16)?))
+}
@@ -0,0 +1,443 @@
+use crate::math::{self, Mat4};
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{Extensions, Gltf, Material, Named, Node, NodeTransform, Primitive};
+use core::fmt;
+
+impl<E: Extensions> Gltf<E> {
+    /// Walk the node hierarchy of a scene depth-first, visiting each node before its children.
+    ///
+    /// Returns `None` if the scene doesn't exist. Nodes that are out of bounds are skipped, as
+    /// are nodes that have already been visited, so malformed files with cycles still terminate.
+    pub fn scene_nodes(&self, scene: usize) -> Option<SceneNodes<'_, E>> {
+        let scene = self.scenes.get(scene)?;
+
+        Some(SceneNodes {
+            gltf: self,
+            stack: scene
+                .nodes
+                .iter()
+                .rev()
+                .map(|&node| (node, math::IDENTITY))
+                .collect(),
+            visited: vec![false; self.nodes.len()],
+        })
+    }
+
+    /// Build a table from each node to its parent, as `children` only links downwards.
+    ///
+    /// A node that is the child of more than one parent is invalid. The first parent found is
+    /// used for it, and it's listed in [`NodeParents::multiple_parents`]. Children that are out
+    /// of bounds are ignored.
+    pub fn node_parents(&self) -> NodeParents {
+        let mut parents = vec![None; self.nodes.len()];
+        let mut multiple_parents = Vec::new();
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            for &child in &node.children {
+                match parents.get_mut(child) {
+                    Some(parent @ None) => *parent = Some(index),
+                    Some(Some(_)) if !multiple_parents.contains(&child) => {
+                        multiple_parents.push(child);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        NodeParents {
+            parents,
+            multiple_parents,
+        }
+    }
+
+    /// Check the `children` links between nodes for problems that would send a naive
+    /// recursive traversal into an infinite loop or visit a node twice.
+    pub fn check_node_graph(&self) -> NodeGraphReport {
+        let mut report = NodeGraphReport {
+            multiple_parents: self.node_parents().multiple_parents,
+            ..Default::default()
+        };
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            for &child in &node.children {
+                if child >= self.nodes.len() {
+                    report.out_of_bounds_children.push((index, child));
+                }
+            }
+        }
+
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Unvisited,
+            OnPath,
+            Done,
+        }
+
+        let mut states = vec![State::Unvisited; self.nodes.len()];
+
+        for root in 0..self.nodes.len() {
+            if states[root] != State::Unvisited {
+                continue;
+            }
+
+            states[root] = State::OnPath;
+            // The current path from the root, with the position in each node's children.
+            let mut path = vec![(root, 0)];
+
+            while let Some((node, next_child)) = path.last_mut() {
+                let node = *node;
+
+                let child = match self.nodes[node].children.get(*next_child) {
+                    Some(&child) => child,
+                    None => {
+                        states[node] = State::Done;
+                        path.pop();
+                        continue;
+                    }
+                };
+
+                *next_child += 1;
+
+                match states.get(child) {
+                    Some(State::Unvisited) => {
+                        states[child] = State::OnPath;
+                        path.push((child, 0));
+                    }
+                    Some(State::OnPath) => {
+                        let start = path.iter().position(|&(node, _)| node == child).unwrap();
+                        report
+                            .cycles
+                            .push(path[start..].iter().map(|&(node, _)| node).collect());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        report
+    }
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Display the scenes and their node hierarchies as an indented tree, e.g.
+    ///
+    /// ```text
+    /// scene 0 "Scene" (default)
+    ///   node 0 "Armature" rotation=[0.7071, 0.0, 0.0, 0.7071]
+    ///     node 1 "Body" mesh=0 skin=0
+    /// ```
+    ///
+    /// Nodes that aren't in any scene are listed afterwards. Each node is shown once, even if
+    /// the hierarchy has cycles.
+    pub fn display_hierarchy(&self) -> HierarchyDisplay<'_, E> {
+        HierarchyDisplay { gltf: self }
+    }
+}
+
+/// Displays the node hierarchy, created by [`Gltf::display_hierarchy`].
+pub struct HierarchyDisplay<'a, E: Extensions> {
+    gltf: &'a Gltf<E>,
+}
+
+impl<'a, E: Extensions> HierarchyDisplay<'a, E> {
+    // Uses an explicit stack rather than recursion, as exported bone chains can be thousands of
+    // nodes deep.
+    fn write_node(
+        &self,
+        f: &mut fmt::Formatter,
+        index: usize,
+        depth: usize,
+        visited: &mut [bool],
+    ) -> fmt::Result {
+        let mut stack = vec![(index, depth)];
+
+        while let Some((index, depth)) = stack.pop() {
+            write!(f, "{:indent$}node {}", "", index, indent = depth * 2)?;
+
+            let node = match (self.gltf.nodes.get(index), visited.get_mut(index)) {
+                (Some(node), Some(visited)) => {
+                    if *visited {
+                        writeln!(f, " (already shown)")?;
+                        continue;
+                    }
+
+                    *visited = true;
+                    node
+                }
+                _ => {
+                    writeln!(f, " (missing)")?;
+                    continue;
+                }
+            };
+
+            write_node_properties(f, node)?;
+            writeln!(f)?;
+
+            stack.extend(node.children.iter().rev().map(|&child| (child, depth + 1)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, E: Extensions> fmt::Display for HierarchyDisplay<'a, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut visited = vec![false; self.gltf.nodes.len()];
+
+        for (index, scene) in self.gltf.scenes.iter().enumerate() {
+            write!(f, "scene {}", index)?;
+            if let Some(name) = scene.name() {
+                write!(f, " {:?}", name)?;
+            }
+            if self.gltf.scene == Some(index) {
+                write!(f, " (default)")?;
+            }
+            writeln!(f)?;
+
+            for &node in &scene.nodes {
+                self.write_node(f, node, 1, &mut visited)?;
+            }
+        }
+
+        let parents = self.gltf.node_parents();
+        let roots: Vec<usize> = (0..self.gltf.nodes.len())
+            .filter(|&index| !visited[index] && parents.parent(index).is_none())
+            .collect();
+
+        if !roots.is_empty() {
+            writeln!(f, "not in a scene")?;
+
+            for root in roots {
+                self.write_node(f, root, 1, &mut visited)?;
+            }
+        }
+
+        // Anything left over is only reachable through a cycle.
+        for index in 0..self.gltf.nodes.len() {
+            if !visited[index] {
+                self.write_node(f, index, 1, &mut visited)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_node_properties<E: Extensions>(f: &mut fmt::Formatter, node: &Node<E>) -> fmt::Result {
+    if let Some(name) = node.name() {
+        write!(f, " {:?}", name)?;
+    }
+
+    if let Some(mesh) = node.mesh {
+        write!(f, " mesh={}", mesh)?;
+    }
+    if let Some(skin) = node.skin {
+        write!(f, " skin={}", skin)?;
+    }
+    if let Some(camera) = node.camera {
+        write!(f, " camera={}", camera)?;
+    }
+
+    match node.transform() {
+        NodeTransform::Matrix(matrix) => {
+            if matrix != math::IDENTITY {
+                write!(f, " matrix={:?}", matrix)?;
+            }
+        }
+        NodeTransform::Set {
+            translation,
+            rotation,
+            scale,
+        } => {
+            if translation != [0.0; 3] {
+                write!(f, " translation={:?}", translation)?;
+            }
+            if rotation != [0.0, 0.0, 0.0, 1.0] {
+                write!(f, " rotation={:?}", rotation)?;
+            }
+            if scale != [1.0; 3] {
+                write!(f, " scale={:?}", scale)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Every primitive of every mesh, with its material resolved.
+    pub fn primitives(&self) -> impl Iterator<Item = MeshPrimitive<'_, E>> {
+        (0..self.meshes.len()).flat_map(move |mesh| self.mesh_primitives(mesh))
+    }
+
+    /// The primitives of each node with a mesh in a scene, in traversal order. A mesh that is
+    /// used by several nodes is visited once per node.
+    pub fn scene_primitives(
+        &self,
+        scene: usize,
+    ) -> Option<impl Iterator<Item = (SceneNode, MeshPrimitive<'_, E>)>> {
+        Some(self.scene_nodes(scene)?.flat_map(move |node| {
+            self.nodes[node.index]
+                .mesh
+                .into_iter()
+                .flat_map(move |mesh| self.mesh_primitives(mesh))
+                .map(move |primitive| (node, primitive))
+        }))
+    }
+
+    fn mesh_primitives(&self, mesh: usize) -> impl Iterator<Item = MeshPrimitive<'_, E>> {
+        self.meshes
+            .get(mesh)
+            .into_iter()
+            .flat_map(|mesh| mesh.primitives.iter().enumerate())
+            .map(move |(index, primitive)| MeshPrimitive {
+                mesh,
+                index,
+                primitive,
+                material: match primitive
+                    .material
+                    .and_then(|index| Some((index, self.materials.get(index)?)))
+                {
+                    Some((index, material)) => PrimitiveMaterial::Indexed(index, material),
+                    None => PrimitiveMaterial::Default(Default::default()),
+                },
+            })
+    }
+}
+
+/// A primitive visited by [`Gltf::primitives`] or [`Gltf::scene_primitives`].
+pub struct MeshPrimitive<'a, E: Extensions> {
+    pub mesh: usize,
+    /// The index of the primitive within the mesh.
+    pub index: usize,
+    pub primitive: &'a Primitive<E>,
+    pub material: PrimitiveMaterial<'a, E>,
+}
+
+/// The material of a [`MeshPrimitive`]. Derefs to the material.
+pub enum PrimitiveMaterial<'a, E: Extensions> {
+    Indexed(usize, &'a Material<E>),
+    /// The default material from the spec, used when the primitive doesn't have a material or
+    /// the index is out of bounds.
+    Default(Material<E>),
+}
+
+impl<'a, E: Extensions> PrimitiveMaterial<'a, E> {
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::Indexed(index, _) => Some(*index),
+            Self::Default(_) => None,
+        }
+    }
+}
+
+impl<'a, E: Extensions> core::ops::Deref for PrimitiveMaterial<'a, E> {
+    type Target = Material<E>;
+
+    fn deref(&self) -> &Material<E> {
+        match self {
+            Self::Indexed(_, material) => material,
+            Self::Default(material) => material,
+        }
+    }
+}
+
+/// Problems found in the node hierarchy by [`Gltf::check_node_graph`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeGraphReport {
+    /// Each cycle found, as the nodes along it in order. The last node lists the first as a
+    /// child.
+    pub cycles: Vec<Vec<usize>>,
+    /// Nodes that are the child of more than one parent.
+    pub multiple_parents: Vec<usize>,
+    /// Children that don't exist, as `(parent, child)`.
+    pub out_of_bounds_children: Vec<(usize, usize)>,
+}
+
+impl NodeGraphReport {
+    pub fn is_clean(&self) -> bool {
+        self.cycles.is_empty()
+            && self.multiple_parents.is_empty()
+            && self.out_of_bounds_children.is_empty()
+    }
+}
+
+/// The parent of each node, created by [`Gltf::node_parents`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeParents {
+    /// The parent of each node, indexed by node. `None` for root nodes.
+    pub parents: Vec<Option<usize>>,
+    /// Nodes that are the child of more than one parent, which the spec doesn't allow.
+    pub multiple_parents: Vec<usize>,
+}
+
+impl NodeParents {
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents.get(node).copied().flatten()
+    }
+
+    /// The ancestors of a node, starting with its parent. Stops early if the parent links
+    /// form a cycle.
+    pub fn ancestors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut current = node;
+        let mut steps = 0;
+
+        core::iter::from_fn(move || {
+            if steps >= self.parents.len() {
+                return None;
+            }
+
+            steps += 1;
+            current = self.parent(current)?;
+            Some(current)
+        })
+    }
+}
+
+/// A node visited by [`SceneNodes`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneNode {
+    pub index: usize,
+    /// The column-major transform from the node's local space to the scene's.
+    pub world_matrix: Mat4,
+}
+
+/// An iterator over the nodes of a scene, created by [`Gltf::scene_nodes`].
+pub struct SceneNodes<'a, E: Extensions> {
+    gltf: &'a Gltf<E>,
+    // The nodes left to visit, along with the world matrix of their parent.
+    stack: Vec<(usize, Mat4)>,
+    visited: Vec<bool>,
+}
+
+impl<'a, E: Extensions> Iterator for SceneNodes<'a, E> {
+    type Item = SceneNode;
+
+    fn next(&mut self) -> Option<SceneNode> {
+        loop {
+            let (index, parent_matrix) = self.stack.pop()?;
+
+            let (node, visited) = match (self.gltf.nodes.get(index), self.visited.get_mut(index)) {
+                (Some(node), Some(visited)) if !*visited => (node, visited),
+                _ => continue,
+            };
+
+            *visited = true;
+
+            let world_matrix = math::mul(&parent_matrix, &node.transform().to_matrix());
+
+            self.stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, world_matrix)),
+            );
+
+            return Some(SceneNode {
+                index,
+                world_matrix,
+            });
+        }
+    }
+}
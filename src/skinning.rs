@@ -0,0 +1,111 @@
+use crate::math::{self, Mat4};
+
+/// One set of `JOINTS_n`/`WEIGHTS_n` attributes.
+#[derive(Debug, Clone, Copy)]
+pub struct InfluenceSet<'a> {
+    pub joints: &'a [[u32; 4]],
+    pub weights: &'a [[f32; 4]],
+}
+
+/// Build the joint matrix palette for a skin, where each matrix is the joint's world transform
+/// multiplied by its inverse bind matrix.
+///
+/// Missing inverse bind matrices are treated as identity, as per the spec.
+pub fn joint_matrices(joint_world_matrices: &[Mat4], inverse_bind_matrices: &[Mat4]) -> Vec<Mat4> {
+    joint_world_matrices
+        .iter()
+        .enumerate()
+        .map(|(i, world)| {
+            math::mul(
+                world,
+                inverse_bind_matrices.get(i).unwrap_or(&math::IDENTITY),
+            )
+        })
+        .collect()
+}
+
+/// Compute the weighted skinning matrix for each vertex.
+///
+/// Influences referencing joints outside of `joint_matrices` are ignored.
+pub fn skinning_matrices(
+    vertex_count: usize,
+    sets: &[InfluenceSet],
+    joint_matrices: &[Mat4],
+) -> Vec<Mat4> {
+    (0..vertex_count)
+        .map(|vertex| {
+            let mut matrix = [0.0; 16];
+
+            for set in sets {
+                let (joints, weights) = match (set.joints.get(vertex), set.weights.get(vertex)) {
+                    (Some(joints), Some(weights)) => (joints, weights),
+                    _ => continue,
+                };
+
+                for (&joint, &weight) in joints.iter().zip(weights) {
+                    if weight == 0.0 {
+                        continue;
+                    }
+
+                    if let Some(joint_matrix) = joint_matrices.get(joint as usize) {
+                        for i in 0..16 {
+                            matrix[i] += joint_matrix[i] * weight;
+                        }
+                    }
+                }
+            }
+
+            matrix
+        })
+        .collect()
+}
+
+/// Deform positions by the skin.
+pub fn skin_positions(
+    positions: &[[f32; 3]],
+    sets: &[InfluenceSet],
+    joint_matrices: &[Mat4],
+) -> Vec<[f32; 3]> {
+    skinning_matrices(positions.len(), sets, joint_matrices)
+        .iter()
+        .zip(positions)
+        .map(|(matrix, &position)| math::transform_point(matrix, position))
+        .collect()
+}
+
+/// Deform normals by the skin. The results are renormalized.
+pub fn skin_normals(
+    normals: &[[f32; 3]],
+    sets: &[InfluenceSet],
+    joint_matrices: &[Mat4],
+) -> Vec<[f32; 3]> {
+    skinning_matrices(normals.len(), sets, joint_matrices)
+        .iter()
+        .zip(normals)
+        .map(|(matrix, &normal)| math::normalize3(math::transform_normal(matrix, normal)))
+        .collect()
+}
+
+/// Clean up a `WEIGHTS_0` attribute so that each vertex's weights sum to 1, as quantized weights
+/// often don't.
+///
+/// NaN, infinite and negative weights are set to 0 first. Vertices without any positive weights
+/// are left as all zeroes. This only handles a single set of weights, as the spec requires the
+/// sum over all sets to be 1.
+pub fn normalize_weights(weights: &mut [[f32; 4]]) {
+    for vertex in weights {
+        for weight in vertex.iter_mut() {
+            if !weight.is_finite() || *weight < 0.0 {
+                *weight = 0.0;
+            }
+        }
+
+        let sum: f32 = vertex.iter().sum();
+
+        if sum > 0.0 {
+            for weight in vertex.iter_mut() {
+                *weight /= sum;
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+#[cfg(not(feature = "no_std"))]
+use std::path::{Component, Path, PathBuf};
+
+/// The contents of a `data:` uri.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUri<'a> {
+    /// The media type, e.g. `application/octet-stream` or `image/png`. Empty if not given.
+    pub mime_type: &'a str,
+    pub data: Vec<u8>,
+}
+
+/// Decode a base64 `data:` uri, as used for buffers and images embedded in a .gltf file.
+///
+/// Returns `None` if `uri` isn't a base64 data uri or the base64 is invalid.
+pub fn decode_data_uri(uri: &str) -> Option<DataUri<'_>> {
+    let (header, payload) = uri.strip_prefix("data:")?.split_once(',')?;
+
+    let mut parameters = header.split(';');
+    let mime_type = parameters.next().unwrap_or_default();
+
+    if !parameters.any(|parameter| parameter == "base64") {
+        return None;
+    }
+
+    Some(DataUri {
+        mime_type,
+        data: decode_base64(payload)?,
+    })
+}
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+
+    // A single leftover character can't hold a whole byte, so the data has been cut short.
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in input.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            // The url-safe alphabet is accepted as well.
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(output)
+}
+
+impl<E: crate::Extensions> crate::Buffer<E> {
+    /// The data of a buffer with a base64 `data:` uri.
+    pub fn embedded_data(&self) -> Option<Vec<u8>> {
+        self.uri
+            .as_deref()
+            .and_then(decode_data_uri)
+            .map(|data_uri| data_uri.data)
+    }
+}
+
+impl<E: crate::Extensions> crate::Image<E> {
+    /// The data and media type of an image with a base64 `data:` uri.
+    pub fn embedded_data(&self) -> Option<DataUri<'_>> {
+        self.uri.as_deref().and_then(decode_data_uri)
+    }
+}
+
+/// Why a uri couldn't be resolved to a file path by [`resolve_uri`].
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The uri has a scheme, such as `data:` or `https:`, so it isn't a relative path.
+    NotRelative,
+    /// The uri is an absolute path.
+    Absolute,
+    /// The uri has an invalid percent-encoded sequence, or decodes to invalid UTF-8.
+    InvalidEncoding,
+    /// The resolved path is outside of the root directory.
+    OutsideRoot,
+}
+
+#[cfg(not(feature = "no_std"))]
+impl core::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::NotRelative => "uri isn't a relative path",
+            Self::Absolute => "uri is an absolute path",
+            Self::InvalidEncoding => "uri has invalid percent-encoding",
+            Self::OutsideRoot => "uri resolves to a path outside of the root directory",
+        })
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl core::error::Error for ResolveError {}
+
+/// Resolve a relative uri against `base_dir`, the directory of the document that references it.
+///
+/// The uri is percent-decoded and any query or fragment is dropped. The resolved path has to be
+/// within `root`, which makes this suitable for loading untrusted files. Paths are normalized
+/// without touching the filesystem, so symlinks inside `root` aren't followed or checked.
+#[cfg(not(feature = "no_std"))]
+pub fn resolve_uri(root: &Path, base_dir: &Path, uri: &str) -> Result<PathBuf, ResolveError> {
+    let uri = uri.split(['?', '#']).next().unwrap_or_default();
+
+    // A scheme is a `:` before the first `/`, which also catches Windows drive letters.
+    if uri
+        .split('/')
+        .next()
+        .is_some_and(|first| first.contains(':'))
+    {
+        return Err(ResolveError::NotRelative);
+    }
+
+    let path = percent_decode(uri).ok_or(ResolveError::InvalidEncoding)?;
+
+    if path.contains('\0') {
+        return Err(ResolveError::InvalidEncoding);
+    }
+
+    if path.starts_with(['/', '\\']) {
+        return Err(ResolveError::Absolute);
+    }
+
+    let root = normalize(root).ok_or(ResolveError::OutsideRoot)?;
+    let resolved = normalize(&base_dir.join(path)).ok_or(ResolveError::OutsideRoot)?;
+
+    if !resolved.starts_with(&root) {
+        return Err(ResolveError::OutsideRoot);
+    }
+
+    Ok(resolved)
+}
+
+// Returns `None` if the path goes above its start with `..`.
+#[cfg(not(feature = "no_std"))]
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                _ => return None,
+            },
+            other => normalized.push(other),
+        }
+    }
+
+    Some(normalized)
+}
+
+#[cfg(not(feature = "no_std"))]
+fn percent_decode(input: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut input = input.bytes();
+
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let high = (input.next()? as char).to_digit(16)?;
+            let low = (input.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
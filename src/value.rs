@@ -0,0 +1,195 @@
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::HashMap;
+use core::str::Chars;
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok, SerJson, SerJsonState};
+
+/// A dynamically-typed JSON value.
+///
+/// It can be used for any of the [`Extensions`](crate::Extensions) associated types, e.g. to
+/// read the custom properties that Blender writes to `extras`:
+///
+/// ```
+/// let json = r#"{"asset": {"version": "2.0"}, "nodes": [{"extras": {"speed": 2.5}}]}"#;
+/// let gltf = goth_gltf::Gltf::<goth_gltf::AnyExtensions>::from_json_string(json).unwrap();
+///
+/// let speed = gltf.nodes[0].extras.get("speed").and_then(|speed| speed.as_f64());
+/// assert_eq!(speed, Some(2.5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum JsonValue {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(JsonMap),
+}
+
+/// A JSON object, such as the `extensions` of an object in [`AnyExtensions`](crate::AnyExtensions).
+pub type JsonMap = HashMap<String, JsonValue>;
+
+impl JsonValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value as an index or count, if it's a non-negative whole number.
+    pub fn as_usize(&self) -> Option<usize> {
+        match *self {
+            Self::Number(value) if value >= 0.0 && value % 1.0 == 0.0 => Some(value as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&JsonMap> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Look up a member of an object. Returns `None` for other types of value.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object().and_then(|members| members.get(key))
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<Vec<JsonValue>> for JsonValue {
+    fn from(values: Vec<JsonValue>) -> Self {
+        Self::Array(values)
+    }
+}
+
+impl From<JsonMap> for JsonValue {
+    fn from(members: JsonMap) -> Self {
+        Self::Object(members)
+    }
+}
+
+impl DeJson for JsonValue {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Self, DeJsonErr> {
+        Self::de_json_nested(state, input, 0)
+    }
+}
+
+impl JsonValue {
+    // Limited to the same depth as raw JSON, so that deeply nested extras can't overflow the
+    // stack.
+    fn de_json_nested(
+        state: &mut DeJsonState,
+        input: &mut Chars,
+        depth: usize,
+    ) -> Result<Self, DeJsonErr> {
+        let max_depth = crate::json::MAX_DEPTH;
+
+        if matches!(state.tok, DeJsonTok::BlockOpen | DeJsonTok::CurlyOpen) && depth == max_depth {
+            return Err(crate::limits::exceeded(state, "nesting depth", max_depth));
+        }
+
+        let value = match state.tok {
+            DeJsonTok::Null => Self::Null,
+            DeJsonTok::Bool(value) => Self::Bool(value),
+            DeJsonTok::U64(value) => Self::Number(value as f64),
+            DeJsonTok::I64(value) => Self::Number(value as f64),
+            DeJsonTok::F64(value) => Self::Number(value),
+            DeJsonTok::Str => Self::String(state.as_string()?),
+            DeJsonTok::BlockOpen => {
+                state.next_tok(input)?;
+                let mut values = Vec::new();
+
+                while state.tok != DeJsonTok::BlockClose {
+                    values.push(Self::de_json_nested(state, input, depth + 1)?);
+                    state.eat_comma_block(input)?;
+                }
+
+                Self::Array(values)
+            }
+            DeJsonTok::CurlyOpen => {
+                state.next_tok(input)?;
+                let mut members = JsonMap::new();
+
+                while state.tok != DeJsonTok::CurlyClose {
+                    let key = state.as_string()?;
+                    state.next_colon(input)?;
+                    members.insert(key, Self::de_json_nested(state, input, depth + 1)?);
+                    state.eat_comma_curly(input)?;
+                }
+
+                Self::Object(members)
+            }
+            _ => return Err(state.err_token("value")),
+        };
+
+        state.next_tok(input)?;
+        Ok(value)
+    }
+}
+
+impl SerJson for JsonValue {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        match self {
+            Self::Null => s.out.push_str("null"),
+            Self::Bool(value) => value.ser_json(d, s),
+            // JSON has no representation for these.
+            Self::Number(value) if !value.is_finite() => s.out.push_str("null"),
+            Self::Number(value) => value.ser_json(d, s),
+            Self::String(value) => value.ser_json(d, s),
+            Self::Array(values) => values.ser_json(d, s),
+            Self::Object(members) => members.ser_json(d, s),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    Accessor, AccessorType, ComponentType, Extensions, FilterMode, MinFilter, PrimitiveMode,
+    Sampler, SamplerWrap,
+};
+use wgpu_types::{
+    AddressMode, MipmapFilterMode, PrimitiveTopology, SamplerDescriptor, VertexFormat,
+};
+
+/// A combination of component type, accessor type and normalization that doesn't have a
+/// matching [`VertexFormat`], such as a `VEC3` of bytes or a matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedVertexFormat {
+    pub component_type: ComponentType,
+    pub components: usize,
+    pub normalized: bool,
+}
+
+impl core::fmt::Display for UnsupportedVertexFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "no vertex format for {} components of {:?}{}",
+            self.components,
+            self.component_type,
+            if self.normalized { " (normalized)" } else { "" }
+        )
+    }
+}
+
+impl core::error::Error for UnsupportedVertexFormat {}
+
+/// The vertex format to upload accessor data with as it's stored.
+///
+/// Integer components that aren't normalized map to the `Uint` and `Sint` formats, which shaders
+/// read as integers rather than floats. wgpu doesn't have any 3-component 8 or 16-bit formats,
+/// so those need to be padded or converted first.
+pub fn vertex_format(
+    component_type: ComponentType,
+    accessor_type: &AccessorType,
+    normalized: bool,
+) -> Result<VertexFormat, UnsupportedVertexFormat> {
+    use VertexFormat::*;
+
+    let components = accessor_type.num_components();
+    let is_matrix = matches!(
+        accessor_type,
+        AccessorType::Mat2 | AccessorType::Mat3 | AccessorType::Mat4
+    );
+
+    let formats: [Option<VertexFormat>; 4] = match (component_type, normalized) {
+        (ComponentType::UnsignedByte, false) => [Some(Uint8), Some(Uint8x2), None, Some(Uint8x4)],
+        (ComponentType::UnsignedByte, true) => [Some(Unorm8), Some(Unorm8x2), None, Some(Unorm8x4)],
+        (ComponentType::Byte, false) => [Some(Sint8), Some(Sint8x2), None, Some(Sint8x4)],
+        (ComponentType::Byte, true) => [Some(Snorm8), Some(Snorm8x2), None, Some(Snorm8x4)],
+        (ComponentType::UnsignedShort, false) => {
+            [Some(Uint16), Some(Uint16x2), None, Some(Uint16x4)]
+        }
+        (ComponentType::UnsignedShort, true) => {
+            [Some(Unorm16), Some(Unorm16x2), None, Some(Unorm16x4)]
+        }
+        (ComponentType::Short, false) => [Some(Sint16), Some(Sint16x2), None, Some(Sint16x4)],
+        (ComponentType::Short, true) => [Some(Snorm16), Some(Snorm16x2), None, Some(Snorm16x4)],
+        (ComponentType::UnsignedInt, false) => {
+            [Some(Uint32), Some(Uint32x2), Some(Uint32x3), Some(Uint32x4)]
+        }
+        (ComponentType::Float, false) => [
+            Some(Float32),
+            Some(Float32x2),
+            Some(Float32x3),
+            Some(Float32x4),
+        ],
+        (ComponentType::UnsignedInt | ComponentType::Float, true)
+        | (ComponentType::Unknown(_), _) => [None; 4],
+    };
+
+    formats
+        .get(components - 1)
+        .copied()
+        .flatten()
+        .filter(|_| !is_matrix)
+        .ok_or(UnsupportedVertexFormat {
+            component_type,
+            components,
+            normalized,
+        })
+}
+
+impl<E: Extensions> Accessor<E> {
+    /// The vertex format to upload this accessor's data with. See [`vertex_format`].
+    pub fn wgpu_vertex_format(&self) -> Result<VertexFormat, UnsupportedVertexFormat> {
+        vertex_format(self.component_type, &self.accessor_type, self.normalized)
+    }
+}
+
+impl PrimitiveMode {
+    /// The matching topology, if there is one. Line loops and triangle fans need converting with
+    /// [`to_list_indices`](crate::geometry::to_list_indices) first.
+    pub fn wgpu_topology(self) -> Option<PrimitiveTopology> {
+        match self {
+            Self::Points => Some(PrimitiveTopology::PointList),
+            Self::Lines => Some(PrimitiveTopology::LineList),
+            Self::LineStrip => Some(PrimitiveTopology::LineStrip),
+            Self::Triangles => Some(PrimitiveTopology::TriangleList),
+            Self::TriangleStrip => Some(PrimitiveTopology::TriangleStrip),
+            Self::LineLoop | Self::TriangleFan | Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// Unknown filters use linear filtering.
+impl FilterMode {
+    pub fn wgpu(&self) -> wgpu_types::FilterMode {
+        match self {
+            Self::Nearest => wgpu_types::FilterMode::Nearest,
+            Self::Linear | Self::Unknown(_) => wgpu_types::FilterMode::Linear,
+        }
+    }
+
+    pub fn wgpu_mipmap(&self) -> MipmapFilterMode {
+        match self {
+            Self::Nearest => MipmapFilterMode::Nearest,
+            Self::Linear | Self::Unknown(_) => MipmapFilterMode::Linear,
+        }
+    }
+}
+
+impl SamplerWrap {
+    /// Unknown wrapping modes repeat, as is the default.
+    pub fn wgpu(&self) -> AddressMode {
+        match self {
+            Self::ClampToEdge => AddressMode::ClampToEdge,
+            Self::MirroredRepeat => AddressMode::MirrorRepeat,
+            Self::Repeat | Self::Unknown(_) => AddressMode::Repeat,
+        }
+    }
+}
+
+impl<E: Extensions> Sampler<E> {
+    /// A sampler descriptor with the sampler's filtering and wrapping.
+    ///
+    /// Filters that aren't given default to linear filtering with linear mipmaps. When the
+    /// minification filter doesn't use mipmaps, only the base level is sampled.
+    pub fn wgpu_descriptor<'a>(
+        &self,
+        label: Option<&'a str>,
+    ) -> SamplerDescriptor<Option<&'a str>> {
+        let (min_filter, mipmap_filter) = match &self.min_filter {
+            Some(MinFilter { mode, mipmap }) => {
+                (mode.wgpu(), mipmap.as_ref().map(FilterMode::wgpu_mipmap))
+            }
+            None => (
+                wgpu_types::FilterMode::Linear,
+                Some(MipmapFilterMode::Linear),
+            ),
+        };
+
+        let mut descriptor = SamplerDescriptor {
+            label,
+            address_mode_u: self.wrap_s.wgpu(),
+            address_mode_v: self.wrap_t.wgpu(),
+            mag_filter: self
+                .mag_filter
+                .as_ref()
+                .map_or(wgpu_types::FilterMode::Linear, FilterMode::wgpu),
+            min_filter,
+            mipmap_filter: mipmap_filter.unwrap_or(MipmapFilterMode::Nearest),
+            ..Default::default()
+        };
+
+        if mipmap_filter.is_none() {
+            descriptor.lod_max_clamp = 0.0;
+        }
+
+        descriptor
+    }
+}
@@ -0,0 +1,375 @@
+use crate::json::{escape, RawValue};
+#[cfg(feature = "no_std")]
+use crate::prelude::*;
+use crate::{Extensions, GlbError, Gltf};
+use nanoserde::SerJson;
+
+// Arrays that are parsed with a default, so that they're written out as `[]` when empty. The spec
+// requires arrays to be non-empty, so these are left out instead.
+const DEFAULTED_ARRAYS: &[&str] = &[
+    "accessors",
+    "animations",
+    "bufferViews",
+    "buffers",
+    "cameras",
+    "channels",
+    "children",
+    "extensionsRequired",
+    "extensionsUsed",
+    "images",
+    "joints",
+    "materials",
+    "meshes",
+    "nodes",
+    "primitives",
+    "samplers",
+    "scenes",
+    "skins",
+    "textures",
+];
+
+// The derived serialization writes out defaulted fields as-is, e.g. `"children":[]`, or
+// `"extensions":null` for `()` extensions. Only the core properties are pruned: extension data
+// and extras can be anything, including raw JSON kept from the source, so apart from empty
+// `extensions` and `extras` objects (such as those of a texture info within an extension) they're
+// written out unchanged.
+fn prune(value: &mut RawValue, core: bool) {
+    match value {
+        RawValue::Array(values) => values.iter_mut().for_each(|value| prune(value, core)),
+        RawValue::Object(members) => {
+            members.retain_mut(|(key, value)| {
+                let empty = match value {
+                    RawValue::Null => true,
+                    RawValue::Object(members) => members.is_empty(),
+                    _ => false,
+                };
+
+                match key.as_str() {
+                    "extras" => !empty,
+                    "extensions" => {
+                        if let RawValue::Object(extensions) = value {
+                            for (_, extension) in extensions {
+                                prune(extension, false);
+                            }
+                        }
+
+                        !empty
+                    }
+                    key => {
+                        prune(value, core);
+
+                        !(core
+                            && DEFAULTED_ARRAYS.contains(&key)
+                            && matches!(value, RawValue::Array(values) if values.is_empty()))
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Serialize the document as `.gltf` JSON.
+    ///
+    /// Empty core arrays and empty `extensions` and `extras` objects are left out, and the members in
+    /// [`unknown_json`](Self::unknown_json) are added back unless the document now has a value
+    /// for them. `extensions_used` and `extensions_required` are written as they are, so they
+    /// need to be kept up to date when adding or removing extension data.
+    pub fn to_json_string(&self) -> String {
+        let json = self.serialize_json();
+
+        let mut value = match RawValue::parse(&json) {
+            Some(value) => value,
+            // Only possible if an extension type has a broken `SerJson` implementation, or
+            // extension data is nested more than `json::MAX_DEPTH` deep.
+            None => return json,
+        };
+
+        prune(&mut value, true);
+
+        if let Some(unknown) = &self.unknown_json {
+            unknown.restore(&mut value);
+        }
+
+        let mut output = String::with_capacity(json.len());
+        value.write(&mut output);
+        output
+    }
+
+    /// Serialize the document as a .glb file, the inverse of [`Gltf::from_bytes`].
+    ///
+    /// `binary_chunk` is the data of the buffer without a uri, which has to be the first one.
+    /// Both chunks are padded to 4 bytes.
+    pub fn to_glb(&self, binary_chunk: Option<&[u8]>) -> Result<Vec<u8>, GlbError> {
+        if binary_chunk.is_some()
+            && self
+                .buffers
+                .first()
+                .is_none_or(|buffer| buffer.uri.is_some())
+        {
+            return Err(GlbError::UnreferencedBinaryChunk);
+        }
+
+        let mut json = self.to_json_string().into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+
+        let binary_length = binary_chunk.map_or(0, |bytes| 8 + bytes.len().next_multiple_of(4));
+        let length = 12 + 8 + json.len() + binary_length;
+        // Every other length is smaller than the total.
+        let header_length = u32::try_from(length).map_err(|_| GlbError::TooLarge(length))?;
+
+        let mut glb = Vec::with_capacity(length);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2_u32.to_le_bytes());
+        glb.extend_from_slice(&header_length.to_le_bytes());
+
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+
+        if let Some(bytes) = binary_chunk {
+            let padded_length = bytes.len().next_multiple_of(4);
+            glb.extend_from_slice(&(padded_length as u32).to_le_bytes());
+            glb.extend_from_slice(b"BIN\0");
+            glb.extend_from_slice(bytes);
+            glb.resize(length, 0);
+        }
+
+        Ok(glb)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl PathSegment {
+    pub(crate) fn key(key: &str) -> Self {
+        Self::Key(key.to_string())
+    }
+}
+
+/// The JSON members of a source document that a [`Gltf`] doesn't keep, such as properties from
+/// vendors, extensions that aren't parsed, or names when the `names` feature is disabled.
+///
+/// Kept in [`Gltf::unknown_json`] by [`Gltf::keep_unknown_json`], and written back out by
+/// [`Gltf::to_json_string`]. Members are stored by the position of their object in the document.
+/// The [`edit`](crate::edit) functions and [`Gltf::sanitize`] update the positions when they
+/// remove objects, but other changes that remove or reorder objects need to clear it first.
+#[derive(Debug, Clone, Default)]
+pub struct UnknownJson {
+    objects: Vec<UnknownMembers>,
+}
+
+#[derive(Debug, Clone)]
+struct UnknownMembers {
+    path: Vec<PathSegment>,
+    members: Vec<(String, RawValue)>,
+}
+
+impl UnknownJson {
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The JSON pointer of each object with unknown members, along with their names.
+    pub fn members(&self) -> impl Iterator<Item = (String, &str)> {
+        self.objects.iter().flat_map(|object| {
+            let pointer: String = object
+                .path
+                .iter()
+                .map(|segment| match segment {
+                    PathSegment::Key(key) => {
+                        format!("/{}", key.replace('~', "~0").replace('/', "~1"))
+                    }
+                    PathSegment::Index(index) => format!("/{}", index),
+                })
+                .collect();
+
+            object
+                .members
+                .iter()
+                .map(move |(key, _)| (pointer.clone(), key.as_str()))
+        })
+    }
+
+    // Keep the members with their objects when the element at `index` of the array at `array`
+    // is removed, dropping those of the element itself.
+    pub(crate) fn remove_element(&mut self, array: &[PathSegment], index: usize) {
+        let in_array =
+            |path: &[PathSegment]| path.len() > array.len() && path[..array.len()] == *array;
+
+        self.objects.retain_mut(|object| {
+            if !in_array(&object.path) {
+                return true;
+            }
+
+            match &mut object.path[array.len()] {
+                PathSegment::Index(element) if *element == index => false,
+                PathSegment::Index(element) => {
+                    if *element > index {
+                        *element -= 1;
+                    }
+                    true
+                }
+                PathSegment::Key(_) => true,
+            }
+        });
+    }
+
+    fn collect(&mut self, path: &mut Vec<PathSegment>, source: &RawValue, known: &RawValue) {
+        match (source, known) {
+            (RawValue::Object(source), RawValue::Object(known)) => {
+                let mut unknown = Vec::new();
+
+                for (key, value) in source {
+                    match known.iter().find(|(known_key, _)| known_key == key) {
+                        Some((_, known_value)) => {
+                            path.push(PathSegment::Key(key.clone()));
+                            self.collect(path, value, known_value);
+                            path.pop();
+                        }
+                        None => unknown.push((key.clone(), value.clone())),
+                    }
+                }
+
+                if !unknown.is_empty() {
+                    self.objects.push(UnknownMembers {
+                        path: path.clone(),
+                        members: unknown,
+                    });
+                }
+            }
+            (RawValue::Array(source), RawValue::Array(known)) => {
+                for (index, (value, known_value)) in source.iter().zip(known).enumerate() {
+                    path.push(PathSegment::Index(index));
+                    self.collect(path, value, known_value);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn restore(&self, value: &mut RawValue) {
+        'objects: for UnknownMembers { path, members } in &self.objects {
+            let mut target = &mut *value;
+
+            for segment in path {
+                target = match (target, segment) {
+                    (RawValue::Object(object), PathSegment::Key(key)) => {
+                        // Objects that only had unknown members were pruned.
+                        let position = match object.iter().position(|(other, _)| other == key) {
+                            Some(position) => position,
+                            None => {
+                                object.push((key.clone(), RawValue::Object(Vec::new())));
+                                object.len() - 1
+                            }
+                        };
+
+                        &mut object[position].1
+                    }
+                    (RawValue::Array(array), &PathSegment::Index(index)) => {
+                        match array.get_mut(index) {
+                            Some(target) => target,
+                            None => continue 'objects,
+                        }
+                    }
+                    _ => continue 'objects,
+                };
+            }
+
+            if let RawValue::Object(object) = target {
+                for (key, member) in members {
+                    if !object.iter().any(|(other, _)| other == key) {
+                        object.push((key.clone(), member.clone()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: Extensions> Gltf<E> {
+    /// Keep the members of `json`, the source this document was parsed from, that the document
+    /// doesn't model in [`unknown_json`](Self::unknown_json), so that
+    /// [`to_json_string`](Self::to_json_string) writes them back out.
+    ///
+    /// This needs to be called before the document is modified.
+    pub fn keep_unknown_json(&mut self, json: &str) {
+        let mut unknown = UnknownJson::default();
+
+        if let (Some(source), Some(known)) = (
+            RawValue::parse(json),
+            RawValue::parse(&self.serialize_json()),
+        ) {
+            unknown.collect(&mut Vec::new(), &source, &known);
+        }
+
+        self.unknown_json = Some(unknown);
+    }
+}
+
+/// A .glb file split up by [`split_glb`].
+#[derive(Debug, Clone)]
+pub struct SplitGlb<'a> {
+    /// The .gltf JSON.
+    pub json: String,
+    pub binary_chunk: Option<&'a [u8]>,
+}
+
+/// Split a .glb file into .gltf JSON and the binary chunk, so that they can be stored or served
+/// separately. The first buffer is pointed at `binary_uri`, which is where the binary chunk
+/// needs to be written.
+///
+/// The JSON isn't parsed into a [`Gltf`], so everything in it is kept, including extensions this
+/// crate doesn't know about. Input that isn't a .glb file is returned as-is.
+pub fn split_glb<'a>(bytes: &'a [u8], binary_uri: &str) -> Result<SplitGlb<'a>, GlbError> {
+    let (json_bytes, binary_chunk) = crate::glb::split_chunks(bytes)?;
+    let json = crate::json_str(json_bytes)?;
+
+    let binary_chunk = match binary_chunk {
+        Some(binary_chunk) => binary_chunk,
+        None => {
+            return Ok(SplitGlb {
+                json: json.to_string(),
+                binary_chunk: None,
+            })
+        }
+    };
+
+    let invalid = || GlbError::Json(nanoserde::DeJsonState::default().err_parse("glb json"));
+
+    let mut value = RawValue::parse(json).ok_or_else(invalid)?;
+
+    let buffer = match &mut value {
+        RawValue::Object(members) => members
+            .iter_mut()
+            .find(|(key, _)| key == "buffers")
+            .and_then(|(_, buffers)| match buffers {
+                RawValue::Array(buffers) => buffers.first_mut(),
+                _ => None,
+            }),
+        _ => return Err(invalid()),
+    };
+
+    if let Some(RawValue::Object(members)) = buffer {
+        let uri = RawValue::String(escape(binary_uri));
+
+        match members.iter_mut().find(|(key, _)| key == "uri") {
+            Some((_, value)) => *value = uri,
+            None => members.insert(0, ("uri".to_string(), uri)),
+        }
+    }
+
+    let mut output = String::with_capacity(json.len() + binary_uri.len() + 8);
+    value.write(&mut output);
+
+    Ok(SplitGlb {
+        json: output,
+        binary_chunk: Some(binary_chunk),
+    })
+}
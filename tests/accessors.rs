@@ -0,0 +1,468 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::primitive_reader::{
+    dequantization_matrix, dequantization_scale, dequantize, dequantized_bounds, iter_accessor,
+    read_accessor, view_accessor, Error, PrimitiveReader,
+};
+use goth_gltf::Gltf;
+use std::collections::HashMap;
+
+// A document with a single buffer view holding `bytes`, and an accessor into it.
+fn accessor(accessor: &str, bytes: Vec<u8>) -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let json = format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": {0}}}],
+            "bufferViews": [{{"buffer": 0, "byteLength": {0}}}],
+            "accessors": [{1}]
+        }}"#,
+        bytes.len(),
+        accessor
+    );
+
+    (
+        Gltf::from_json_string(&json).unwrap(),
+        HashMap::from([(0, bytes)]),
+    )
+}
+
+fn floats(values: &[f32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+#[test]
+fn elements_are_read_as_the_requested_type() {
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC2"}"#,
+        floats(&[1.0, 2.0, 3.5, 4.0]),
+    );
+    let accessor = &gltf.accessors[0];
+
+    let values: Vec<[f32; 2]> = read_accessor(&data, &gltf, accessor).unwrap();
+    assert_eq!(values, [[1.0, 2.0], [3.5, 4.0]]);
+
+    let values: Vec<[u32; 2]> = read_accessor(&data, &gltf, accessor).unwrap();
+    assert_eq!(values, [[1, 2], [3, 4]]);
+}
+
+#[test]
+fn scalars_are_read_as_single_components() {
+    let bytes = [1u16, 2, 65535].map(u16::to_le_bytes).concat();
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5123, "count": 3, "type": "SCALAR"}"#,
+        bytes,
+    );
+    let accessor = &gltf.accessors[0];
+
+    assert_eq!(
+        read_accessor::<u32, _>(&data, &gltf, accessor).unwrap(),
+        [1, 2, 65535]
+    );
+    assert_eq!(
+        read_accessor::<u16, _>(&data, &gltf, accessor).unwrap(),
+        [1, 2, 65535]
+    );
+    assert_eq!(
+        read_accessor::<f32, _>(&data, &gltf, accessor).unwrap(),
+        [1.0, 2.0, 65535.0]
+    );
+}
+
+#[test]
+fn normalized_integers_are_converted_to_floats() {
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5121, "normalized": true, "count": 1, "type": "VEC4"}"#,
+        vec![0, 51, 255, 255],
+    );
+
+    let values: Vec<[f32; 4]> = read_accessor(&data, &gltf, &gltf.accessors[0]).unwrap();
+    assert_eq!(values, [[0.0, 0.2, 1.0, 1.0]]);
+
+    // Integer outputs get the stored values.
+    let values: Vec<[u16; 4]> = read_accessor(&data, &gltf, &gltf.accessors[0]).unwrap();
+    assert_eq!(values, [[0, 51, 255, 255]]);
+}
+
+#[test]
+fn matrices_are_read_column_major() {
+    let identity = [
+        1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 5.0, 6.0, 7.0, 1.0,
+    ];
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5126, "count": 1, "type": "MAT4"}"#,
+        floats(&identity),
+    );
+
+    let values: Vec<[f32; 16]> = read_accessor(&data, &gltf, &gltf.accessors[0]).unwrap();
+    assert_eq!(values, [identity]);
+}
+
+#[test]
+fn the_component_count_has_to_match() {
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3"}"#,
+        floats(&[1.0, 2.0, 3.0]),
+    );
+
+    assert!(matches!(
+        read_accessor::<[f32; 4], _>(&data, &gltf, &gltf.accessors[0]),
+        Err(Error::ComponentCountMismatch(4, 3))
+    ));
+    assert!(matches!(
+        read_accessor::<f32, _>(&data, &gltf, &gltf.accessors[0]),
+        Err(Error::ComponentCountMismatch(1, 3))
+    ));
+}
+
+// A primitive with each attribute in its own buffer view.
+fn primitive(attributes: &[(&str, &str, Vec<u8>)]) -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut semantics = Vec::new();
+    let mut data = HashMap::new();
+
+    for (index, (semantic, accessor, bytes)) in attributes.iter().enumerate() {
+        buffer_views.push(format!(r#"{{"buffer": 0, "byteLength": {}}}"#, bytes.len()));
+        accessors.push(format!(r#"{{"bufferView": {}, {}}}"#, index, accessor));
+        semantics.push(format!(r#""{}": {}"#, semantic, index));
+        data.insert(index, bytes.clone());
+    }
+
+    let json = format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": 0}}],
+            "bufferViews": [{}],
+            "accessors": [{}],
+            "meshes": [{{"primitives": [{{"attributes": {{{}}}}}]}}]
+        }}"#,
+        buffer_views.join(","),
+        accessors.join(","),
+        semantics.join(",")
+    );
+
+    (Gltf::from_json_string(&json).unwrap(), data)
+}
+
+#[test]
+fn quantized_attributes_are_read() {
+    let shorts = |values: &[i16]| {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    };
+
+    let (mut gltf, data) = primitive(&[
+        // Unnormalized unsigned shorts and normalized bytes, padded to 8 and 4 bytes per element.
+        (
+            "POSITION",
+            r#""componentType": 5123, "count": 2, "type": "VEC3""#,
+            shorts(&[1, 2, 3, 0, 4, 5, 6, 0]),
+        ),
+        (
+            "NORMAL",
+            r#""componentType": 5120, "normalized": true, "count": 2, "type": "VEC3""#,
+            vec![127, 0, 0, 0, 0, 129, 0, 0],
+        ),
+        (
+            "TEXCOORD_0",
+            r#""componentType": 5121, "normalized": true, "count": 2, "type": "VEC2""#,
+            vec![0, 255, 51, 102],
+        ),
+        (
+            "TEXCOORD_1",
+            r#""componentType": 5123, "normalized": true, "count": 2, "type": "VEC2""#,
+            shorts(&[0, -1, -1, 0]),
+        ),
+        (
+            "WEIGHTS_0",
+            r#""componentType": 5123, "normalized": true, "count": 2, "type": "VEC4""#,
+            shorts(&[-1, 0, 0, 0, 0, 0, 0, -1]),
+        ),
+    ]);
+    gltf.buffer_views[0].byte_stride = Some(8);
+    gltf.buffer_views[1].byte_stride = Some(4);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    assert_eq!(
+        &reader.read_positions().unwrap().unwrap()[..],
+        &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+    );
+    assert_eq!(
+        &reader.read_normals().unwrap().unwrap()[..],
+        &[[1.0, 0.0, 0.0], [0.0, -1.0, 0.0]]
+    );
+    assert_eq!(
+        &reader.read_uvs().unwrap().unwrap()[..],
+        &[[0.0, 1.0], [0.2, 0.4]]
+    );
+    assert_eq!(
+        &reader.read_second_uvs().unwrap().unwrap()[..],
+        &[[0.0, 1.0], [1.0, 0.0]]
+    );
+    assert_eq!(
+        &reader.read_weights(0).unwrap().unwrap()[..],
+        &[[1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0]]
+    );
+}
+
+#[test]
+fn floats_are_read_with_any_stride() {
+    // Three floats per element, padded to 20 bytes.
+    let mut bytes = floats(&[1.0, 2.0, 3.0, 0.0, 0.0]);
+    bytes.extend(floats(&[4.0, 5.0, 6.0]));
+
+    let (mut gltf, data) = primitive(&[(
+        "POSITION",
+        r#""componentType": 5126, "count": 2, "type": "VEC3""#,
+        bytes,
+    )]);
+    gltf.buffer_views[0].byte_stride = Some(20);
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+    assert_eq!(
+        &reader.read_positions().unwrap().unwrap()[..],
+        &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+    );
+}
+
+#[test]
+fn combinations_the_spec_disallows_are_an_error() {
+    let (gltf, data) = primitive(&[(
+        "TEXCOORD_0",
+        r#""componentType": 5125, "count": 1, "type": "VEC2""#,
+        [1u32, 2].map(u32::to_le_bytes).concat(),
+    )]);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    assert!(matches!(
+        reader.read_uvs(),
+        Err(Error::UnsupportedCombination(..))
+    ));
+}
+
+#[test]
+fn accessors_can_share_an_interleaved_buffer_view() {
+    // Positions and UVs interleaved with a stride of 20 bytes. The view ends exactly where the
+    // last UV does.
+    let mut bytes = floats(&[1.0, 2.0, 3.0, 0.25, 0.5]);
+    bytes.extend(floats(&[4.0, 5.0, 6.0, 0.75, 1.0]));
+
+    let json = format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": {0}}}],
+            "bufferViews": [{{"buffer": 0, "byteLength": {0}, "byteStride": 20}}],
+            "accessors": [
+                {{"bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3"}},
+                {{"bufferView": 0, "byteOffset": 12, "componentType": 5126, "count": 2, "type": "VEC2"}}
+            ],
+            "meshes": [{{"primitives": [{{"attributes": {{"POSITION": 0, "TEXCOORD_0": 1}}}}]}}]
+        }}"#,
+        bytes.len()
+    );
+    let gltf = Gltf::<Extensions>::from_json_string(&json).unwrap();
+    let mut data = HashMap::from([(0, bytes)]);
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+    assert_eq!(
+        &reader.read_positions().unwrap().unwrap()[..],
+        &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+    );
+    assert_eq!(
+        &reader.read_uvs().unwrap().unwrap()[..],
+        &[[0.25, 0.5], [0.75, 1.0]]
+    );
+
+    // One byte less and the last UV no longer fits, while the positions still do.
+    data.get_mut(&0).unwrap().pop();
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+    assert!(reader.read_positions().is_ok());
+    assert!(matches!(
+        reader.read_uvs(),
+        Err(Error::AccessorOutOfBounds(40, 39))
+    ));
+}
+
+#[test]
+fn misaligned_data_is_read() {
+    // Everything starts one byte into the buffer view, so none of it can be cast in place.
+    let mut bytes = vec![0];
+    bytes.extend(floats(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    bytes.extend(floats(&[0.5, 0.25, 0.125, 0.125]));
+    bytes.extend([0u32, 1, 2].map(u32::to_le_bytes).concat());
+
+    let json = format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": {0}}}],
+            "bufferViews": [{{"buffer": 0, "byteLength": {0}}}],
+            "accessors": [
+                {{"bufferView": 0, "byteOffset": 1, "componentType": 5126, "count": 2, "type": "VEC3"}},
+                {{"bufferView": 0, "byteOffset": 25, "componentType": 5126, "count": 1, "type": "VEC4"}},
+                {{"bufferView": 0, "byteOffset": 41, "componentType": 5125, "count": 3, "type": "SCALAR"}}
+            ],
+            "meshes": [{{"primitives": [{{"attributes": {{"POSITION": 0, "WEIGHTS_0": 1}}, "indices": 2}}]}}]
+        }}"#,
+        bytes.len()
+    );
+    let gltf = Gltf::<Extensions>::from_json_string(&json).unwrap();
+    let data = HashMap::from([(0, bytes)]);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    assert_eq!(
+        &reader.read_positions().unwrap().unwrap()[..],
+        &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+    );
+    assert_eq!(
+        &reader.read_weights(0).unwrap().unwrap()[..],
+        &[[0.5, 0.25, 0.125, 0.125]]
+    );
+    assert_eq!(
+        &reader.read_indices().unwrap().unwrap().into_u32()[..],
+        &[0, 1, 2]
+    );
+
+    let values: Vec<[f32; 4]> = read_accessor(&data, &gltf, &gltf.accessors[1]).unwrap();
+    assert_eq!(values, [[0.5, 0.25, 0.125, 0.125]]);
+}
+
+#[test]
+fn iterators_decode_the_same_values_as_the_readers() {
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC2"}"#,
+        vec![0, 51, 255, 102],
+    );
+    let accessor = &gltf.accessors[0];
+
+    let mut values = iter_accessor::<[f32; 2], _>(&data, &gltf, accessor).unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values.next(), Some([0.0, 0.2]));
+    assert_eq!(values.len(), 1);
+    assert_eq!(values.next(), Some([1.0, 0.4]));
+    assert_eq!(values.next(), None);
+
+    assert!(matches!(
+        iter_accessor::<[f32; 3], _>(&data, &gltf, accessor),
+        Err(Error::ComponentCountMismatch(3, 2))
+    ));
+
+    // Strided and quantized attributes.
+    let (mut gltf, data) = primitive(&[(
+        "POSITION",
+        r#""componentType": 5123, "count": 2, "type": "VEC3""#,
+        [1u16, 2, 3, 0, 4, 5, 6, 0].map(u16::to_le_bytes).concat(),
+    )]);
+    gltf.buffer_views[0].byte_stride = Some(8);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    assert_eq!(
+        reader
+            .iter_positions()
+            .unwrap()
+            .unwrap()
+            .collect::<Vec<_>>(),
+        reader.read_positions().unwrap().unwrap().into_owned()
+    );
+    assert!(reader.iter_normals().unwrap().is_none());
+}
+
+#[test]
+fn quantized_values_are_dequantized_within_their_bounds() {
+    // Normalized shorts, where -32768 is clamped to -1 and the bounds are stored unnormalized.
+    let (gltf, data) = accessor(
+        r#"{
+            "bufferView": 0, "componentType": 5122, "normalized": true, "count": 2, "type": "VEC2",
+            "min": [-32767, 0], "max": [32767, 16384]
+        }"#,
+        [-32768i16, 0, 32767, 32767].map(i16::to_le_bytes).concat(),
+    );
+    let accessor = &gltf.accessors[0];
+
+    let (min, max) = dequantized_bounds(accessor).unwrap();
+    assert_eq!(min, [-1.0, 0.0]);
+    assert_eq!(max, [1.0, 16384.0 / 32767.0]);
+
+    let values: Vec<[f32; 2]> = dequantize(&data, &gltf, accessor).unwrap();
+    assert_eq!(values, [[-1.0, 0.0], [1.0, 16384.0 / 32767.0]]);
+
+    assert_eq!(dequantization_scale(accessor), 1.0 / 32767.0);
+}
+
+#[test]
+fn unnormalized_values_keep_their_scale() {
+    let (gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5121, "count": 1, "type": "VEC3"}"#,
+        vec![1, 2, 255],
+    );
+    let accessor = &gltf.accessors[0];
+
+    assert_eq!(dequantized_bounds(accessor), None);
+    let values: Vec<[f32; 3]> = dequantize(&data, &gltf, accessor).unwrap();
+    assert_eq!(values, [[1.0, 2.0, 255.0]]);
+    assert_eq!(dequantization_scale(accessor), 1.0);
+}
+
+#[test]
+fn the_dequantization_matrix_scales_normalized_positions() {
+    let (gltf, _) = accessor(
+        r#"{"bufferView": 0, "componentType": 5123, "normalized": true, "count": 0, "type": "VEC3"}"#,
+        Vec::new(),
+    );
+
+    let scale = 1.0 / 65535.0;
+    assert_eq!(
+        dequantization_matrix(&gltf.accessors[0]),
+        [scale, 0.0, 0.0, 0.0, 0.0, scale, 0.0, 0.0, 0.0, 0.0, scale, 0.0, 0.0, 0.0, 0.0, 1.0]
+    );
+}
+
+#[test]
+fn views_decode_elements_on_access() {
+    // Normalized bytes, padded to 4 bytes per element.
+    let (mut gltf, data) = accessor(
+        r#"{"bufferView": 0, "componentType": 5121, "normalized": true, "count": 3, "type": "VEC2"}"#,
+        vec![0, 255, 9, 9, 51, 102, 9, 9, 255, 0],
+    );
+    gltf.buffer_views[0].byte_stride = Some(4);
+    let accessor = &gltf.accessors[0];
+
+    let view = view_accessor::<[f32; 2], _>(&data, &gltf, accessor).unwrap();
+    assert_eq!(view.len(), 3);
+    assert_eq!(view.stride(), 4);
+    assert_eq!(view.get(2), Some([1.0, 0.0]));
+    assert_eq!(view.get(0), Some([0.0, 1.0]));
+    assert_eq!(view.get(3), None);
+    assert_eq!(
+        view.iter().rev().collect::<Vec<_>>(),
+        [[1.0, 0.0], [0.2, 0.4], [0.0, 1.0]]
+    );
+
+    assert!(matches!(
+        view_accessor::<[f32; 4], _>(&data, &gltf, accessor),
+        Err(Error::ComponentCountMismatch(4, 2))
+    ));
+}
+
+#[test]
+fn accessors_without_data_to_borrow_cant_be_viewed() {
+    let (gltf, data) = accessor(
+        r#"{"componentType": 5126, "count": 2, "type": "SCALAR"}"#,
+        Vec::new(),
+    );
+
+    assert!(matches!(
+        view_accessor::<f32, _>(&data, &gltf, &gltf.accessors[0]),
+        Err(Error::AccessorNotViewable)
+    ));
+    // They can still be read.
+    assert_eq!(
+        read_accessor::<f32, _>(&data, &gltf, &gltf.accessors[0]).unwrap(),
+        [0.0, 0.0]
+    );
+}
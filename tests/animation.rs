@@ -0,0 +1,381 @@
+#![cfg(not(feature = "no_std"))]
+
+use goth_gltf::animation::{Keyframes, ResampleError, MAX_BAKED_FRAMES};
+use goth_gltf::{Interpolation, TargetPath};
+
+fn sample(keyframes: &Keyframes, time: f32) -> f32 {
+    let mut output = [0.0];
+    keyframes.sample(time, &mut output);
+    output[0]
+}
+
+fn keyframes<'a>(
+    interpolation: Interpolation,
+    times: &'a [f32],
+    values: &'a [f32],
+) -> Keyframes<'a> {
+    Keyframes {
+        interpolation,
+        path: TargetPath::Weights,
+        times,
+        values,
+    }
+}
+
+#[test]
+fn linear_sampling_interpolates_and_clamps() {
+    let keyframes = keyframes(Interpolation::Linear, &[0.0, 1.0, 2.0], &[0.0, 10.0, 30.0]);
+
+    assert_eq!(sample(&keyframes, -1.0), 0.0);
+    assert_eq!(sample(&keyframes, 0.5), 5.0);
+    assert_eq!(sample(&keyframes, 1.5), 20.0);
+    assert_eq!(sample(&keyframes, 3.0), 30.0);
+    assert_eq!(sample(&keyframes, f32::INFINITY), 30.0);
+    assert_eq!(sample(&keyframes, f32::NEG_INFINITY), 0.0);
+}
+
+#[test]
+fn nan_times_dont_panic() {
+    for (interpolation, values) in [
+        (Interpolation::Linear, &[1.0; 3][..]),
+        (Interpolation::Step, &[1.0; 3]),
+        (Interpolation::CubicSpline, &[1.0; 9]),
+    ] {
+        for times in [
+            [f32::NAN, 1.0, 2.0],
+            [0.0, f32::NAN, 2.0],
+            [0.0, 1.0, f32::NAN],
+        ] {
+            let keyframes = keyframes(interpolation, &times, values);
+
+            for time in [f32::NAN, -1.0, 0.5, 1.5, 3.0] {
+                sample(&keyframes, time);
+            }
+
+            let _ = keyframes.resample(30.0);
+        }
+    }
+}
+
+#[test]
+fn unsorted_times_dont_panic() {
+    let values = [0.0, 1.0, 2.0, 3.0];
+
+    for times in [
+        [3.0, 2.0, 1.0, 0.0],
+        [0.0, 2.0, 1.0, 3.0],
+        [2.0, 0.0, 3.0, 1.0],
+    ] {
+        let keyframes = keyframes(Interpolation::Linear, &times, &values);
+
+        for time in [f32::NAN, -1.0, 0.5, 1.5, 2.5, 4.0] {
+            let value = sample(&keyframes, time);
+            assert!((0.0..=3.0).contains(&value) || value.is_nan());
+        }
+
+        let _ = keyframes.resample(30.0);
+    }
+}
+
+#[test]
+fn step_sampling_holds_the_previous_value() {
+    let keyframes = keyframes(Interpolation::Step, &[0.0, 1.0, 2.0], &[0.0, 10.0, 30.0]);
+
+    assert_eq!(sample(&keyframes, 0.0), 0.0);
+    assert_eq!(sample(&keyframes, 0.99), 0.0);
+    assert_eq!(sample(&keyframes, 1.0), 10.0);
+    assert_eq!(sample(&keyframes, 1.5), 10.0);
+    assert_eq!(sample(&keyframes, 5.0), 30.0);
+}
+
+#[test]
+fn cubic_spline_sampling_uses_the_tangents() {
+    // (in-tangent, value, out-tangent) per keyframe.
+    let flat = keyframes(
+        Interpolation::CubicSpline,
+        &[0.0, 1.0],
+        &[0.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    );
+    assert_eq!(sample(&flat, 0.0), 0.0);
+    assert_eq!(sample(&flat, 0.5), 0.5);
+    assert_eq!(sample(&flat, 1.0), 1.0);
+
+    // With tangents matching the slope, the curve is a straight line.
+    let straight = keyframes(
+        Interpolation::CubicSpline,
+        &[0.0, 2.0],
+        &[1.0, 0.0, 1.0, 1.0, 2.0, 1.0],
+    );
+    assert!((sample(&straight, 0.5) - 0.5).abs() < 1.0e-6);
+    assert!((sample(&straight, 1.5) - 1.5).abs() < 1.0e-6);
+}
+
+#[test]
+fn resampling_uses_the_keyframes_interpolation() {
+    let linear = keyframes(Interpolation::Linear, &[0.0, 1.0], &[0.0, 10.0]);
+    let baked = linear.resample(4.0).unwrap();
+    assert_eq!(baked.times, [0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(baked.values, [0.0, 2.5, 5.0, 7.5, 10.0]);
+
+    let step = keyframes(Interpolation::Step, &[0.0, 1.0], &[0.0, 10.0]);
+    assert_eq!(
+        step.resample(4.0).unwrap().values,
+        [0.0, 0.0, 0.0, 0.0, 10.0]
+    );
+
+    // The last keyframe is included even if it isn't on a frame.
+    let baked = linear.resample(3.0 / 0.9).unwrap();
+    assert_eq!(baked.times.len(), 5);
+    assert_eq!(baked.times.last(), Some(&1.0));
+    assert_eq!(baked.values.last(), Some(&10.0));
+
+    let single = keyframes(Interpolation::Linear, &[2.0], &[7.0]);
+    let baked = single.resample(30.0).unwrap();
+    assert_eq!((baked.times, baked.values), (vec![2.0], vec![7.0]));
+}
+
+#[test]
+fn resampling_rejects_bad_rates_and_spans() {
+    let linear = keyframes(Interpolation::Linear, &[0.0, 1.0], &[0.0, 10.0]);
+
+    for rate in [0.0, -30.0, f32::NAN, f32::INFINITY] {
+        assert!(matches!(
+            linear.resample(rate),
+            Err(ResampleError::InvalidRate(_))
+        ));
+    }
+
+    for times in [[0.0, f32::INFINITY], [f32::NEG_INFINITY, 0.0], [1.0, 0.0]] {
+        let keyframes = keyframes(Interpolation::Linear, &times, &[0.0, 10.0]);
+        assert!(matches!(
+            keyframes.resample(30.0),
+            Err(ResampleError::InvalidTimes { .. })
+        ));
+    }
+
+    // Spans that would overflow or allocate without bound.
+    for (times, rate) in [
+        ([-f32::MAX, f32::MAX], 30.0),
+        ([0.0, 1.0], f32::MAX),
+        ([0.0, MAX_BAKED_FRAMES as f32], 1.0),
+    ] {
+        let keyframes = keyframes(Interpolation::Linear, &times, &[0.0, 10.0]);
+        assert_eq!(
+            keyframes.resample(rate).unwrap_err(),
+            ResampleError::TooManyFrames
+        );
+    }
+
+    let keyframes = keyframes(Interpolation::Linear, &[0.0, 1.0], &[0.0, 10.0]);
+    assert_eq!(
+        keyframes
+            .resample(MAX_BAKED_FRAMES as f32 - 2.0)
+            .unwrap()
+            .times
+            .len(),
+        MAX_BAKED_FRAMES - 1
+    );
+}
+
+#[cfg(feature = "primitive_reader")]
+#[test]
+fn poses_fall_back_to_the_nodes_rest_values() {
+    use goth_gltf::{default_extensions::Extensions, Gltf, NodeTransform};
+    use std::collections::HashMap;
+
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"translation": [0, 0, 0], "scale": [2, 2, 2]}, {}, {}],
+            "buffers": [{"byteLength": 32}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR"},
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"}
+            ],
+            "animations": [{
+                "samplers": [{"input": 0, "output": 1}],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}},
+                    {"sampler": 0, "target": {"node": 5, "path": "scale"}}
+                ]
+            }]
+        }"#,
+    )
+    .unwrap();
+    let floats = |values: &[f32]| -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    };
+    let buffer_views = HashMap::from([
+        (0, floats(&[0.0, 2.0])),
+        (1, floats(&[0.0, 0.0, 0.0, 4.0, 2.0, 0.0])),
+    ]);
+
+    let poses = gltf.animations[0]
+        .pose_at(&gltf, &buffer_views, 0.5)
+        .unwrap();
+
+    // The channel targeting a missing node is skipped.
+    assert_eq!(poses.len(), 1);
+    assert_eq!(poses[0].node, 0);
+    assert_eq!(poses[0].weights, None);
+    assert_eq!(
+        poses[0].transform,
+        NodeTransform::Set {
+            translation: [1.0, 0.5, 0.0],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+            scale: [2.0, 2.0, 2.0],
+        }
+    );
+}
+
+// Two keyframes for a translation, a quantized rotation and the weights of two morph targets.
+#[cfg(feature = "primitive_reader")]
+fn animated() -> (
+    goth_gltf::Gltf<goth_gltf::default_extensions::Extensions>,
+    std::collections::HashMap<usize, Vec<u8>>,
+) {
+    let floats = |values: &[f32]| -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    };
+
+    let gltf = goth_gltf::Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{}],
+            "buffers": [{"byteLength": 68}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 32, "byteLength": 16},
+                {"buffer": 0, "byteOffset": 48, "byteLength": 16}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR"},
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5122, "normalized": true, "count": 2, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5126, "count": 4, "type": "SCALAR"}
+            ],
+            "animations": [{
+                "samplers": [
+                    {"input": 0, "output": 1},
+                    {"input": 0, "output": 2, "interpolation": "STEP"},
+                    {"input": 0, "output": 3}
+                ],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}},
+                    {"sampler": 1, "target": {"node": 0, "path": "rotation"}},
+                    {"sampler": 2, "target": {"node": 0, "path": "weights"}},
+                    {"sampler": 3, "target": {"node": 0, "path": "scale"}}
+                ]
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    let buffer_views = std::collections::HashMap::from([
+        (0, floats(&[1.0, 3.0])),
+        (1, floats(&[0.0, 0.0, 0.0, 2.0, 4.0, -2.0])),
+        (
+            2,
+            [0i16, 0, 0, 32767, 0, 32767, 0, 0]
+                .map(i16::to_le_bytes)
+                .concat(),
+        ),
+        (3, floats(&[0.0, 1.0, 1.0, 0.0])),
+    ]);
+
+    (gltf, buffer_views)
+}
+
+#[cfg(feature = "primitive_reader")]
+#[test]
+fn channels_are_read_as_their_target_path() {
+    use goth_gltf::animation::{AnimationReader, ChannelOutputs};
+    use goth_gltf::primitive_reader::Error;
+
+    let (gltf, buffer_views) = animated();
+    let reader = AnimationReader::new(&gltf, &gltf.animations[0], &buffer_views);
+
+    assert_eq!(reader.read_inputs(0).unwrap(), [1.0, 3.0]);
+    assert_eq!(
+        reader.read_outputs(0).unwrap(),
+        ChannelOutputs::Translations(vec![[0.0, 0.0, 0.0], [2.0, 4.0, -2.0]])
+    );
+    assert_eq!(
+        reader.read_outputs(1).unwrap(),
+        ChannelOutputs::Rotations(vec![[0.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 0.0]])
+    );
+    assert_eq!(
+        reader.read_outputs(2).unwrap(),
+        ChannelOutputs::Weights(vec![0.0, 1.0, 1.0, 0.0])
+    );
+
+    assert!(matches!(
+        reader.read_outputs(3),
+        Err(Error::SamplerIndexOutOfBounds(3))
+    ));
+    assert!(matches!(
+        reader.read_inputs(4),
+        Err(Error::ChannelIndexOutOfBounds(4))
+    ));
+}
+
+#[cfg(feature = "primitive_reader")]
+#[test]
+fn channels_are_sampled_with_their_samplers_interpolation() {
+    use goth_gltf::animation::AnimationReader;
+
+    let (gltf, buffer_views) = animated();
+    let animation = &gltf.animations[0];
+    let reader = AnimationReader::new(&gltf, animation, &buffer_views);
+    let sample = |channel: usize, time| animation.channels[channel].sample(&reader, time).unwrap();
+
+    assert_eq!(sample(0, 0.0), [0.0, 0.0, 0.0]);
+    assert_eq!(sample(0, 2.0), [1.0, 2.0, -1.0]);
+    assert_eq!(sample(0, 5.0), [2.0, 4.0, -2.0]);
+
+    assert_eq!(sample(1, 2.9), [0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(sample(1, 3.0), [0.0, 1.0, 0.0, 0.0]);
+
+    assert_eq!(sample(2, 1.5), [0.25, 0.75]);
+}
+
+#[test]
+fn rotations_are_slerped() {
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    let keyframes = Keyframes {
+        interpolation: Interpolation::Linear,
+        path: TargetPath::Rotation,
+        times: &[0.0, 1.0],
+        // No rotation, then a quarter turn around y.
+        values: &[0.0, 0.0, 0.0, 1.0, 0.0, half, 0.0, half],
+    };
+
+    let mut rotation = [0.0; 4];
+    keyframes.sample(0.5, &mut rotation);
+
+    // An eighth of a turn.
+    let angle = std::f32::consts::FRAC_PI_8;
+    let expected = [0.0, angle.sin(), 0.0, angle.cos()];
+    for (value, expected) in rotation.iter().zip(expected) {
+        assert!((value - expected).abs() < 1e-6, "{:?}", rotation);
+    }
+
+    // Away from the middle, a normalized lerp would only approximate this.
+    keyframes.sample(0.25, &mut rotation);
+    let angle = std::f32::consts::FRAC_PI_8 / 2.0;
+    let expected = [0.0, angle.sin(), 0.0, angle.cos()];
+    for (value, expected) in rotation.iter().zip(expected) {
+        assert!((value - expected).abs() < 1e-6, "{:?}", rotation);
+    }
+}
@@ -0,0 +1,118 @@
+use goth_gltf::animation_pointer::{MaterialTexture, ObjectKind, PointerTarget, Property};
+
+fn parse(pointer: &str) -> Option<(ObjectKind, usize, Property)> {
+    PointerTarget::parse(pointer).map(|target| (target.object, target.index, target.property))
+}
+
+#[test]
+fn core_properties_are_resolved() {
+    assert_eq!(
+        parse("/nodes/3/rotation"),
+        Some((ObjectKind::Node, 3, Property::Rotation))
+    );
+    assert_eq!(
+        parse("/meshes/0/weights"),
+        Some((ObjectKind::Mesh, 0, Property::Weights))
+    );
+    assert_eq!(
+        parse("/materials/2/pbrMetallicRoughness/baseColorFactor"),
+        Some((ObjectKind::Material, 2, Property::BaseColorFactor))
+    );
+    assert_eq!(
+        parse("/cameras/1/perspective/yfov"),
+        Some((ObjectKind::Camera, 1, Property::PerspectiveYfov))
+    );
+    assert_eq!(
+        parse("/extensions/KHR_lights_punctual/lights/4/spot/outerConeAngle"),
+        Some((ObjectKind::Light, 4, Property::LightSpotOuterConeAngle))
+    );
+    assert_eq!(
+        parse("/materials/0/normalTexture/extensions/KHR_texture_transform/offset"),
+        Some((
+            ObjectKind::Material,
+            0,
+            Property::TextureTransformOffset(MaterialTexture::Normal)
+        ))
+    );
+}
+
+#[test]
+fn other_properties_keep_their_path() {
+    assert_eq!(
+        parse("/materials/1/extensions/KHR_materials_ior/ior"),
+        Some((
+            ObjectKind::Material,
+            1,
+            Property::Other("extensions/KHR_materials_ior/ior".to_string())
+        ))
+    );
+    // Escaped `/` and `~` in a token.
+    assert_eq!(
+        parse("/nodes/0/extras/a~1b~0c"),
+        Some((
+            ObjectKind::Node,
+            0,
+            Property::Other("extras/a/b~c".to_string())
+        ))
+    );
+}
+
+#[test]
+fn malformed_pointers_are_rejected() {
+    for pointer in [
+        "",
+        "nodes/0/rotation",
+        "/nodes/0",
+        "/nodes/x/rotation",
+        "/nodes/-1/rotation",
+        "/skins/0/joints",
+        "/materials/0/normalTexture/extensions/KHR_texture_transform/texCoord2",
+    ] {
+        assert_eq!(PointerTarget::parse(pointer), None, "{}", pointer);
+    }
+}
+
+#[test]
+fn component_counts_match_the_property() {
+    assert_eq!(Property::Rotation.components(), Some(4));
+    assert_eq!(Property::LightColor.components(), Some(3));
+    assert_eq!(
+        Property::TextureTransformScale(MaterialTexture::BaseColor).components(),
+        Some(2)
+    );
+    assert_eq!(Property::AlphaCutoff.components(), Some(1));
+    assert_eq!(Property::Weights.components(), None);
+}
+
+#[cfg(feature = "khr_animation_pointer")]
+#[test]
+fn channel_targets_resolve_their_pointer() {
+    use goth_gltf::{default_extensions::Extensions, Gltf};
+
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "animations": [{
+                "samplers": [{"input": 0, "output": 1}],
+                "channels": [
+                    {"sampler": 0, "target": {"path": "pointer", "extensions": {
+                        "KHR_animation_pointer": {"pointer": "/materials/0/alphaCutoff"}
+                    }}},
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}}
+                ]
+            }]
+        }"#,
+    )
+    .unwrap();
+
+    let channels = &gltf.animations[0].channels;
+    assert_eq!(
+        channels[0].target.pointer_target(),
+        Some(PointerTarget {
+            object: ObjectKind::Material,
+            index: 0,
+            property: Property::AlphaCutoff,
+        })
+    );
+    assert_eq!(channels[1].target.pointer_target(), None);
+}
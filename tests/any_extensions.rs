@@ -0,0 +1,107 @@
+use goth_gltf::{AnyExtensions, Gltf, JsonValue};
+use nanoserde::SerJson;
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0", "extensions": {"VENDOR_asset": 1}},
+    "extensions": {"VENDOR_root": {"list": [1, "two", null]}},
+    "extensionsUsed": ["VENDOR_root"],
+    "materials": [{"extensions": {"KHR_materials_unlit": {}}, "extras": {"blender": true}}],
+    "accessors": [{
+        "componentType": 5126,
+        "count": 1,
+        "type": "SCALAR",
+        "extensions": {"VENDOR_accessor": "x"}
+    }],
+    "nodes": [{"extras": [1, 2]}],
+    "animations": [{
+        "channels": [{"sampler": 0, "target": {"path": "pointer", "extensions": {
+            "KHR_animation_pointer": {"pointer": "/nodes/0/translation"}
+        }}}],
+        "samplers": [{"input": 0, "output": 0, "extensions": {"VENDOR_sampler": -1.5}}]
+    }]
+}"#;
+
+#[test]
+fn every_extension_is_kept_as_json() {
+    let gltf = Gltf::<AnyExtensions>::from_json_string(JSON).unwrap();
+
+    let object = |members: &[(&str, JsonValue)]| {
+        JsonValue::Object(
+            members
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        )
+    };
+
+    assert_eq!(
+        gltf.asset.extensions["VENDOR_asset"],
+        JsonValue::Number(1.0)
+    );
+    assert_eq!(
+        gltf.extensions["VENDOR_root"],
+        object(&[(
+            "list",
+            JsonValue::Array(vec![
+                JsonValue::Number(1.0),
+                JsonValue::String("two".to_string()),
+                JsonValue::Null,
+            ])
+        )])
+    );
+    assert_eq!(
+        gltf.materials[0].extensions["KHR_materials_unlit"],
+        object(&[])
+    );
+    assert_eq!(
+        gltf.materials[0].extras,
+        object(&[("blender", JsonValue::Bool(true))])
+    );
+    assert_eq!(
+        gltf.accessors[0].extensions["VENDOR_accessor"],
+        JsonValue::String("x".to_string())
+    );
+    assert_eq!(
+        gltf.nodes[0].extras,
+        JsonValue::Array(vec![JsonValue::Number(1.0), JsonValue::Number(2.0)])
+    );
+
+    let animation = &gltf.animations[0];
+    assert_eq!(
+        animation.channels[0].target.extensions["KHR_animation_pointer"],
+        object(&[(
+            "pointer",
+            JsonValue::String("/nodes/0/translation".to_string())
+        )])
+    );
+    assert_eq!(
+        animation.samplers[0].extensions["VENDOR_sampler"],
+        JsonValue::Number(-1.5)
+    );
+}
+
+#[test]
+fn extensions_survive_a_round_trip() {
+    let gltf = Gltf::<AnyExtensions>::from_json_string(JSON).unwrap();
+    let written = Gltf::<AnyExtensions>::from_json_string(&gltf.to_json_string()).unwrap();
+
+    assert_eq!(written.extensions, gltf.extensions);
+    assert_eq!(written.asset.extensions, gltf.asset.extensions);
+    assert_eq!(
+        written.materials[0].extensions,
+        gltf.materials[0].extensions
+    );
+    assert_eq!(written.materials[0].extras, gltf.materials[0].extras);
+    assert_eq!(written.nodes[0].extras, gltf.nodes[0].extras);
+    assert_eq!(
+        written.animations[0].samplers[0].extensions,
+        gltf.animations[0].samplers[0].extensions
+    );
+}
+
+#[test]
+fn non_finite_numbers_are_written_as_null() {
+    assert_eq!(JsonValue::Number(f64::NAN).serialize_json(), "null");
+    assert_eq!(JsonValue::Number(f64::INFINITY).serialize_json(), "null");
+    assert_eq!(JsonValue::Number(0.5).serialize_json(), "0.5");
+}
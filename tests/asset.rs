@@ -0,0 +1,42 @@
+use goth_gltf::{default_extensions::Extensions, AnyExtensions, Gltf};
+
+const JSON: &str = r#"{
+    "asset": {
+        "version": "2.0",
+        "minVersion": "2.0",
+        "generator": "Khronos glTF Blender I/O v3.6.27",
+        "copyright": "CC-BY",
+        "extras": {"exporterSettings": 1}
+    }
+}"#;
+
+#[test]
+fn asset_metadata_is_parsed() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+
+    assert_eq!(gltf.asset.version, "2.0");
+    assert_eq!(gltf.asset.min_version.as_deref(), Some("2.0"));
+    assert_eq!(
+        gltf.asset.generator.as_deref(),
+        Some("Khronos glTF Blender I/O v3.6.27")
+    );
+    assert_eq!(gltf.asset.copyright.as_deref(), Some("CC-BY"));
+}
+
+#[test]
+fn asset_extras_are_kept() {
+    let gltf = Gltf::<AnyExtensions>::from_json_string(JSON).unwrap();
+    let written = gltf.to_json_string();
+
+    assert!(written.contains(r#""extras":{"exporterSettings":1}"#));
+    assert!(written.contains(r#""generator":"Khronos glTF Blender I/O v3.6.27""#));
+}
+
+#[test]
+fn optional_asset_fields_can_be_missing() {
+    let gltf = Gltf::<Extensions>::from_json_string(r#"{"asset": {"version": "2.0"}}"#).unwrap();
+
+    assert!(gltf.asset.min_version.is_none());
+    assert!(gltf.asset.generator.is_none());
+    assert!(gltf.asset.copyright.is_none());
+}
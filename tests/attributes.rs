@@ -0,0 +1,178 @@
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::Gltf;
+
+#[cfg(feature = "primitive_reader")]
+use goth_gltf::primitive_reader::PrimitiveReader;
+#[cfg(feature = "primitive_reader")]
+use std::collections::HashMap;
+
+// Two vertices with two sets of four influences each: u8 joints and normalized u8 weights in the
+// first set, u16 joints and float weights in the second.
+const SKINNED: &str = r#"{
+    "asset": {"version": "2.0"},
+    "buffers": [{"byteLength": 64}],
+    "bufferViews": [
+        {"buffer": 0, "byteLength": 8},
+        {"buffer": 0, "byteOffset": 8, "byteLength": 8},
+        {"buffer": 0, "byteOffset": 16, "byteLength": 16},
+        {"buffer": 0, "byteOffset": 32, "byteLength": 32}
+    ],
+    "accessors": [
+        {"bufferView": 0, "componentType": 5121, "count": 2, "type": "VEC4"},
+        {"bufferView": 1, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC4"},
+        {"bufferView": 2, "componentType": 5123, "count": 2, "type": "VEC4"},
+        {"bufferView": 3, "componentType": 5126, "count": 2, "type": "VEC4"}
+    ],
+    "meshes": [{"primitives": [{"attributes": {
+        "JOINTS_0": 0,
+        "WEIGHTS_0": 1,
+        "JOINTS_1": 2,
+        "WEIGHTS_1": 3
+    }}]}]
+}"#;
+
+#[test]
+fn both_influence_sets_are_parsed() {
+    let gltf = Gltf::<Extensions>::from_json_string(SKINNED).unwrap();
+    let attributes = &gltf.meshes[0].primitives[0].attributes;
+
+    assert_eq!(attributes.joints_1, Some(2));
+    assert_eq!(attributes.weights_1, Some(3));
+    assert_eq!(attributes.joints(0), Some(0));
+    assert_eq!(attributes.weights(1), Some(3));
+    assert_eq!(attributes.joints(2), None);
+    assert!(attributes.other.is_empty());
+}
+
+#[test]
+fn further_influence_sets_are_kept_by_semantic() {
+    let json = r#"{
+        "asset": {"version": "2.0"},
+        "meshes": [{"primitives": [{"attributes": {"JOINTS_2": 4, "WEIGHTS_2": 5}}]}]
+    }"#;
+    let gltf = Gltf::<Extensions>::from_json_string(json).unwrap();
+    let attributes = &gltf.meshes[0].primitives[0].attributes;
+
+    assert_eq!(attributes.joints(2), Some(4));
+    assert_eq!(attributes.weights(2), Some(5));
+}
+
+#[test]
+#[cfg(feature = "primitive_reader")]
+fn both_influence_sets_are_read() {
+    let gltf = Gltf::<Extensions>::from_json_string(SKINNED).unwrap();
+
+    let mut bytes = vec![0, 1, 2, 3, 4, 5, 6, 7, 255, 0, 0, 0, 0, 0, 0, 255];
+    bytes.extend(
+        [8u16, 9, 10, 11, 300, 301, 302, 303]
+            .map(u16::to_le_bytes)
+            .concat(),
+    );
+    bytes.extend(
+        [0.5f32, 0.5, 0.0, 0.0, 0.25, 0.25, 0.25, 0.25]
+            .map(f32::to_le_bytes)
+            .concat(),
+    );
+    let buffer_views: HashMap<usize, Vec<u8>> = gltf
+        .buffer_views
+        .iter()
+        .enumerate()
+        .map(|(index, view)| {
+            let start = view.byte_offset;
+            (index, bytes[start..start + view.byte_length].to_vec())
+        })
+        .collect();
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+
+    assert_eq!(
+        &reader.read_joints(0).unwrap().unwrap()[..],
+        &[[0, 1, 2, 3], [4, 5, 6, 7]]
+    );
+    assert_eq!(
+        &reader.read_weights(0).unwrap().unwrap()[..],
+        &[[1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0]]
+    );
+    assert_eq!(
+        &reader.read_joints(1).unwrap().unwrap()[..],
+        &[[8, 9, 10, 11], [300, 301, 302, 303]]
+    );
+    assert_eq!(
+        &reader.read_weights(1).unwrap().unwrap()[..],
+        &[[0.5, 0.5, 0.0, 0.0], [0.25; 4]]
+    );
+    assert!(reader.read_joints(2).unwrap().is_none());
+    assert!(reader.read_weights(2).unwrap().is_none());
+}
+
+const CUSTOM: &str = r#"{
+    "asset": {"version": "2.0"},
+    "buffers": [{"byteLength": 12}],
+    "bufferViews": [{"buffer": 0, "byteLength": 4}, {"buffer": 0, "byteOffset": 4, "byteLength": 8}],
+    "accessors": [
+        {"bufferView": 0, "componentType": 5123, "count": 2, "type": "SCALAR"},
+        {"bufferView": 1, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC4"}
+    ],
+    "meshes": [{"primitives": [{"attributes": {
+        "_FEATURE_ID_0": 0,
+        "COLOR_1": 1,
+        "TEXCOORD_2": 1,
+        "POSITION": 0
+    }}]}]
+}"#;
+
+#[test]
+fn other_attributes_are_kept_sorted_by_semantic() {
+    let gltf = Gltf::<Extensions>::from_json_string(CUSTOM).unwrap();
+    let attributes = &gltf.meshes[0].primitives[0].attributes;
+
+    assert_eq!(attributes.position, Some(0));
+    assert_eq!(
+        attributes.other,
+        [
+            ("COLOR_1".to_string(), 1),
+            ("TEXCOORD_2".to_string(), 1),
+            ("_FEATURE_ID_0".to_string(), 0),
+        ]
+    );
+    assert_eq!(attributes.get("_FEATURE_ID_0"), Some(0));
+    assert_eq!(attributes.get("POSITION"), Some(0));
+    assert_eq!(attributes.get("TEXCOORD_3"), None);
+    assert_eq!(attributes.iter().count(), 4);
+}
+
+#[test]
+fn other_attributes_are_written_back() {
+    let gltf = Gltf::<Extensions>::from_json_string(CUSTOM).unwrap();
+    let written = Gltf::<Extensions>::from_json_string(&gltf.to_json_string()).unwrap();
+
+    assert_eq!(
+        written.meshes[0].primitives[0].attributes.other,
+        gltf.meshes[0].primitives[0].attributes.other
+    );
+}
+
+#[test]
+#[cfg(feature = "primitive_reader")]
+fn any_attribute_can_be_read_by_semantic() {
+    let gltf = Gltf::<Extensions>::from_json_string(CUSTOM).unwrap();
+    let mut bytes = [7u16, 7000].map(u16::to_le_bytes).concat();
+    bytes.extend([0, 255, 51, 255, 255, 0, 102, 255]);
+    let buffer_views = HashMap::from([(0, bytes[..4].to_vec()), (1, bytes[4..].to_vec())]);
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+
+    assert_eq!(
+        reader.read_attribute("_FEATURE_ID_0").unwrap(),
+        Some(vec![7.0, 7000.0])
+    );
+    assert_eq!(
+        reader.read_attribute("TEXCOORD_2").unwrap(),
+        Some(vec![0.0, 1.0, 0.2, 1.0, 1.0, 0.0, 0.4, 1.0])
+    );
+    assert_eq!(
+        &reader.read_colors(1).unwrap().unwrap()[..],
+        &[[0.0, 1.0, 0.2, 1.0], [1.0, 0.0, 0.4, 1.0]]
+    );
+    assert_eq!(reader.read_attribute("_MISSING").unwrap(), None);
+}
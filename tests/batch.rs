@@ -0,0 +1,115 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::batch::{batch_by_material, DrawRange};
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::Gltf;
+use std::collections::HashMap;
+
+fn floats(values: &[f32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+// A triangle, a fan, a line and an indexed triangle, across two meshes.
+fn meshes() -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let gltf = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "materials": [{}, {}],
+            "buffers": [{"byteLength": 126}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 72, "byteLength": 48},
+                {"buffer": 0, "byteOffset": 120, "byteLength": 6}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3"},
+                {"bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 4, "type": "VEC3"},
+                {"bufferView": 3, "componentType": 5123, "count": 3, "type": "SCALAR"}
+            ],
+            "meshes": [
+                {"primitives": [
+                    {"attributes": {"POSITION": 0, "NORMAL": 1}, "material": 1},
+                    {"attributes": {"POSITION": 2}, "mode": 6},
+                    {"attributes": {"POSITION": 0}, "mode": 1, "material": 1}
+                ]},
+                {"primitives": [
+                    {"attributes": {"POSITION": 0}, "indices": 3, "material": 1}
+                ]}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let buffer_views = HashMap::from([
+        (0, floats(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0])),
+        (1, floats(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0])),
+        (2, floats(&[0.0; 12])),
+        (3, [2u16, 1, 0].map(u16::to_le_bytes).concat()),
+    ]);
+
+    (gltf, buffer_views)
+}
+
+#[test]
+fn primitives_are_merged_by_material() {
+    let (gltf, buffer_views) = meshes();
+    let batches = batch_by_material(&gltf, &buffer_views, 0..gltf.meshes.len(), None).unwrap();
+
+    assert_eq!(batches.len(), 2);
+
+    // The fan, with the default material.
+    let fan = &batches[0];
+    assert_eq!(fan.material, None);
+    assert_eq!(fan.positions.len(), 4);
+    assert_eq!(fan.indices, [1, 2, 0, 2, 3, 0]);
+    // Missing attributes are filled in.
+    assert_eq!(fan.normals, [[0.0; 3]; 4]);
+    assert_eq!(fan.colors, [[1.0; 4]; 4]);
+    assert_eq!(fan.tangents, [[1.0, 0.0, 0.0, 1.0]; 4]);
+
+    // The two triangles, but not the line.
+    let triangles = &batches[1];
+    assert_eq!(triangles.material, Some(1));
+    assert_eq!(triangles.positions.len(), 6);
+    assert_eq!(triangles.indices, [0, 1, 2, 5, 4, 3]);
+    assert_eq!(
+        triangles.normals,
+        [[0.0, 0.0, 1.0]; 3]
+            .into_iter()
+            .chain([[0.0; 3]; 3])
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        triangles.draws,
+        [
+            DrawRange {
+                mesh: 0,
+                primitive: 0,
+                indices: 0..3,
+                vertices: 0..3,
+            },
+            DrawRange {
+                mesh: 1,
+                primitive: 0,
+                indices: 3..6,
+                vertices: 3..6,
+            },
+        ]
+    );
+}
+
+#[test]
+fn only_the_given_meshes_are_batched() {
+    let (gltf, buffer_views) = meshes();
+    // Meshes that don't exist are skipped.
+    let batches = batch_by_material(&gltf, &buffer_views, [1, 7], None).unwrap();
+
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].material, Some(1));
+    assert_eq!(batches[0].indices, [2, 1, 0]);
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::buffers::{
+    pack_glb, repack_buffers, BorrowedBufferViews, BufferViewData, BufferViewMapBuilder, Error,
+    MeshoptDecoder,
+};
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::extensions::ExtMeshoptCompression;
+use goth_gltf::primitive_reader::PrimitiveReader;
+use goth_gltf::Gltf;
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+fn parse(json: &str) -> Gltf<Extensions> {
+    Gltf::from_json_string(json).unwrap()
+}
+
+#[test]
+fn buffer_views_are_sliced_from_every_kind_of_buffer() {
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [
+                {"byteLength": 4},
+                {"byteLength": 3, "uri": "data:application/octet-stream;base64,AQID"},
+                {"byteLength": 2, "uri": "external.bin"}
+            ],
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 1, "byteLength": 2},
+                {"buffer": 1, "byteLength": 3},
+                {"buffer": 2, "byteOffset": 1, "byteLength": 1},
+                {"buffer": 2, "byteLength": 2}
+            ]
+        }"#,
+    );
+    let loaded = RefCell::new(Vec::new());
+
+    let map = BufferViewMapBuilder::new(&gltf)
+        .with_binary_chunk(Some(&[10, 11, 12, 13]))
+        .with_loader(|uri| {
+            loaded.borrow_mut().push(uri.to_string());
+            Ok(vec![20, 21])
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(map[&0], [11, 12]);
+    assert_eq!(map[&1], [1, 2, 3]);
+    assert_eq!(map[&2], [21]);
+    assert_eq!(map[&3], [20, 21]);
+    // Each buffer is only loaded once, and data uris don't go through the loader.
+    assert_eq!(*loaded.borrow(), ["external.bin"]);
+}
+
+#[test]
+fn missing_buffer_data_is_an_error() {
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4}],
+            "bufferViews": [{"buffer": 0, "byteOffset": 2, "byteLength": 4}]
+        }"#,
+    );
+
+    assert!(matches!(
+        BufferViewMapBuilder::new(&gltf).build(),
+        Err(Error::MissingBufferData(0))
+    ));
+    assert!(matches!(
+        BufferViewMapBuilder::new(&gltf)
+            .with_binary_chunk(Some(&[0; 4]))
+            .build(),
+        Err(Error::BufferViewOutOfBounds(0))
+    ));
+
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4, "uri": "external.bin"}],
+            "bufferViews": [{"buffer": 0, "byteLength": 4}]
+        }"#,
+    );
+
+    assert!(matches!(
+        BufferViewMapBuilder::new(&gltf).build(),
+        Err(Error::LoaderMissing(uri)) if uri == "external.bin"
+    ));
+    assert!(matches!(
+        BufferViewMapBuilder::new(&gltf)
+            .with_loader(|_| Err("not found".into()))
+            .build(),
+        Err(Error::Load(..))
+    ));
+}
+
+// Returns the compressed bytes reversed, so that it's clear they went through the decoder.
+struct Reverse;
+
+impl MeshoptDecoder for Reverse {
+    fn decode(
+        &self,
+        bytes: &[u8],
+        extension: &ExtMeshoptCompression,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        assert_eq!(bytes.len(), extension.byte_length);
+        Ok(bytes.iter().rev().copied().collect())
+    }
+}
+
+#[test]
+fn meshopt_buffer_views_are_decoded_without_loading_the_fallback() {
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [
+                {"byteLength": 4},
+                {"byteLength": 4, "uri": "fallback.bin", "extensions": {"EXT_meshopt_compression": {"fallback": true}}}
+            ],
+            "bufferViews": [{
+                "buffer": 1,
+                "byteLength": 4,
+                "extensions": {"EXT_meshopt_compression": {
+                    "buffer": 0, "byteOffset": 1, "byteLength": 3, "byteStride": 1, "count": 4, "mode": "ATTRIBUTES"
+                }}
+            }]
+        }"#,
+    );
+    let binary_chunk = [0, 1, 2, 3];
+
+    let map = BufferViewMapBuilder::new(&gltf)
+        .with_binary_chunk(Some(&binary_chunk))
+        .with_loader(|uri| panic!("{} shouldn't be loaded", uri))
+        .with_meshopt_decoder(&Reverse)
+        .build()
+        .unwrap();
+    assert_eq!(map[&0], [3, 2, 1]);
+
+    assert!(matches!(
+        BufferViewMapBuilder::new(&gltf)
+            .with_binary_chunk(Some(&binary_chunk))
+            .build(),
+        Err(Error::MeshoptDecoderMissing(0))
+    ));
+}
+
+#[test]
+fn buffer_views_can_be_borrowed_from_buffers_in_memory() {
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 28}, {"byteLength": 4}],
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 4, "byteLength": 24},
+                {"buffer": 1, "byteLength": 8},
+                {"buffer": 2, "byteLength": 4}
+            ],
+            "accessors": [{"bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3"}],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}]
+        }"#,
+    );
+    // Keep the floats aligned after the 4 byte offset.
+    let floats: Vec<f32> = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let buffers: [&[u8]; 2] = [bytemuck::cast_slice(&floats), &[0; 4]];
+
+    let buffer_views = BorrowedBufferViews::new(&gltf, |index| buffers.get(index).copied());
+
+    assert_eq!(buffer_views.buffer_view_bytes(0), Some(&buffers[0][4..]));
+    // Out of bounds of the buffer, and a buffer that doesn't exist.
+    assert_eq!(buffer_views.buffer_view_bytes(1), None);
+    assert_eq!(buffer_views.buffer_view_bytes(2), None);
+    assert_eq!(buffer_views.buffer_view_bytes(3), None);
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+    match reader.read_positions().unwrap().unwrap() {
+        Cow::Borrowed(positions) => assert_eq!(positions, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]),
+        Cow::Owned(_) => panic!("expected the positions to be borrowed"),
+    }
+}
+
+#[test]
+fn buffers_are_merged_and_views_aligned() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 8}, {"byteLength": 4, "uri": "second.bin"}],
+            "bufferViews": [
+                {"buffer": 1, "byteOffset": 1, "byteLength": 3},
+                {"buffer": 0, "byteOffset": 2, "byteLength": 5},
+                {"buffer": 0, "byteLength": 2}
+            ]
+        }"#,
+    );
+    let buffers: [&[u8]; 2] = [&[0, 1, 2, 3, 4, 5, 6, 7], &[10, 11, 12, 13]];
+
+    let packed = repack_buffers(&mut gltf, |index| buffers.get(index).copied()).unwrap();
+
+    // Each view starts on a 4-byte boundary, and bytes outside of any view are dropped.
+    assert_eq!(packed, [11, 12, 13, 0, 2, 3, 4, 5, 6, 0, 0, 0, 0, 1, 0, 0]);
+    assert_eq!(
+        gltf.buffer_views
+            .iter()
+            .map(|view| (view.buffer, view.byte_offset, view.byte_length))
+            .collect::<Vec<_>>(),
+        [(0, 0, 3), (0, 4, 5), (0, 12, 2)]
+    );
+    assert_eq!(gltf.buffers.len(), 1);
+    assert_eq!(gltf.buffers[0].uri, None);
+    assert_eq!(gltf.buffers[0].byte_length, 16);
+}
+
+#[test]
+fn repacking_leaves_the_document_unchanged_on_errors() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4}, {"byteLength": 4, "uri": "missing.bin"}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 4},
+                {"buffer": 1, "byteLength": 4}
+            ]
+        }"#,
+    );
+    let buffers: [&[u8]; 1] = [&[0; 4]];
+
+    assert!(matches!(
+        repack_buffers(&mut gltf, |index| buffers.get(index).copied()),
+        Err(Error::MissingBufferData(1))
+    ));
+    assert_eq!(gltf.buffers.len(), 2);
+    assert_eq!(gltf.buffer_views[1].buffer, 1);
+
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4}],
+            "bufferViews": [{
+                "buffer": 0,
+                "byteLength": 4,
+                "extensions": {"EXT_meshopt_compression": {
+                    "buffer": 0, "byteLength": 4, "byteStride": 4, "count": 1, "mode": "ATTRIBUTES"
+                }}
+            }]
+        }"#,
+    );
+    assert!(matches!(
+        repack_buffers(&mut gltf, |index| buffers.get(index).copied()),
+        Err(Error::MeshoptRepack(0))
+    ));
+}
+
+#[test]
+fn packed_glbs_read_back_with_every_buffer_embedded() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 2, "uri": "first.bin"}, {"byteLength": 3, "uri": "second.bin"}],
+            "bufferViews": [{"buffer": 1, "byteLength": 3}, {"buffer": 0, "byteLength": 2}]
+        }"#,
+    );
+    let buffers: [&[u8]; 2] = [&[1, 2], &[3, 4, 5]];
+
+    let glb = pack_glb(&mut gltf, |index| buffers.get(index).copied()).unwrap();
+    let (read, binary_chunk) = Gltf::<Extensions>::from_bytes(&glb).unwrap();
+
+    assert_eq!(read.to_json_string(), gltf.to_json_string());
+    assert_eq!(read.buffers.len(), 1);
+    assert_eq!(read.buffers[0].uri, None);
+
+    let map = BufferViewMapBuilder::new(&read)
+        .with_binary_chunk(binary_chunk)
+        .build()
+        .unwrap();
+    assert_eq!(map[&0], [3, 4, 5]);
+    assert_eq!(map[&1], [1, 2]);
+}
@@ -0,0 +1,102 @@
+// Triggered by the code nanoserde derives for `Option` fields.
+#![allow(clippy::question_mark)]
+
+use goth_gltf::{Gltf, JsonValue};
+use nanoserde::{DeJson, SerJson};
+
+#[derive(Debug, Default, Clone, DeJson, SerJson)]
+pub struct TagExtensions {
+    #[nserde(rename = "ACME_tag")]
+    pub acme_tag: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, DeJson, SerJson)]
+pub struct PriorityExtras {
+    pub priority: Option<u32>,
+}
+
+goth_gltf::compose_extensions! {
+    pub struct TaggedExtensions {
+        NodeExtensions += TagExtensions,
+        TextureExtensions += TagExtensions,
+        NodeExtras += PriorityExtras,
+    }
+}
+
+// A second set in the same module, to check the invocations don't clash.
+goth_gltf::compose_extensions! {
+    struct DynamicExtensions {
+        RootExtensions += goth_gltf::JsonMap,
+    }
+}
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "extensions": {"VENDOR_root": [1]},
+    "images": [{"uri": "a.png"}, {"uri": "a.webp"}],
+    "textures": [{
+        "source": 0,
+        "extensions": {"EXT_texture_webp": {"source": 1}, "ACME_tag": "texture"}
+    }],
+    "nodes": [{
+        "extensions": {"ACME_tag": "door", "MSFT_lod": {"ids": [1]}},
+        "extras": {"priority": 3}
+    }, {}]
+}"#;
+
+#[test]
+fn composed_extensions_are_parsed_alongside_the_defaults() {
+    let gltf = Gltf::<TaggedExtensions>::from_json_string(JSON).unwrap();
+
+    let node = &gltf.nodes[0];
+    assert_eq!(node.extensions.extra.acme_tag.as_deref(), Some("door"));
+    #[cfg(feature = "msft_lod")]
+    assert_eq!(node.extensions.base.msft_lod.as_ref().unwrap().ids, [1]);
+    assert_eq!(node.extras.extra.priority, Some(3));
+    assert!(gltf.nodes[1].extensions.extra.acme_tag.is_none());
+
+    let texture = &gltf.textures[0];
+    assert_eq!(
+        texture.extensions.extra.acme_tag.as_deref(),
+        Some("texture")
+    );
+}
+
+#[test]
+#[cfg(feature = "texture_formats")]
+fn composed_extensions_keep_the_default_helpers() {
+    use goth_gltf::TextureSourceKind;
+
+    let gltf = Gltf::<TaggedExtensions>::from_json_string(JSON).unwrap();
+    let source = gltf.textures[0]
+        .preferred_source(&[TextureSourceKind::ExtTextureWebp])
+        .unwrap();
+
+    assert_eq!(source.image, 1);
+    assert_eq!(source.extension, Some(TextureSourceKind::ExtTextureWebp));
+}
+
+#[test]
+fn composed_extensions_are_written_once() {
+    let gltf = Gltf::<TaggedExtensions>::from_json_string(JSON).unwrap();
+    let written = gltf.to_json_string();
+
+    assert_eq!(written.matches(r#""ACME_tag":"door""#).count(), 1);
+    assert!(written.contains(r#""extras":{"priority":3}"#));
+
+    let reparsed = Gltf::<TaggedExtensions>::from_json_string(&written).unwrap();
+    assert_eq!(
+        reparsed.nodes[0].extensions.extra.acme_tag.as_deref(),
+        Some("door")
+    );
+}
+
+#[test]
+fn dynamic_json_can_be_composed() {
+    let gltf = Gltf::<DynamicExtensions>::from_json_string(JSON).unwrap();
+
+    assert_eq!(
+        gltf.extensions.extra["VENDOR_root"],
+        JsonValue::Array(vec![JsonValue::Number(1.0)])
+    );
+}
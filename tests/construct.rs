@@ -0,0 +1,72 @@
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::{
+    Accessor, AccessorType, Attributes, Buffer, BufferView, ComponentType, Gltf, Material, Mesh,
+    Node, Primitive, Scene,
+};
+
+#[test]
+fn documents_can_be_built_in_memory() {
+    let mut gltf = Gltf::<Extensions>::default();
+    assert_eq!(gltf.asset.version, "2.0");
+
+    gltf.buffers
+        .push(Buffer::new(Some("mesh.bin".to_string()), 36));
+    gltf.buffer_views.push(BufferView::new(0, 0, 36));
+    let mut positions = Accessor::new(0, ComponentType::Float, AccessorType::Vec3, 3);
+    positions.min = Some(vec![0.0; 3]);
+    positions.max = Some(vec![1.0; 3]);
+    gltf.accessors.push(positions);
+
+    gltf.materials.push(Material {
+        alpha_cutoff: 0.25,
+        ..Default::default()
+    });
+    gltf.meshes.push(Mesh {
+        primitives: vec![Primitive {
+            attributes: Attributes {
+                position: Some(0),
+                ..Default::default()
+            },
+            material: Some(0),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    gltf.nodes.push(Node {
+        mesh: Some(0),
+        ..Default::default()
+    });
+    gltf.scenes.push(Scene {
+        nodes: vec![0],
+        ..Default::default()
+    });
+    gltf.scene = Some(0);
+
+    // The document survives being written and parsed again.
+    let parsed = Gltf::<Extensions>::from_json_string(&gltf.to_json_string()).unwrap();
+
+    assert_eq!(parsed.buffers[0].uri.as_deref(), Some("mesh.bin"));
+    assert_eq!(parsed.buffer_views[0].byte_length, 36);
+    let accessor = &parsed.accessors[0];
+    assert_eq!(accessor.buffer_view, Some(0));
+    assert_eq!(accessor.component_type, ComponentType::Float);
+    assert_eq!(accessor.count, 3);
+    assert_eq!(accessor.max.as_deref(), Some(&[1.0; 3][..]));
+    assert_eq!(parsed.materials[0].alpha_cutoff, 0.25);
+    assert_eq!(parsed.meshes[0].primitives[0].attributes.position, Some(0));
+    assert_eq!(parsed.nodes[0].mesh, Some(0));
+    assert_eq!(parsed.scenes[0].nodes, [0]);
+    assert_eq!(parsed.scene, Some(0));
+}
+
+#[test]
+fn defaults_match_the_spec() {
+    let material = Material::<Extensions>::default();
+    assert_eq!(material.pbr_metallic_roughness.base_color_factor, [1.0; 4]);
+    assert_eq!(material.pbr_metallic_roughness.metallic_factor, 1.0);
+    assert_eq!(material.alpha_cutoff, 0.5);
+    assert!(!material.double_sided);
+
+    let node = Node::<Extensions>::default();
+    assert!(node.children.is_empty() && node.mesh.is_none() && node.matrix.is_none());
+}
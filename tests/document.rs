@@ -0,0 +1,101 @@
+use goth_gltf::document::Document;
+use goth_gltf::{AnyExtensions, Gltf};
+
+fn gltf() -> Gltf<AnyExtensions> {
+    Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scene": 0,
+            "scenes": [{"nodes": [0, 9]}],
+            "nodes": [
+                {"children": [1, 2, 9], "skin": 0},
+                {"mesh": 0},
+                {"mesh": 4, "camera": 0}
+            ],
+            "cameras": [{"type": "perspective", "perspective": {"yfov": 1, "znear": 0.1}}],
+            "skins": [{"joints": [1, 9, 2], "inverseBindMatrices": 0}],
+            "meshes": [{"primitives": [
+                {"attributes": {"POSITION": 0}},
+                {"attributes": {"POSITION": 0, "NORMAL": 1}, "indices": 2, "material": 0}
+            ]}],
+            "materials": [{
+                "pbrMetallicRoughness": {"baseColorTexture": {"index": 0}},
+                "normalTexture": {"index": 1},
+                "emissiveTexture": {"index": 5}
+            }],
+            "textures": [{"source": 0, "sampler": 0}, {"source": 3}],
+            "samplers": [{"magFilter": 9728}],
+            "images": [{"bufferView": 1, "mimeType": "image/png"}],
+            "buffers": [{"byteLength": 8}],
+            "bufferViews": [{"buffer": 0, "byteLength": 4}, {"buffer": 0, "byteOffset": 4, "byteLength": 4}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR"},
+                {"componentType": 5126, "count": 1, "type": "SCALAR"}
+            ]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn the_node_hierarchy_is_resolved() {
+    let gltf = gltf();
+    let document = Document::new(&gltf);
+
+    let scene = document.default_scene().unwrap();
+    // Nodes that don't exist are skipped.
+    let roots: Vec<_> = scene.nodes().map(|node| node.index()).collect();
+    assert_eq!(roots, [0]);
+
+    let root = document.node(0).unwrap();
+    let children: Vec<_> = root.children().map(|node| node.index()).collect();
+    assert_eq!(children, [1, 2]);
+
+    let skin = root.skin().unwrap();
+    let joints: Vec<_> = skin.joints().map(|node| node.index()).collect();
+    assert_eq!(joints, [1, 2]);
+    assert_eq!(skin.inverse_bind_matrices().unwrap().index(), 0);
+    assert!(skin.skeleton().is_none());
+
+    // A mesh index that's out of bounds.
+    let node = document.node(2).unwrap();
+    assert!(node.mesh().is_none());
+    assert_eq!(node.camera().unwrap().index(), 0);
+    assert!(document.node(3).is_none());
+}
+
+#[test]
+fn primitives_and_materials_are_resolved() {
+    let gltf = gltf();
+    let document = Document::new(&gltf);
+
+    let mesh = document.node(1).unwrap().mesh().unwrap();
+    assert_eq!(mesh.primitives().len(), 2);
+
+    let primitive = mesh.primitives().nth(1).unwrap();
+    // Primitive indices are within the mesh.
+    assert_eq!(primitive.index(), 1);
+    assert_eq!(primitive.position().unwrap().index(), 0);
+    assert!(primitive.normal().unwrap().buffer_view().is_none());
+    assert!(primitive.tangent().is_none());
+    // The indices accessor is out of bounds.
+    assert!(primitive.indices().is_none());
+    assert!(mesh.primitives().next().unwrap().material().is_none());
+
+    let material = primitive.material().unwrap();
+    let texture = material.base_color_texture().unwrap();
+    assert_eq!(texture.index(), 0);
+    assert!(texture.sampler().is_some());
+
+    let image = texture.image().unwrap();
+    assert_eq!(image.mime_type.as_deref(), Some("image/png"));
+    let buffer_view = image.buffer_view().unwrap();
+    assert_eq!(buffer_view.byte_offset, 4);
+    assert_eq!(buffer_view.buffer().unwrap().byte_length, 8);
+
+    // The normal texture's image and the emissive texture don't exist.
+    assert!(material.normal_texture().unwrap().image().is_none());
+    assert!(material.emissive_texture().is_none());
+    assert!(material.metallic_roughness_texture().is_none());
+    assert!(material.occlusion_texture().is_none());
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::primitive_reader::{DracoDecoder, DracoMesh, Error, Indices, PrimitiveReader};
+use goth_gltf::Gltf;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+// Stands in for a real decoder, checking it's given the compressed buffer view.
+#[derive(Default)]
+struct MockDecoder {
+    calls: Cell<usize>,
+}
+
+impl DracoDecoder for MockDecoder {
+    fn decode(&self, bytes: &[u8]) -> Result<DracoMesh, Box<dyn std::error::Error + Send + Sync>> {
+        self.calls.set(self.calls.get() + 1);
+
+        if bytes != b"draco" {
+            return Err("not draco data".into());
+        }
+
+        Ok(DracoMesh {
+            indices: vec![0, 1, 2],
+            attributes: HashMap::from([
+                (0, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]),
+                (1, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+            ]),
+        })
+    }
+}
+
+fn compressed() -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let gltf = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 5}],
+            "bufferViews": [{"buffer": 0, "byteLength": 5}],
+            "accessors": [
+                {"componentType": 5126, "count": 3, "type": "VEC3"},
+                {"componentType": 5126, "count": 3, "type": "VEC2"},
+                {"componentType": 5125, "count": 3, "type": "SCALAR"}
+            ],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 0, "TEXCOORD_0": 1},
+                "indices": 2,
+                "extensions": {"KHR_draco_mesh_compression": {
+                    "bufferView": 0,
+                    "attributes": {"POSITION": 0, "TEXCOORD_0": 1}
+                }}
+            }]}]
+        }"#,
+    )
+    .unwrap();
+
+    (gltf, HashMap::from([(0, b"draco".to_vec())]))
+}
+
+#[test]
+fn compressed_primitives_are_read_with_the_decoder() {
+    let (gltf, buffer_views) = compressed();
+    let decoder = MockDecoder::default();
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views)
+        .with_draco_decoder(&decoder);
+
+    assert_eq!(
+        &reader.read_positions().unwrap().unwrap()[..],
+        &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    assert_eq!(
+        &reader.read_uvs().unwrap().unwrap()[..],
+        &[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]
+    );
+    match reader.read_indices().unwrap().unwrap() {
+        Indices::U32(indices) => assert_eq!(&indices[..], &[0, 1, 2]),
+        _ => panic!("expected 32-bit indices"),
+    }
+
+    // The data is only decoded once per reader.
+    assert_eq!(decoder.calls.get(), 1);
+}
+
+#[test]
+fn compressed_primitives_need_a_decoder() {
+    let (gltf, buffer_views) = compressed();
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+
+    assert!(matches!(
+        reader.read_positions(),
+        Err(Error::DracoDecoderMissing)
+    ));
+    assert!(matches!(
+        reader.read_indices(),
+        Err(Error::DracoDecoderMissing)
+    ));
+}
+
+#[test]
+fn decoding_errors_are_returned() {
+    let (gltf, _) = compressed();
+    let buffer_views = HashMap::from([(0, b"other".to_vec())]);
+    let decoder = MockDecoder::default();
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views)
+        .with_draco_decoder(&decoder);
+
+    match reader.read_positions() {
+        Err(Error::DracoDecode(error)) => assert_eq!(error.to_string(), "not draco data"),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
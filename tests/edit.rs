@@ -0,0 +1,152 @@
+use goth_gltf::edit::EditError;
+use goth_gltf::{default_extensions::Extensions, Gltf};
+
+// 0 ─┬─ 1 ── 2
+//    └─ 3
+// 4 (joint of skin 0, which has 3 as its skeleton)
+// 5
+fn document() -> Gltf<Extensions> {
+    Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scenes": [{"nodes": [0, 4]}, {"nodes": [5, 0]}, {"nodes": [5]}],
+            "nodes": [
+                {"children": [1, 3]},
+                {"children": [2], "mesh": 1},
+                {"mesh": 2},
+                {"mesh": 0},
+                {},
+                {"mesh": 2}
+            ],
+            "meshes": [
+                {"primitives": [{"attributes": {}, "material": 1}]},
+                {"primitives": [{"attributes": {}, "material": 0}]},
+                {"primitives": [{"attributes": {}, "material": 2}, {"attributes": {}}]}
+            ],
+            "materials": [{}, {}, {}],
+            "skins": [{"joints": [4, 5], "skeleton": 3}],
+            "animations": [{
+                "samplers": [{"input": 0, "output": 1}],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 1, "path": "rotation"}},
+                    {"sampler": 0, "target": {"node": 3, "path": "rotation"}},
+                    {"sampler": 0, "target": {"node": 5, "path": "rotation"}}
+                ]
+            }]
+        }"#,
+    )
+    .unwrap()
+}
+
+fn channel_targets(gltf: &Gltf<Extensions>) -> Vec<Option<usize>> {
+    gltf.animations[0]
+        .channels
+        .iter()
+        .map(|channel| channel.target.node)
+        .collect()
+}
+
+#[test]
+fn removing_a_root_node_puts_its_children_in_its_place() {
+    let mut gltf = document();
+    let removed = gltf.remove_node(0).unwrap();
+
+    assert_eq!(removed.children, [1, 3]);
+    assert_eq!(gltf.nodes.len(), 5);
+    assert_eq!(gltf.nodes[0].children, [1]);
+    assert_eq!(gltf.nodes[0].mesh, Some(1));
+
+    assert_eq!(gltf.scenes[0].nodes, [0, 2, 3]);
+    assert_eq!(gltf.scenes[1].nodes, [4, 0, 2]);
+    assert_eq!(gltf.scenes[2].nodes, [4]);
+
+    assert_eq!(gltf.skins[0].joints, [3, 4]);
+    assert_eq!(gltf.skins[0].skeleton, Some(2));
+    assert_eq!(channel_targets(&gltf), [Some(0), Some(2), Some(4)]);
+}
+
+#[test]
+fn removing_a_nested_node_adds_its_children_to_the_scenes_it_was_in() {
+    let mut gltf = document();
+    gltf.remove_node(1).unwrap();
+
+    assert_eq!(gltf.nodes[0].children, [2]);
+    assert_eq!(gltf.nodes[1].children, Vec::<usize>::new());
+    assert_eq!(gltf.scenes[0].nodes, [0, 3, 1]);
+    assert_eq!(gltf.scenes[1].nodes, [4, 0, 1]);
+    assert_eq!(gltf.scenes[2].nodes, [4]);
+
+    // The channel that targeted the removed node goes with it.
+    assert_eq!(channel_targets(&gltf), [Some(2), Some(4)]);
+    assert_eq!(gltf.skins[0].skeleton, Some(2));
+}
+
+#[test]
+fn removing_the_skeleton_root_clears_it() {
+    let mut gltf = document();
+    gltf.remove_node(3).unwrap();
+
+    assert_eq!(gltf.skins[0].skeleton, None);
+    assert_eq!(gltf.skins[0].joints, [3, 4]);
+    assert_eq!(gltf.nodes[0].children, [1]);
+}
+
+#[test]
+fn joints_and_missing_nodes_cant_be_removed() {
+    let mut gltf = document();
+
+    assert_eq!(
+        gltf.remove_node(4).unwrap_err(),
+        EditError::NodeIsJoint { node: 4, skin: 0 }
+    );
+    assert_eq!(
+        gltf.remove_node(6).unwrap_err(),
+        EditError::NodeOutOfBounds(6)
+    );
+    assert_eq!(gltf.nodes.len(), 6);
+}
+
+#[test]
+fn removing_a_mesh_shifts_node_meshes() {
+    let mut gltf = document();
+    gltf.remove_mesh(1).unwrap();
+
+    let meshes: Vec<_> = gltf.nodes.iter().map(|node| node.mesh).collect();
+    assert_eq!(meshes, [None, None, Some(1), Some(0), None, Some(1)]);
+    assert_eq!(
+        gltf.remove_mesh(2).unwrap_err(),
+        EditError::MeshOutOfBounds(2)
+    );
+}
+
+#[test]
+fn removing_a_material_shifts_primitive_materials() {
+    let mut gltf = document();
+    gltf.remove_material(1).unwrap();
+
+    let materials: Vec<_> = gltf
+        .meshes
+        .iter()
+        .flat_map(|mesh| &mesh.primitives)
+        .map(|primitive| primitive.material)
+        .collect();
+    assert_eq!(materials, [None, Some(0), Some(1), None]);
+}
+
+#[test]
+fn reparenting_moves_nodes_out_of_scenes_and_rejects_cycles() {
+    let mut gltf = document();
+
+    assert_eq!(
+        gltf.reparent_node(0, Some(2)).unwrap_err(),
+        EditError::WouldCreateCycle { node: 0, parent: 2 }
+    );
+
+    gltf.reparent_node(5, Some(2)).unwrap();
+    assert_eq!(gltf.nodes[2].children, [5]);
+    assert_eq!(gltf.scenes[1].nodes, [0]);
+    assert!(gltf.scenes[2].nodes.is_empty());
+
+    gltf.reparent_node(3, None).unwrap();
+    assert_eq!(gltf.nodes[0].children, [1]);
+}
@@ -0,0 +1,229 @@
+#![cfg(any(feature = "omi", feature = "vrm", feature = "mpeg"))]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::Gltf;
+
+fn parse(json: &str) -> Gltf<Extensions> {
+    Gltf::from_json_string(json).unwrap()
+}
+
+#[test]
+#[cfg(feature = "omi")]
+fn omi_physics_extensions_are_parsed() {
+    use goth_gltf::extensions::{PhysicsMotionType, PhysicsShapeType};
+
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "extensions": {"OMI_physics_shape": {"shapes": [
+                {"type": "box", "box": {"size": [1, 2, 3]}},
+                {"type": "sphere", "sphere": {}},
+                {"type": "trimesh", "trimesh": {"mesh": 0}}
+            ]}},
+            "nodes": [
+                {"extensions": {"OMI_physics_body": {
+                    "motion": {"type": "dynamic", "mass": 2},
+                    "collider": {"shape": 0}
+                }}},
+                {"extensions": {"OMI_physics_body": {"trigger": {"nodes": [0]}}}}
+            ]
+        }"#,
+    );
+
+    let shapes = &gltf.extensions.omi_physics_shape.as_ref().unwrap().shapes;
+    assert_eq!(shapes[0].ty, PhysicsShapeType::Box);
+    assert_eq!(shapes[0].box_shape.unwrap().size, [1.0, 2.0, 3.0]);
+    assert_eq!(shapes[1].sphere.unwrap().radius, 0.5);
+    assert_eq!(shapes[2].trimesh.unwrap().mesh, 0);
+
+    let body = gltf.nodes[0].extensions.omi_physics_body.as_ref().unwrap();
+    let motion = body.motion.unwrap();
+    assert_eq!(motion.ty, PhysicsMotionType::Dynamic);
+    assert_eq!(motion.mass, 2.0);
+    assert_eq!(motion.gravity_factor, 1.0);
+    assert_eq!(body.collider.unwrap().shape, Some(0));
+
+    let trigger = gltf.nodes[1].extensions.omi_physics_body.as_ref().unwrap();
+    assert_eq!(
+        trigger.trigger.as_ref().unwrap().nodes.as_deref(),
+        Some(&[0][..])
+    );
+}
+
+#[test]
+#[cfg(feature = "omi")]
+fn omi_colliders_are_parsed() {
+    use goth_gltf::extensions::ColliderType;
+
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "extensions": {"OMI_collider": {"colliders": [
+                {"type": "capsule", "radius": 0.25, "height": 1, "isTrigger": true}
+            ]}},
+            "nodes": [{"extensions": {"OMI_collider": {"collider": 0}}}]
+        }"#,
+    );
+
+    let collider = &gltf.extensions.omi_collider.as_ref().unwrap().colliders[0];
+    assert_eq!(collider.ty, ColliderType::Capsule);
+    assert!(collider.is_trigger);
+    assert_eq!(collider.radius, Some(0.25));
+    assert_eq!(gltf.nodes[0].extensions.omi_collider.unwrap().collider, 0);
+}
+
+#[test]
+#[cfg(feature = "vrm")]
+fn vrmc_vrm_is_parsed() {
+    use goth_gltf::extensions::{
+        VrmCommercialUsage, VrmExpressionOverride, VrmFirstPersonType, VrmLookAtType,
+        VrmMaterialColorType,
+    };
+
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "extensions": {"VRMC_vrm": {
+                "specVersion": "1.0",
+                "meta": {
+                    "name": "Avatar",
+                    "authors": ["someone"],
+                    "licenseUrl": "https://vrm.dev/licenses/1.0/",
+                    "commercialUsage": "corporation"
+                },
+                "humanoid": {"humanBones": {"hips": {"node": 1}, "head": {"node": 4}}},
+                "firstPerson": {"meshAnnotations": [{"node": 2, "type": "thirdPersonOnly"}]},
+                "lookAt": {"type": "expression", "rangeMapVerticalUp": {"outputScale": 1}},
+                "expressions": {
+                    "preset": {"blink": {
+                        "morphTargetBinds": [{"node": 2, "index": 0, "weight": 1}],
+                        "isBinary": true,
+                        "overrideMouth": "blend"
+                    }},
+                    "custom": {"glow": {
+                        "materialColorBinds": [
+                            {"material": 0, "type": "emissionColor", "targetValue": [1, 1, 1, 1]}
+                        ]
+                    }}
+                }
+            }}
+        }"#,
+    );
+
+    let vrm = gltf.extensions.vrmc_vrm.as_ref().unwrap();
+    assert_eq!(vrm.spec_version, "1.0");
+    assert_eq!(vrm.meta.name, "Avatar");
+    assert_eq!(vrm.meta.authors, ["someone"]);
+    assert_eq!(vrm.meta.commercial_usage, VrmCommercialUsage::Corporation);
+    assert!(!vrm.meta.allow_redistribution);
+
+    assert_eq!(vrm.humanoid.bone("hips"), Some(1));
+    assert_eq!(vrm.humanoid.bone("head"), Some(4));
+    assert_eq!(vrm.humanoid.bone("leftFoot"), None);
+
+    let annotation = vrm.first_person.as_ref().unwrap().mesh_annotations[0];
+    assert_eq!(annotation.ty, VrmFirstPersonType::ThirdPersonOnly);
+
+    let look_at = vrm.look_at.unwrap();
+    assert_eq!(look_at.ty, VrmLookAtType::Expression);
+    let range_map = look_at.range_map_vertical_up.unwrap();
+    assert_eq!(range_map.input_max_value, 90.0);
+    assert_eq!(range_map.output_scale, 1.0);
+
+    let expressions = vrm.expressions.as_ref().unwrap();
+    let blink = &expressions.preset["blink"];
+    assert_eq!(blink.morph_target_binds[0].node, 2);
+    assert!(blink.is_binary);
+    assert_eq!(blink.override_mouth, VrmExpressionOverride::Blend);
+    assert_eq!(blink.override_blink, VrmExpressionOverride::None);
+    assert_eq!(
+        expressions.custom["glow"].material_color_binds[0].ty,
+        VrmMaterialColorType::EmissionColor
+    );
+}
+
+#[test]
+#[cfg(feature = "vrm")]
+fn vrmc_spring_bone_is_parsed() {
+    let gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "extensions": {"VRMC_springBone": {
+                "specVersion": "1.0",
+                "colliders": [
+                    {"node": 3, "shape": {"sphere": {"radius": 0.1}}},
+                    {"node": 4, "shape": {"capsule": {"radius": 0.05, "tail": [0, 0.2, 0]}}}
+                ],
+                "colliderGroups": [{"name": "head", "colliders": [0, 1]}],
+                "springs": [{
+                    "name": "hair",
+                    "joints": [{"node": 5, "hitRadius": 0.02}, {"node": 6, "stiffness": 2}],
+                    "colliderGroups": [0]
+                }]
+            }}
+        }"#,
+    );
+
+    let spring_bone = gltf.extensions.vrmc_spring_bone.as_ref().unwrap();
+    assert_eq!(spring_bone.colliders[0].node, 3);
+    assert_eq!(spring_bone.colliders[0].shape.sphere.unwrap().radius, 0.1);
+    assert!(spring_bone.colliders[0].shape.capsule.is_none());
+    assert_eq!(
+        spring_bone.colliders[1].shape.capsule.unwrap().tail,
+        [0.0, 0.2, 0.0]
+    );
+    assert_eq!(spring_bone.collider_groups[0].colliders, [0, 1]);
+
+    let spring = &spring_bone.springs[0];
+    assert_eq!(spring.name.as_deref(), Some("hair"));
+    assert_eq!(spring.collider_groups, [0]);
+    assert_eq!(spring.joints[0].hit_radius, 0.02);
+    assert_eq!(spring.joints[0].stiffness, 1.0);
+    assert_eq!(spring.joints[0].gravity_dir, [0.0, -1.0, 0.0]);
+    assert_eq!(spring.joints[0].drag_force, 0.5);
+    assert_eq!(spring.joints[1].stiffness, 2.0);
+}
+
+#[test]
+#[cfg(feature = "mpeg")]
+fn mpeg_media_and_texture_video_are_parsed() {
+    use goth_gltf::extensions::VideoFormat;
+
+    let gltf = parse(
+        r##"{
+            "asset": {"version": "2.0"},
+            "extensions": {"MPEG_media": {"media": [{
+                "name": "stream",
+                "loop": true,
+                "alternatives": [{
+                    "mimeType": "video/mp4",
+                    "uri": "video.mp4",
+                    "tracks": [{"track": "#track_ID=1", "codecs": "avc1.42E01E"}]
+                }]
+            }]}},
+            "textures": [{"extensions": {"MPEG_texture_video": {
+                "accessor": 0,
+                "width": 1920,
+                "height": 1080,
+                "format": "RGBA"
+            }}}]
+        }"##,
+    );
+
+    let media = &gltf.extensions.mpeg_media.as_ref().unwrap().media[0];
+    assert_eq!(media.name.as_deref(), Some("stream"));
+    assert!(media.looping);
+    assert!(media.autoplay);
+    assert_eq!(media.start_time, 0.0);
+    assert_eq!(media.alternatives[0].mime_type, "video/mp4");
+    assert_eq!(media.alternatives[0].uri, "video.mp4");
+    assert_eq!(
+        media.alternatives[0].tracks[0].codecs.as_deref(),
+        Some("avc1.42E01E")
+    );
+
+    let video = gltf.textures[0].extensions.mpeg_texture_video.unwrap();
+    assert_eq!(video.accessor, 0);
+    assert_eq!((video.width, video.height), (1920, 1080));
+    assert_eq!(video.format, VideoFormat::Rgba);
+}
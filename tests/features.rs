@@ -0,0 +1,73 @@
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::Gltf;
+
+// Uses extensions from several feature groups, on the root, nodes, materials and texture infos.
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "extensionsUsed": ["KHR_lights_punctual", "KHR_materials_ior", "KHR_texture_transform"],
+    "extensions": {"KHR_lights_punctual": {"lights": [{"type": "point", "intensity": 2}]}},
+    "nodes": [{"extensions": {"KHR_lights_punctual": {"light": 0}}}],
+    "materials": [{
+        "pbrMetallicRoughness": {"baseColorTexture": {
+            "index": 0,
+            "extensions": {"KHR_texture_transform": {"rotation": 1.5}}
+        }},
+        "extensions": {"KHR_materials_ior": {"ior": 1.3}}
+    }]
+}"#;
+
+#[test]
+fn documents_parse_whichever_extensions_are_enabled() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+
+    assert_eq!(gltf.nodes.len(), 1);
+    assert_eq!(gltf.materials.len(), 1);
+    assert_eq!(gltf.extensions_used.len(), 3);
+}
+
+#[test]
+#[cfg(feature = "khr_lights")]
+fn enabled_extensions_are_parsed() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+
+    let lights = &gltf.extensions.khr_lights_punctual.as_ref().unwrap().lights;
+    assert_eq!(lights[0].intensity, 2.0);
+    assert_eq!(
+        gltf.nodes[0]
+            .extensions
+            .khr_lights_punctual
+            .as_ref()
+            .unwrap()
+            .light,
+        0
+    );
+}
+
+#[test]
+#[cfg(all(feature = "khr_materials", feature = "khr_texture_transform"))]
+fn enabled_material_extensions_are_parsed() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+    let material = &gltf.materials[0];
+
+    assert_eq!(material.extensions.khr_materials_ior.unwrap().ior, 1.3);
+    let texture = material
+        .pbr_metallic_roughness
+        .base_color_texture
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        texture.extensions.khr_texture_transform.unwrap().rotation,
+        1.5
+    );
+}
+
+// Disabled extensions are skipped, and aren't written back out.
+#[test]
+#[cfg(not(any(feature = "khr_lights", feature = "khr_materials")))]
+fn disabled_extensions_are_skipped() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+    let written = gltf.to_json_string();
+
+    assert!(!written.contains(r#""lights""#));
+    assert!(!written.contains(r#""ior""#));
+}
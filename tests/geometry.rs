@@ -0,0 +1,154 @@
+use goth_gltf::geometry::to_list_indices;
+use goth_gltf::PrimitiveMode;
+
+#[test]
+fn triangle_strips_keep_a_consistent_winding() {
+    // 0 ─ 2 ─ 4
+    // │ ╲ │ ╲ │
+    // 1 ─ 3 ─ 5
+    let triangles = to_list_indices(PrimitiveMode::TriangleStrip, &[0, 1, 2, 3, 4, 5]);
+
+    // Every triangle has the winding of the first one, 0 → 1 → 2.
+    assert_eq!(triangles, [0, 1, 2, 2, 1, 3, 2, 3, 4, 4, 3, 5]);
+}
+
+#[test]
+fn degenerate_strip_triangles_are_removed() {
+    // Two strips joined by repeating the last index of the first and the first of the second.
+    let triangles = to_list_indices(
+        PrimitiveMode::TriangleStrip,
+        &[0, 1, 2, 3, 3, 4, 4, 5, 6, 7],
+    );
+
+    assert_eq!(triangles, [0, 1, 2, 2, 1, 3, 4, 5, 6, 6, 5, 7]);
+    assert!(to_list_indices(PrimitiveMode::TriangleStrip, &[0, 1]).is_empty());
+}
+
+#[test]
+fn triangle_fans_share_the_first_vertex() {
+    let triangles = to_list_indices(PrimitiveMode::TriangleFan, &[0, 1, 2, 3, 4]);
+
+    // Each triangle is wound the same way as the first, 1 → 2 → 0.
+    assert_eq!(triangles, [1, 2, 0, 2, 3, 0, 3, 4, 0]);
+    assert!(to_list_indices(PrimitiveMode::TriangleFan, &[0, 1]).is_empty());
+    assert!(to_list_indices(PrimitiveMode::TriangleFan, &[]).is_empty());
+}
+
+#[cfg(not(feature = "no_std"))]
+fn assert_close(actual: &[[f32; 3]], expected: &[[f32; 3]]) {
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(expected) {
+        for i in 0..3 {
+            assert!((actual[i] - expected[i]).abs() < 1e-6, "{:?}", actual);
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn flat_normals_split_every_triangle() {
+    use goth_gltf::geometry::{flat_normals, PrimitiveGeometry};
+
+    // A square in the xy plane with counter-clockwise triangles, a degenerate triangle and one
+    // that's out of range.
+    let positions = [
+        [0.0, 0.0, 0.0],
+        [2.0, 0.0, 0.0],
+        [2.0, 2.0, 0.0],
+        [0.0, 2.0, 0.0],
+    ];
+    let indices = [0, 1, 2, 0, 2, 3, 0, 1, 1, 0, 1, 9];
+
+    let flat = flat_normals(&PrimitiveGeometry {
+        positions: &positions,
+        indices: Some(&indices),
+        ..Default::default()
+    });
+
+    assert_eq!(flat.source_vertices, [0, 1, 2, 0, 2, 3, 0, 1, 1]);
+    assert_eq!(
+        flat.positions,
+        [0, 1, 2, 0, 2, 3, 0, 1, 1].map(|vertex| positions[vertex])
+    );
+    assert_close(
+        &flat.normals,
+        &[[0.0, 0.0, 1.0]; 6]
+            .into_iter()
+            .chain([[0.0; 3]; 3])
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn smooth_normals_average_the_triangles() {
+    use goth_gltf::geometry::{smooth_normals, PrimitiveGeometry};
+
+    // Two triangles folded at a right angle along the x axis, and a vertex that isn't used.
+    let positions = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, -1.0],
+        [5.0, 5.0, 5.0],
+    ];
+    let indices = [0, 1, 2, 0, 3, 1];
+
+    let normals = smooth_normals(&PrimitiveGeometry {
+        positions: &positions,
+        indices: Some(&indices),
+        ..Default::default()
+    });
+
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    assert_close(
+        &normals,
+        &[
+            [0.0, -half, half],
+            [0.0, -half, half],
+            [0.0, 0.0, 1.0],
+            [0.0, -1.0, 0.0],
+            [0.0; 3],
+        ],
+    );
+}
+
+#[cfg(feature = "mikktspace")]
+#[test]
+fn tangents_follow_the_texture_coordinates() {
+    use goth_gltf::geometry::{generate_tangents, PrimitiveGeometry};
+
+    // A square facing +z, with u increasing along x.
+    let positions = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+    ];
+    let normals = [[0.0, 0.0, 1.0]; 4];
+    let uvs = [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+    let indices = [0, 1, 2, 0, 2, 3];
+
+    let mut geometry = PrimitiveGeometry {
+        positions: &positions,
+        indices: Some(&indices),
+        normals: Some(&normals),
+        uvs: Some(&uvs),
+        ..Default::default()
+    };
+
+    let tangents = generate_tangents(&geometry).unwrap();
+    assert_eq!(tangents.len(), 4);
+    for [x, y, z, w] in tangents {
+        assert!((x - 1.0).abs() < 1e-6 && y.abs() < 1e-6 && z.abs() < 1e-6);
+        assert_eq!(w.abs(), 1.0);
+    }
+
+    // Every vertex needs a normal and uv.
+    geometry.uvs = Some(&uvs[..3]);
+    assert_eq!(generate_tangents(&geometry), None);
+
+    geometry.uvs = Some(&uvs);
+    geometry.indices = Some(&[0, 1, 4]);
+    assert_eq!(generate_tangents(&geometry), None);
+}
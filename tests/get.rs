@@ -0,0 +1,51 @@
+use goth_gltf::{default_extensions::Extensions, Gltf, IndexError};
+
+fn parse() -> Gltf<Extensions> {
+    Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scene": 0,
+            "scenes": [{"nodes": [0]}, {"nodes": [1]}],
+            "nodes": [{"mesh": 0}, {"camera": 0}],
+            "meshes": [{"primitives": [{"attributes": {}}]}],
+            "cameras": [{"type": "perspective", "perspective": {"yfov": 1, "znear": 0.1}}]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn getters_return_items_in_range() {
+    let gltf = parse();
+
+    assert_eq!(gltf.node(1).unwrap().camera, Some(0));
+    assert_eq!(gltf.try_node(0).unwrap().mesh, Some(0));
+    assert_eq!(gltf.scene_at(1).unwrap().nodes, [1]);
+    assert_eq!(gltf.try_mesh(0).unwrap().primitives.len(), 1);
+    assert!(gltf.camera(0).is_some());
+}
+
+#[test]
+fn getters_name_indices_out_of_range() {
+    let gltf = parse();
+
+    assert!(gltf.node(2).is_none());
+    assert!(gltf.accessor(0).is_none());
+
+    let error = gltf.try_node(2).unwrap_err();
+    assert_eq!(
+        error,
+        IndexError {
+            array: "nodes",
+            index: 2,
+            len: 2,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "nodes[2] is out of range, as there are only 2"
+    );
+
+    assert_eq!(gltf.try_scene_at(5).unwrap_err().array, "scenes");
+    assert_eq!(gltf.try_buffer_view(0).unwrap_err().len, 0);
+}
@@ -0,0 +1,138 @@
+use goth_gltf::{default_extensions::Extensions, split_glb, GlbError, Gltf};
+
+fn glb(chunks: &[(u32, u32, &[u8])]) -> Vec<u8> {
+    let mut bytes = b"glTF".to_vec();
+    bytes.extend_from_slice(&2_u32.to_le_bytes());
+    bytes.extend_from_slice(&0_u32.to_le_bytes());
+
+    for &(length, ty, data) in chunks {
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&ty.to_le_bytes());
+        bytes.extend_from_slice(data);
+    }
+
+    bytes
+}
+
+const JSON: &[u8] = br#"{"asset":{"version":"2.0"}}"#;
+
+#[test]
+fn chunk_lengths_past_the_end_of_the_file_are_rejected() {
+    for length in [JSON.len() as u32 + 1, u32::MAX - 7, u32::MAX] {
+        let bytes = glb(&[(length, 0x4E4F534A, JSON)]);
+
+        assert!(matches!(
+            Gltf::<Extensions>::from_bytes(&bytes),
+            Err(GlbError::ChunkOutOfBounds { offset: 12, .. })
+        ));
+    }
+
+    let bytes = glb(&[
+        (JSON.len() as u32, 0x4E4F534A, JSON),
+        (u32::MAX, 0x004E4942, &[0; 4]),
+    ]);
+    assert!(matches!(
+        Gltf::<Extensions>::from_bytes(&bytes),
+        Err(GlbError::ChunkOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn unknown_chunks_are_skipped() {
+    let bytes = glb(&[
+        (JSON.len() as u32, 0x4E4F534A, JSON),
+        (3, 0x12345678, &[1, 2, 3]),
+        (4, 0x004E4942, &[4, 5, 6, 7]),
+    ]);
+
+    let (gltf, binary) = Gltf::<Extensions>::from_bytes(&bytes).unwrap();
+    assert_eq!(gltf.asset.version, "2.0");
+    assert_eq!(binary, Some(&[4, 5, 6, 7][..]));
+}
+
+#[test]
+fn written_glbs_read_back_the_same() {
+    let gltf = Gltf::<Extensions>::from_json_string(
+        r#"{
+            "asset": {"version": "2.0", "generator": "test"},
+            "buffers": [{"byteLength": 6}],
+            "bufferViews": [{"buffer": 0, "byteLength": 6}],
+            "nodes": [{"name": "node", "translation": [1, 2, 3]}]
+        }"#,
+    )
+    .unwrap();
+    let binary = [1, 2, 3, 4, 5, 6];
+
+    let bytes = gltf.to_glb(Some(&binary)).unwrap();
+    assert_eq!(bytes.len() % 4, 0);
+    assert_eq!(
+        u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize,
+        bytes.len()
+    );
+
+    let (read, read_binary) = Gltf::<Extensions>::from_bytes(&bytes).unwrap();
+    assert_eq!(read.to_json_string(), gltf.to_json_string());
+    assert_eq!(read.nodes[0].translation, Some([1.0, 2.0, 3.0]));
+    // The chunk is padded with zeros, and the buffer's length says how much of it is used.
+    assert_eq!(read_binary, Some(&[1, 2, 3, 4, 5, 6, 0, 0][..]));
+
+    let bytes = gltf.to_glb(None).unwrap();
+    let (read, read_binary) = Gltf::<Extensions>::from_bytes(&bytes).unwrap();
+    assert_eq!(read.to_json_string(), gltf.to_json_string());
+    assert_eq!(read_binary, None);
+}
+
+#[test]
+fn binary_chunks_need_a_buffer_without_a_uri() {
+    for buffers in [
+        r#"[]"#,
+        r#"[{"byteLength": 4, "uri": "external.bin"}]"#,
+        r#"[{"byteLength": 4, "uri": "external.bin"}, {"byteLength": 4}]"#,
+    ] {
+        let gltf = Gltf::<Extensions>::from_json_string(&format!(
+            r#"{{"asset": {{"version": "2.0"}}, "buffers": {}}}"#,
+            buffers
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            gltf.to_glb(Some(&[0; 4])),
+            Err(GlbError::UnreferencedBinaryChunk)
+        ));
+        // Without a binary chunk, every buffer is external.
+        assert!(gltf.to_glb(None).is_ok());
+    }
+}
+
+#[test]
+fn split_glbs_point_the_first_buffer_at_the_binary_uri() {
+    let json = br#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4,"extras":{"a":1}},{"byteLength":2,"uri":"other.bin"}],"extensions":{"VENDOR_unknown":{"value":[1,2]}}}"#;
+    let mut padded = json.to_vec();
+    padded.resize(padded.len().next_multiple_of(4), b' ');
+    let bytes = glb(&[
+        (padded.len() as u32, 0x4E4F534A, &padded),
+        (4, 0x004E4942, &[1, 2, 3, 4]),
+    ]);
+
+    let split = split_glb(&bytes, "scene \"1\".bin").unwrap();
+    assert_eq!(split.binary_chunk, Some(&[1, 2, 3, 4][..]));
+
+    let gltf = Gltf::<Extensions>::from_json_string(&split.json).unwrap();
+    assert_eq!(gltf.buffers[0].uri.as_deref(), Some("scene \"1\".bin"));
+    assert_eq!(gltf.buffers[1].uri.as_deref(), Some("other.bin"));
+    // Nothing that isn't understood is dropped.
+    assert!(split.json.contains(r#""extras":{"a":1}"#));
+    assert!(split.json.contains(r#""VENDOR_unknown":{"value":[1,2]}"#));
+}
+
+#[test]
+fn split_glbs_without_a_binary_chunk_keep_their_json() {
+    let bytes = glb(&[(JSON.len() as u32, 0x4E4F534A, JSON)]);
+    let split = split_glb(&bytes, "scene.bin").unwrap();
+    assert_eq!(split.json.as_bytes(), JSON);
+    assert_eq!(split.binary_chunk, None);
+
+    let split = split_glb(JSON, "scene.bin").unwrap();
+    assert_eq!(split.json.as_bytes(), JSON);
+    assert_eq!(split.binary_chunk, None);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::primitive_reader::{Indices, PrimitiveReader};
+use goth_gltf::Gltf;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+// A primitive with only indices, of the given component type.
+fn indexed(component_type: u32, bytes: Vec<u8>) -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let json = format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": {0}}}],
+            "bufferViews": [{{"buffer": 0, "byteLength": {0}}}],
+            "accessors": [{{"bufferView": 0, "componentType": {1}, "count": 3, "type": "SCALAR"}}],
+            "meshes": [{{"primitives": [{{"attributes": {{}}, "indices": 0}}]}}]
+        }}"#,
+        bytes.len(),
+        component_type
+    );
+
+    (
+        Gltf::from_json_string(&json).unwrap(),
+        HashMap::from([(0, bytes)]),
+    )
+}
+
+#[test]
+fn indices_keep_their_width() {
+    let (gltf, data) = indexed(5123, [0u16, 1, 65535].map(u16::to_le_bytes).concat());
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    match reader.read_indices().unwrap().unwrap() {
+        // Tightly packed and aligned, so there's nothing to convert.
+        Indices::U16(Cow::Borrowed(indices)) => assert_eq!(indices, &[0, 1, 65535]),
+        other => panic!("expected borrowed 16-bit indices, got {:?}", other),
+    }
+
+    let (gltf, data) = indexed(5125, [0u32, 1, 70000].map(u32::to_le_bytes).concat());
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    let indices = reader.read_indices().unwrap().unwrap();
+    assert!(matches!(indices, Indices::U32(_)));
+    assert_eq!(indices.len(), 3);
+    assert_eq!(&indices.into_u32()[..], &[0, 1, 70000]);
+}
+
+#[test]
+fn byte_indices_are_widened() {
+    let (gltf, data) = indexed(5121, vec![0, 1, 255]);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    match reader.read_indices().unwrap().unwrap() {
+        Indices::U16(indices) => assert_eq!(&indices[..], &[0, 1, 255]),
+        other => panic!("expected 16-bit indices, got {:?}", other),
+    }
+
+    // Index buffer views shouldn't have a stride, but the bytes are still read one at a time.
+    let (mut gltf, data) = indexed(5121, vec![0, 9, 1, 9, 255]);
+    gltf.buffer_views[0].byte_stride = Some(2);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &data);
+
+    assert_eq!(
+        &reader.read_indices().unwrap().unwrap().into_u32()[..],
+        &[0, 1, 255]
+    );
+}
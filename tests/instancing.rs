@@ -0,0 +1,81 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::primitive_reader::{InstanceTransform, InstancingReader};
+use goth_gltf::Gltf;
+use std::collections::HashMap;
+
+// Two instances, with float translations, normalized short rotations and no scales.
+fn instanced() -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let translations = [1.0f32, 2.0, 3.0, -1.0, 0.0, 0.0].map(f32::to_le_bytes);
+    let rotations = [0i16, 0, 0, 32767, 0, 0, 32767, 0].map(i16::to_le_bytes);
+
+    let gltf = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "extensionsUsed": ["EXT_mesh_gpu_instancing"],
+            "buffers": [{"byteLength": 40}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 24, "byteLength": 16}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 1, "componentType": 5122, "normalized": true, "count": 2, "type": "VEC4"}
+            ],
+            "nodes": [
+                {"extensions": {"EXT_mesh_gpu_instancing": {"attributes": {"TRANSLATION": 0, "ROTATION": 1}}}},
+                {}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let data = HashMap::from([(0, translations.concat()), (1, rotations.concat())]);
+
+    (gltf, data)
+}
+
+#[test]
+fn transforms_are_read_with_defaults_for_missing_attributes() {
+    let (gltf, data) = instanced();
+    let reader = InstancingReader::new(&gltf, &gltf.nodes[0], &data);
+
+    assert_eq!(reader.instance_count().unwrap(), Some(2));
+    assert_eq!(
+        reader.read_transforms().unwrap().unwrap(),
+        [
+            InstanceTransform {
+                translation: [1.0, 2.0, 3.0],
+                rotation: [0.0, 0.0, 0.0, 1.0],
+                scale: [1.0; 3],
+            },
+            InstanceTransform {
+                translation: [-1.0, 0.0, 0.0],
+                rotation: [0.0, 0.0, 1.0, 0.0],
+                scale: [1.0; 3],
+            },
+        ]
+    );
+
+    let matrices = reader.read_matrices().unwrap().unwrap();
+    assert_eq!(
+        matrices[0],
+        [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 2.0, 3.0, 1.0]
+    );
+    // A half turn around z flips x and y.
+    assert_eq!(
+        matrices[1],
+        [-1.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0]
+    );
+}
+
+#[test]
+fn nodes_without_the_extension_are_not_instanced() {
+    let (gltf, data) = instanced();
+    let reader = InstancingReader::new(&gltf, &gltf.nodes[1], &data);
+
+    assert_eq!(reader.instance_count().unwrap(), None);
+    assert_eq!(reader.read_translations().unwrap(), None);
+    assert_eq!(reader.read_matrices().unwrap(), None);
+}
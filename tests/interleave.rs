@@ -0,0 +1,75 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::interleave::{VertexFormat, VertexLayout};
+use goth_gltf::primitive_reader::PrimitiveReader;
+use goth_gltf::Gltf;
+use std::collections::HashMap;
+
+#[test]
+fn vertices_are_written_in_the_layout() {
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 62}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 24, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 48, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 56, "byteLength": 6}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5121, "count": 2, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5123, "count": 3, "type": "SCALAR"}
+            ],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 0, "NORMAL": 1, "JOINTS_0": 2},
+                "indices": 3
+            }]}]
+        }"#,
+    )
+    .unwrap();
+    let floats = |values: &[f32]| -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    };
+    let buffer_views = HashMap::from([
+        (0, floats(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])),
+        (1, floats(&[0.0, 0.0, 1.0, -1.0, 0.0, 0.0])),
+        (2, vec![1, 2, 3, 4, 5, 6, 7, 8]),
+        (3, [0u16, 1, 1].map(u16::to_le_bytes).concat()),
+    ]);
+
+    let layout = VertexLayout::new()
+        .with_attribute("POSITION", VertexFormat::Float32x3)
+        .with_attribute("NORMAL", VertexFormat::Snorm8x4)
+        .with_attribute("JOINTS_0", VertexFormat::Uint16x4)
+        .with_attribute("COLOR_0", VertexFormat::Unorm8x4)
+        .with_padding(4);
+    assert_eq!(layout.stride, 32);
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+    let interleaved = reader.read_interleaved(&layout).unwrap();
+
+    assert_eq!(interleaved.vertex_count, 2);
+    assert_eq!(&interleaved.indices.unwrap().into_u32()[..], &[0, 1, 1]);
+
+    let mut expected = Vec::new();
+    for (position, normal, joints) in [
+        ([1.0, 2.0, 3.0], [0, 0, 127], [1u16, 2, 3, 4]),
+        ([4.0, 5.0, 6.0], [-127i8, 0, 0], [5, 6, 7, 8]),
+    ] {
+        expected.extend(floats(&position));
+        // Missing fourth components are filled in with one, as are missing colors.
+        expected.extend(normal.map(|value| value as u8));
+        expected.push(127);
+        expected.extend(joints.map(u16::to_le_bytes).concat());
+        expected.extend([0, 0, 0, 255]);
+        expected.extend([0; 4]);
+    }
+    assert_eq!(interleaved.vertices, expected);
+}
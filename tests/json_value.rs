@@ -0,0 +1,86 @@
+use goth_gltf::{AnyExtensions, Gltf, JsonMap, JsonValue};
+use nanoserde::DeJson;
+
+#[test]
+fn values_of_every_type_are_parsed() {
+    let value = JsonValue::deserialize_json(
+        r#"{"null": null, "bool": true, "int": -3, "float": 2.5, "text": "a\nb", "list": [1, {}]}"#,
+    )
+    .unwrap();
+
+    assert!(value.get("null").unwrap().is_null());
+    assert_eq!(value.get("bool").and_then(JsonValue::as_bool), Some(true));
+    assert_eq!(value.get("int").and_then(JsonValue::as_f64), Some(-3.0));
+    assert_eq!(value.get("int").and_then(JsonValue::as_usize), None);
+    assert_eq!(value.get("float").and_then(JsonValue::as_f64), Some(2.5));
+    assert_eq!(value.get("text").and_then(JsonValue::as_str), Some("a\nb"));
+
+    let list = value.get("list").and_then(JsonValue::as_array).unwrap();
+    assert_eq!(list[0].as_usize(), Some(1));
+    assert!(list[1].as_object().unwrap().is_empty());
+    assert!(value.get("missing").is_none());
+    assert!(list[0].get("key").is_none());
+}
+
+#[test]
+fn values_can_be_built_from_rust_types() {
+    let mut object = JsonMap::new();
+    object.insert("name".to_string(), "door".into());
+    object.insert("open".to_string(), true.into());
+    object.insert("width".to_string(), 0.8.into());
+
+    let value = JsonValue::from(vec![JsonValue::from(object), JsonValue::Null]);
+    let array = value.as_array().unwrap();
+    assert_eq!(
+        array[0].get("name").and_then(JsonValue::as_str),
+        Some("door")
+    );
+    assert_eq!(
+        array[0].get("open").and_then(JsonValue::as_bool),
+        Some(true)
+    );
+    assert!(array[1].is_null());
+}
+
+#[test]
+fn blender_custom_properties_can_be_read_from_extras() {
+    let json = r#"{
+        "asset": {"version": "2.0"},
+        "nodes": [{"extras": {"speed": 2.5, "tags": ["door", "red"]}}]
+    }"#;
+    let gltf = Gltf::<AnyExtensions>::from_json_string(json).unwrap();
+    let extras = &gltf.nodes[0].extras;
+
+    assert_eq!(extras.get("speed").and_then(JsonValue::as_f64), Some(2.5));
+    let tags: Vec<_> = extras
+        .get("tags")
+        .and_then(JsonValue::as_array)
+        .unwrap()
+        .iter()
+        .filter_map(JsonValue::as_str)
+        .collect();
+    assert_eq!(tags, ["door", "red"]);
+}
+
+#[test]
+fn deeply_nested_values_are_an_error() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+    assert!(JsonValue::deserialize_json(&nested(60)).is_ok());
+
+    let error = JsonValue::deserialize_json(&nested(100_000)).unwrap_err();
+    assert!(error.msg.contains("nesting depth"), "{}", error.msg);
+
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "nodes": [{{"extras": {}}}]}}"#,
+        nested(100_000)
+    );
+    assert!(Gltf::<AnyExtensions>::from_json_string(&json).is_err());
+}
+
+#[test]
+fn malformed_values_are_an_error() {
+    assert!(JsonValue::deserialize_json(r#"{"a": 1"#).is_err());
+    assert!(JsonValue::deserialize_json(r#"{1: 2}"#).is_err());
+    assert!(JsonValue::deserialize_json("[1, 2").is_err());
+}
@@ -0,0 +1,66 @@
+use goth_gltf::{default_extensions::Extensions, lazy::LazyGltf, Gltf};
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "extensionsUsed": ["KHR_materials_unlit"],
+    "scene": 0,
+    "scenes": [{"nodes": [0]}],
+    "nodes": [{"mesh": 0}, {"children": [0]}],
+    "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}],
+    "accessors": [{"componentType": 5126, "count": 3, "type": "VEC3"}],
+    "materials": [{"extensions": {"KHR_materials_unlit": {}}}]
+}"#;
+
+#[test]
+fn arrays_are_only_deserialized_when_used() {
+    let gltf = LazyGltf::<Extensions>::from_json_string(JSON).unwrap();
+
+    assert_eq!(gltf.asset.version, "2.0");
+    assert_eq!(gltf.extensions_used, ["KHR_materials_unlit"]);
+    assert_eq!(gltf.scene, Some(0));
+    assert_eq!(gltf.scenes[0].nodes, [0]);
+
+    assert!(!gltf.nodes.is_parsed());
+    assert_eq!(
+        gltf.nodes.json(),
+        Some(r#"[{"mesh": 0}, {"children": [0]}]"#)
+    );
+    assert_eq!(gltf.nodes.get().unwrap()[1].children, [0]);
+    assert!(gltf.nodes.is_parsed());
+    assert!(!gltf.accessors.is_parsed());
+
+    // Arrays the document doesn't have are empty.
+    assert_eq!(gltf.skins.json(), None);
+    assert!(gltf.skins.get().unwrap().is_empty());
+}
+
+#[test]
+fn arrays_are_deserialized_the_same_as_the_whole_document() {
+    let lazy = LazyGltf::<Extensions>::from_json_string(JSON).unwrap();
+    // One array that was already used, and the rest that weren't.
+    lazy.meshes.get().unwrap();
+
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+    assert_eq!(
+        lazy.into_gltf().unwrap().to_json_string(),
+        gltf.to_json_string()
+    );
+}
+
+#[test]
+fn invalid_arrays_only_fail_when_used() {
+    let json =
+        r#"{"asset": {"version": "2.0"}, "nodes": [{"mesh": "zero"}], "skins": [{"joints": [0]}]}"#;
+    let gltf = LazyGltf::<Extensions>::from_json_string(json).unwrap();
+
+    assert_eq!(gltf.skins.get().unwrap()[0].joints, [0]);
+    assert!(gltf.nodes.get().is_err());
+    // The error isn't kept, so the array is deserialized again.
+    assert!(!gltf.nodes.is_parsed());
+    assert!(gltf.nodes.get().is_err());
+    assert!(gltf.into_gltf().is_err());
+
+    // The top-level properties that are deserialized upfront are checked upfront.
+    assert!(LazyGltf::<Extensions>::from_json_string(r#"{"nodes": []}"#).is_err());
+    assert!(LazyGltf::<Extensions>::from_json_string(r#"{"asset": {"version": 2}}"#).is_err());
+}
@@ -0,0 +1,77 @@
+use goth_gltf::{default_extensions::Extensions, Gltf, ParseLimits};
+
+fn check(limits: ParseLimits, json: &str) -> Result<(), String> {
+    let checked = limits.check(json).map_err(|error| error.msg);
+    assert!(ParseLimits::unlimited().check(json).is_ok());
+    checked
+}
+
+fn exceeds(limits: ParseLimits, json: &str, what: &str) {
+    let message = check(limits, json).unwrap_err();
+    assert!(message.contains(what), "{}", message);
+}
+
+#[test]
+fn nesting_depth_is_limited() {
+    let limits = ParseLimits {
+        max_depth: 3,
+        ..ParseLimits::default()
+    };
+
+    check(limits, r#"{"a": [{"b": 1}]}"#).unwrap();
+    exceeds(limits, r#"{"a": [{"b": []}]}"#, "nesting depth");
+    exceeds(limits, "[[[[]]]]", "nesting depth");
+}
+
+#[test]
+fn array_length_is_limited() {
+    let limits = ParseLimits {
+        max_array_length: 3,
+        ..ParseLimits::default()
+    };
+
+    check(limits, r#"{"a": [1, 2, 3], "b": [[1, 2, 3], {}, "x"]}"#).unwrap();
+    exceeds(limits, r#"{"a": [1, 2, 3, 4]}"#, "array length");
+    // Object members don't count as array elements.
+    check(limits, r#"{"a": 1, "b": 2, "c": 3, "d": 4}"#).unwrap();
+}
+
+#[test]
+fn string_length_is_limited() {
+    let limits = ParseLimits {
+        max_string_length: 4,
+        ..ParseLimits::default()
+    };
+
+    check(limits, r#"{"name": "abcd"}"#).unwrap();
+    exceeds(limits, r#"{"name": "abcde"}"#, "string length");
+    exceeds(limits, r#"{"abcde": 1}"#, "string length");
+}
+
+#[test]
+fn total_elements_are_limited() {
+    let limits = ParseLimits {
+        max_total_elements: 5,
+        ..ParseLimits::default()
+    };
+
+    // The object, the array and its three elements.
+    check(limits, r#"{"a": [1, 2, 3]}"#).unwrap();
+    exceeds(limits, r#"{"a": [1, 2, 3], "b": 4}"#, "total elements");
+    exceeds(limits, "[[], [], [], [], [], []]", "total elements");
+}
+
+#[test]
+fn documents_are_checked_before_deserializing() {
+    let json = r#"{"asset": {"version": "2.0"}, "nodes": [{}, {}, {}]}"#;
+    let limits = ParseLimits {
+        max_array_length: 2,
+        ..ParseLimits::default()
+    };
+
+    assert!(Gltf::<Extensions>::from_json_string_with_limits(json, &limits).is_err());
+    assert!(
+        Gltf::<Extensions>::from_json_string_with_limits(json, &ParseLimits::unlimited()).is_ok()
+    );
+    assert!(Gltf::<Extensions>::from_bytes_with_limits(json.as_bytes(), &limits).is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "mmap")]
+
+use goth_gltf::{default_extensions::Extensions, FromPathError, Gltf};
+use std::path::PathBuf;
+
+// Removes the file when the test is done with it, even if it fails.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("goth-gltf-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "buffers": [{"byteLength": 8}],
+    "bufferViews": [{"buffer": 0, "byteLength": 8}]
+}"#;
+
+#[test]
+fn glbs_are_loaded_with_their_binary_chunk_mapped() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+    let binary = [1, 2, 3, 4, 5, 6, 7, 8];
+    let file = TempFile::new("mapped.glb", &gltf.to_glb(Some(&binary)).unwrap());
+
+    let (read, mapped) = unsafe { Gltf::<Extensions>::from_path(&file.0) }.unwrap();
+    assert_eq!(read.to_json_string(), gltf.to_json_string());
+    assert_eq!(&*mapped.unwrap(), binary);
+}
+
+#[test]
+fn gltfs_are_loaded_without_a_binary_chunk() {
+    let file = TempFile::new("mapped.gltf", JSON.as_bytes());
+
+    let (read, mapped) = unsafe { Gltf::<Extensions>::from_path(&file.0) }.unwrap();
+    assert_eq!(read.buffer_views[0].byte_length, 8);
+    assert!(mapped.is_none());
+}
+
+#[test]
+fn missing_and_invalid_files_are_an_error() {
+    let path = std::env::temp_dir().join("goth-gltf-missing.glb");
+    assert!(matches!(
+        unsafe { Gltf::<Extensions>::from_path(path) },
+        Err(FromPathError::Io(_))
+    ));
+
+    let file = TempFile::new("truncated.glb", b"glTF\x02\x00\x00\x00\x40\x00\x00\x00");
+    assert!(matches!(
+        unsafe { Gltf::<Extensions>::from_path(&file.0) },
+        Err(FromPathError::Glb(_))
+    ));
+}
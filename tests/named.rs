@@ -0,0 +1,29 @@
+use goth_gltf::{default_extensions::Extensions, Gltf, Named};
+
+// Compiles the same way whether or not the `names` feature is enabled.
+fn names<T: Named>(items: &[T]) -> Vec<Option<&str>> {
+    items.iter().map(Named::name).collect()
+}
+
+#[test]
+fn names_are_read_through_the_trait() {
+    let gltf = Gltf::<Extensions>::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"name": "root"}, {}],
+            "materials": [{"name": "metal"}],
+            "scenes": [{"name": "scene", "nodes": [0]}]
+        }"#,
+    )
+    .unwrap();
+
+    if cfg!(feature = "names") {
+        assert_eq!(names(&gltf.nodes), [Some("root"), None]);
+        assert_eq!(names(&gltf.materials), [Some("metal")]);
+        assert_eq!(gltf.scenes[0].name(), Some("scene"));
+    } else {
+        assert_eq!(names(&gltf.nodes), [None, None]);
+        assert_eq!(names(&gltf.materials), [None]);
+        assert_eq!(gltf.scenes[0].name(), None);
+    }
+}
@@ -0,0 +1,32 @@
+// Run with `RUSTC_BOOTSTRAP=1 cargo test --features no_std --test no_std`, as nanoserde's `no_std`
+// support needs a nightly compiler.
+#![cfg(feature = "no_std")]
+
+use goth_gltf::{default_extensions::Extensions, Gltf, ParseOptions};
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "buffers": [{"byteLength": 3, "uri": "data:application/octet-stream;base64,AQID"}],
+    "meshes": [{"primitives": [{"attributes": {"POSITION": 0, "_CUSTOM": 1}, "mode": 9}]}],
+    "nodes": [{"mesh": 0, "extensions": {"VENDOR_unknown": {}}}]
+}"#;
+
+#[test]
+fn documents_are_parsed_and_written_without_std() {
+    assert!(Gltf::<Extensions>::from_json_string(JSON).is_err());
+
+    let gltf =
+        Gltf::<Extensions>::from_json_string_with_options(JSON, &ParseOptions::lenient()).unwrap();
+
+    // Attributes are deserialized through hashbrown's map without `std`.
+    let attributes = &gltf.meshes[0].primitives[0].attributes;
+    assert_eq!(attributes.position, Some(0));
+    assert_eq!(attributes.get("_CUSTOM"), Some(1));
+    assert_eq!(gltf.buffers[0].embedded_data(), Some(vec![1, 2, 3]));
+
+    let glb = gltf.to_glb(None).unwrap();
+    let (read, binary_chunk) =
+        Gltf::<Extensions>::from_bytes_with_options(&glb, &ParseOptions::lenient()).unwrap();
+    assert_eq!(read.to_json_string(), gltf.to_json_string());
+    assert_eq!(binary_chunk, None);
+}
@@ -0,0 +1,188 @@
+use goth_gltf::{
+    default_extensions::Extensions, lazy::LazyGltf, AlphaMode, Gltf, Interpolation, ParseOptions,
+    PrimitiveMode, SamplerWrap, UnknownName,
+};
+
+fn strict_error(json: &str) -> String {
+    Gltf::<Extensions>::from_json_string_with_options(json, &ParseOptions::strict())
+        .unwrap_err()
+        .msg
+}
+
+#[test]
+fn required_fields_are_only_checked_when_strict() {
+    let documents = [
+        "{}",
+        r#"{"asset": {"version": "2.0"}, "meshes": [{"primitives": []}]}"#,
+        r#"{"asset": {"version": "2.0"}, "skins": [{"joints": []}]}"#,
+        r#"{"asset": {"version": "2.0"}, "animations": [{"channels": [], "samplers": []}]}"#,
+    ];
+
+    for json in documents {
+        assert!(
+            Gltf::<Extensions>::from_json_string(json).is_ok(),
+            "{}",
+            json
+        );
+        assert!(
+            Gltf::<Extensions>::from_json_string_with_options(json, &ParseOptions::lenient())
+                .is_ok(),
+            "{}",
+            json
+        );
+        assert!(
+            Gltf::<Extensions>::from_json_string_with_options(json, &ParseOptions::strict())
+                .is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn missing_fields_are_named_by_path() {
+    let meshes = r#"{"primitives": [{"attributes": {}}]}"#;
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "meshes": [{0}, {0}, {0}, {{"primitives": []}}]}}"#,
+        meshes
+    );
+    assert!(strict_error(&json).contains("meshes[3].primitives"));
+
+    assert!(strict_error("{}").contains("asset.version"));
+    assert!(strict_error(
+        r#"{"asset": {"version": "2.0"}, "animations": [{"channels": [], "samplers": []}]}"#
+    )
+    .contains("animations[0].channels"));
+    assert!(
+        strict_error(r#"{"asset": {"version": "2.0"}, "skins": [{}, {"joints": [0]}]}"#)
+            .contains("skins[0].joints")
+    );
+}
+
+#[test]
+fn unknown_enum_values_are_rejected_by_default() {
+    let json = r#"{"meshes": [{"primitives": [{"attributes": {}, "mode": 7}]}]}"#;
+
+    assert!(Gltf::<Extensions>::from_json_string(json).is_err());
+    assert!(
+        Gltf::<Extensions>::from_json_string_with_options(json, &ParseOptions::lenient()).is_ok()
+    );
+}
+
+const UNKNOWN_ENUM_VALUES: &str = r#"{
+    "asset": {"version": "2.0"},
+    "meshes": [{"primitives": [{"attributes": {}}, {"attributes": {}, "mode": 7}]}],
+    "materials": [{"alphaMode": "VENDOR_dither"}],
+    "samplers": [{"wrapS": 10497, "wrapT": 1234}],
+    "animations": [{
+        "channels": [{"sampler": 0, "target": {"path": "translation"}}],
+        "samplers": [{"input": 0, "output": 1, "interpolation": "VENDOR_bezier"}]
+    }]
+}"#;
+
+#[test]
+fn unknown_enum_values_are_named_by_path() {
+    let error = strict_error(UNKNOWN_ENUM_VALUES);
+    assert!(error.contains("animations[0].samplers[0].interpolation: VENDOR_bezier"));
+
+    let json = UNKNOWN_ENUM_VALUES.replace("VENDOR_bezier", "STEP");
+    assert!(strict_error(&json).contains("materials[0].alphaMode: VENDOR_dither"));
+
+    let json = json.replace("VENDOR_dither", "MASK");
+    assert!(strict_error(&json).contains("meshes[0].primitives[1].mode: 7"));
+
+    let json = json.replace(r#""mode": 7"#, r#""mode": 4"#);
+    assert!(strict_error(&json).contains("samplers[0].wrapT: 1234"));
+}
+
+#[test]
+fn tolerated_unknown_enum_values_are_kept_and_written_back() {
+    let gltf = Gltf::<Extensions>::from_json_string_with_options(
+        UNKNOWN_ENUM_VALUES,
+        &ParseOptions::lenient(),
+    )
+    .unwrap();
+
+    // Both are `Copy`, so they can be moved out of the document.
+    let interpolation = gltf.animations[0].samplers[0].interpolation;
+    assert_eq!(
+        interpolation,
+        Interpolation::Other(UnknownName::new("VENDOR_bezier").unwrap())
+    );
+    let alpha_mode = gltf.materials[0].alpha_mode;
+    assert!(matches!(alpha_mode, AlphaMode::Other(name) if name.as_str() == "VENDOR_dither"));
+    assert_eq!(gltf.meshes[0].primitives[1].mode, PrimitiveMode::Unknown(7));
+    assert!(matches!(
+        gltf.samplers[0].wrap_t,
+        SamplerWrap::Unknown(1234)
+    ));
+
+    let json = gltf.to_json_string();
+    for value in [
+        r#""interpolation":"VENDOR_bezier""#,
+        r#""alphaMode":"VENDOR_dither""#,
+        r#""mode":7"#,
+        r#""wrapT":1234"#,
+    ] {
+        assert!(json.contains(value), "{} is missing from {}", value, json);
+    }
+}
+
+#[test]
+fn unknown_names_that_are_too_long_are_always_rejected() {
+    let name = "V".repeat(UnknownName::CAPACITY);
+    assert_eq!(UnknownName::new(&name).unwrap().as_str(), name);
+    assert!(UnknownName::new(&format!("{}V", name)).is_none());
+
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "materials": [{{"alphaMode": "{}V"}}]}}"#,
+        name
+    );
+    assert!(
+        Gltf::<Extensions>::from_json_string_with_options(&json, &ParseOptions::lenient()).is_err()
+    );
+}
+
+#[test]
+fn parses_on_other_threads_use_their_own_options() {
+    let strict = std::thread::spawn(|| {
+        (0..100)
+            .map(|_| Gltf::<Extensions>::from_json_string(UNKNOWN_ENUM_VALUES).is_err())
+            .collect::<Vec<_>>()
+    });
+    let lenient = std::thread::spawn(|| {
+        (0..100)
+            .map(|_| {
+                Gltf::<Extensions>::from_json_string_with_options(
+                    UNKNOWN_ENUM_VALUES,
+                    &ParseOptions::lenient(),
+                )
+                .is_ok()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    assert!(strict.join().unwrap().into_iter().all(|rejected| rejected));
+    assert!(lenient.join().unwrap().into_iter().all(|accepted| accepted));
+}
+
+#[test]
+fn lazy_arrays_check_unknown_enum_values_when_they_are_deserialized() {
+    let gltf = LazyGltf::<Extensions>::from_json_string(UNKNOWN_ENUM_VALUES).unwrap();
+    assert!(gltf.buffers.get().is_ok());
+    assert!(gltf
+        .samplers
+        .get()
+        .unwrap_err()
+        .msg
+        .contains("samplers[0].wrapT: 1234"));
+
+    let options = ParseOptions::strict().tolerate_unknown_enum_values(true);
+    let gltf = LazyGltf::<Extensions>::from_json_string_with_options(UNKNOWN_ENUM_VALUES, &options)
+        .unwrap();
+    assert!(matches!(
+        gltf.samplers.get().unwrap()[0].wrap_t,
+        SamplerWrap::Unknown(1234)
+    ));
+    assert_eq!(gltf.into_gltf().unwrap().meshes.len(), 1);
+}
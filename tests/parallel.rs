@@ -0,0 +1,46 @@
+#![cfg(feature = "rayon")]
+
+use goth_gltf::{default_extensions::Extensions, Gltf, ParseOptions, PrimitiveMode};
+
+// Enough meshes to be split between several rayon tasks.
+fn document(mesh: &str) -> String {
+    let meshes = vec![mesh; 1000].join(",");
+    format!(
+        r#"{{"asset": {{"version": "2.0"}}, "meshes": [{}]}}"#,
+        meshes
+    )
+}
+
+#[test]
+fn parallel_parsing_uses_the_options_on_every_thread() {
+    let json = document(r#"{"primitives": [{"attributes": {}, "mode": 7}]}"#);
+
+    assert!(Gltf::<Extensions>::from_json_string_parallel(&json).is_err());
+
+    let options = ParseOptions::strict().tolerate_unknown_enum_values(true);
+    let gltf = Gltf::<Extensions>::from_json_string_parallel_with_options(&json, &options).unwrap();
+    assert_eq!(gltf.meshes.len(), 1000);
+    assert!(gltf
+        .meshes
+        .iter()
+        .all(|mesh| mesh.primitives[0].mode == PrimitiveMode::Unknown(7)));
+
+    // The setting doesn't leak out of the call.
+    assert!(Gltf::<Extensions>::from_json_string_parallel(&json).is_err());
+}
+
+#[test]
+fn parallel_parsing_can_default_missing_fields() {
+    let json = document(r#"{"primitives": []}"#);
+
+    assert!(Gltf::<Extensions>::from_json_string_parallel_with_options(
+        &json,
+        &ParseOptions::strict()
+    )
+    .is_err());
+
+    let gltf =
+        Gltf::<Extensions>::from_json_string_parallel_with_options(&json, &ParseOptions::lenient())
+            .unwrap();
+    assert!(gltf.meshes.iter().all(|mesh| mesh.primitives.is_empty()));
+}
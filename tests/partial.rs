@@ -0,0 +1,155 @@
+use goth_gltf::partial::PartialParser;
+use goth_gltf::{default_extensions, Asset, Node, ParseLimits, Passthrough};
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}],
+    "nodes": [{"mesh": 0}, {"children": [0]}, {"mesh": 1}],
+    "extras": {"nested": [[{"a": "]}"}]]}
+}"#;
+
+#[test]
+fn only_the_requested_sections_are_parsed() {
+    let mut asset: Option<Asset<()>> = None;
+    let mut missing: Option<Vec<Node<()>>> = None;
+    let mut meshes_used = Vec::new();
+
+    PartialParser::new()
+        .section("asset", &mut asset)
+        .section("skins", &mut missing)
+        .each("nodes", |index, node: Node<()>| {
+            meshes_used.extend(node.mesh.map(|mesh| (index, mesh)))
+        })
+        .parse_str(JSON)
+        .unwrap();
+
+    assert_eq!(asset.unwrap().version, "2.0");
+    assert!(missing.is_none());
+    assert_eq!(meshes_used, [(0, 0), (2, 1)]);
+}
+
+#[test]
+fn malformed_input_is_an_error() {
+    for json in [
+        "",
+        "[]",
+        r#"{"nodes": [{"mesh": 0}"#,
+        r#"{"extras": ]}"#,
+        r#"{"extras": {"a": 1]}"#,
+        r#"{"extras": [}"#,
+    ] {
+        assert!(PartialParser::new().parse_str(json).is_err(), "{}", json);
+    }
+}
+
+#[test]
+fn limits_are_checked_before_parsing() {
+    let limits = ParseLimits {
+        max_array_length: 2,
+        ..ParseLimits::default()
+    };
+
+    let mut count = 0;
+    let result = PartialParser::new()
+        .limits(limits)
+        .each("nodes", |_, _: Node<()>| count += 1)
+        .parse_str(JSON);
+
+    assert!(result.is_err());
+    assert_eq!(count, 0);
+}
+
+fn nested_extension(depth: usize) -> String {
+    format!(
+        r#"{{"extensions": {{"VENDOR_deep": {}1{}}}}}"#,
+        "[".repeat(depth),
+        "]".repeat(depth)
+    )
+}
+
+// Unknown extensions are kept as raw JSON, which is built up recursively.
+#[test]
+fn deeply_nested_raw_json_is_rejected() {
+    type Extensions = Passthrough<default_extensions::NodeExtensions>;
+
+    let mut extensions: Option<Extensions> = None;
+    PartialParser::new()
+        .section("extensions", &mut extensions)
+        .parse_str(&nested_extension(60))
+        .unwrap();
+    assert!(extensions.unwrap().unknown.contains_key("VENDOR_deep"));
+
+    let mut extensions: Option<Extensions> = None;
+    let result = PartialParser::new()
+        .section("extensions", &mut extensions)
+        .parse_str(&nested_extension(100_000));
+    assert!(result.unwrap_err().msg.contains("nesting depth"));
+}
+
+// The lazy parser finds the extent of each section by matching up brackets.
+#[test]
+fn mismatched_brackets_are_an_error_when_scanning() {
+    use goth_gltf::{lazy::LazyGltf, AnyExtensions};
+
+    for json in [
+        r#"{"asset": {"version": "2.0"}, "nodes": [{]}"#,
+        r#"{"asset": {"version": "2.0"}, "nodes": [}"#,
+        r#"{"asset": {"version": "2.0"}, "nodes": ]"#,
+        r#"{"asset": {"version": "2.0"}, "nodes": [[[[]"#,
+    ] {
+        assert!(
+            LazyGltf::<AnyExtensions>::from_json_string(json).is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn elements_are_streamed_in_order_as_they_are_parsed() {
+    let mut seen = Vec::new();
+    let result = PartialParser::new()
+        .each("nodes", |index, node: Node<()>| {
+            seen.push((index, node.mesh))
+        })
+        .parse_str(r#"{"nodes": [{"mesh": 3}, {"mesh": 4}, {"mesh": "five"}, {"mesh": 6}]}"#);
+
+    // The elements before the invalid one were already handed over.
+    assert!(result.is_err());
+    assert_eq!(seen, [(0, Some(3)), (1, Some(4))]);
+}
+
+#[test]
+fn several_arrays_can_be_streamed_in_one_pass() {
+    let mut nodes = 0;
+    let mut meshes = Vec::new();
+
+    PartialParser::new()
+        .each("nodes", |_, _: Node<()>| nodes += 1)
+        .each("meshes", |index, mesh: goth_gltf::Mesh<()>| {
+            meshes.push((index, mesh.primitives.len()))
+        })
+        .each("skins", |_, _: goth_gltf::Skin<()>| unreachable!())
+        .parse_str(JSON)
+        .unwrap();
+
+    assert_eq!(nodes, 3);
+    assert_eq!(meshes, [(0, 1)]);
+}
+
+#[test]
+fn streaming_needs_an_array() {
+    for json in [r#"{"nodes": {}}"#, r#"{"nodes": 1}"#] {
+        let result = PartialParser::new()
+            .each("nodes", |_, _: Node<()>| {})
+            .parse_str(json);
+        assert!(result.is_err(), "{}", json);
+    }
+
+    let mut count = 0;
+    PartialParser::new()
+        .each("nodes", |_, _: Node<()>| count += 1)
+        .parse_str(r#"{"nodes": []}"#)
+        .unwrap();
+    assert_eq!(count, 0);
+}
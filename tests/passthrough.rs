@@ -0,0 +1,41 @@
+#![cfg(feature = "msft_lod")]
+
+use goth_gltf::{default_extensions, Passthrough};
+use nanoserde::{DeJson, SerJson};
+
+type NodeExtensions = Passthrough<default_extensions::NodeExtensions>;
+
+const JSON: &str = r#"{
+    "MSFT_lod": {"ids": [1, 2]},
+    "VENDOR_b": {"list": [], "text": "a \"quoted\" line\n"},
+    "VENDOR_a": true
+}"#;
+
+#[test]
+fn unknown_extensions_are_kept_as_raw_json() {
+    let extensions = NodeExtensions::deserialize_json(JSON).unwrap();
+
+    assert_eq!(extensions.known.msft_lod.as_ref().unwrap().ids, [1, 2]);
+    assert_eq!(extensions.unknown.len(), 2);
+    assert_eq!(extensions.unknown["VENDOR_a"], "true");
+    assert_eq!(
+        extensions.unknown["VENDOR_b"],
+        r#"{"list":[],"text":"a \"quoted\" line\n"}"#
+    );
+}
+
+#[test]
+fn unknown_extensions_are_written_after_the_known_ones() {
+    let extensions = NodeExtensions::deserialize_json(JSON).unwrap();
+
+    assert_eq!(
+        extensions.serialize_json(),
+        r#"{"MSFT_lod":{"ids":[1,2]},"VENDOR_a":true,"VENDOR_b":{"list":[],"text":"a \"quoted\" line\n"}}"#
+    );
+}
+
+#[test]
+fn known_extensions_are_still_validated() {
+    assert!(NodeExtensions::deserialize_json(r#"{"MSFT_lod": {"ids": "none"}}"#).is_err());
+    assert!(NodeExtensions::deserialize_json("[]").is_err());
+}
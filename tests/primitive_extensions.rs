@@ -0,0 +1,89 @@
+// Triggered by the code nanoserde derives for `Option` fields.
+#![allow(clippy::question_mark)]
+
+use goth_gltf::{AnyExtensions, Gltf, JsonValue};
+use nanoserde::{DeJson, SerJson};
+
+#[derive(Debug, Default, Clone, DeJson, SerJson)]
+pub struct Mapping {
+    pub material: usize,
+    pub variants: Vec<usize>,
+}
+
+#[derive(Debug, Default, Clone, DeJson, SerJson)]
+pub struct MaterialsVariants {
+    pub mappings: Vec<Mapping>,
+}
+
+#[derive(Debug, Default, Clone, DeJson, SerJson)]
+pub struct VariantsPrimitiveExtensions {
+    #[nserde(rename = "KHR_materials_variants")]
+    pub khr_materials_variants: Option<MaterialsVariants>,
+}
+
+goth_gltf::compose_extensions! {
+    pub struct VariantsExtensions {
+        PrimitiveExtensions += VariantsPrimitiveExtensions,
+    }
+}
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "meshes": [{"primitives": [
+        {
+            "attributes": {"POSITION": 0},
+            "extensions": {"KHR_materials_variants": {"mappings": [
+                {"material": 1, "variants": [0, 2]}
+            ]}}
+        },
+        {"attributes": {"POSITION": 0}}
+    ]}]
+}"#;
+
+#[test]
+fn custom_primitive_extensions_are_parsed() {
+    let gltf = Gltf::<VariantsExtensions>::from_json_string(JSON).unwrap();
+    let primitives = &gltf.meshes[0].primitives;
+
+    let variants = primitives[0]
+        .extensions
+        .extra
+        .khr_materials_variants
+        .as_ref()
+        .unwrap();
+    assert_eq!(variants.mappings[0].material, 1);
+    assert_eq!(variants.mappings[0].variants, [0, 2]);
+    assert!(primitives[1]
+        .extensions
+        .extra
+        .khr_materials_variants
+        .is_none());
+}
+
+#[test]
+fn primitive_extensions_are_written_back() {
+    let gltf = Gltf::<VariantsExtensions>::from_json_string(JSON).unwrap();
+    let written = gltf.to_json_string();
+
+    assert!(written.contains(
+        r#""extensions":{"KHR_materials_variants":{"mappings":[{"material":1,"variants":[0,2]}]}}"#
+    ));
+}
+
+#[test]
+fn any_extensions_keep_every_primitive_extension() {
+    let json = r#"{
+        "asset": {"version": "2.0"},
+        "meshes": [{"primitives": [{
+            "attributes": {},
+            "extensions": {"CESIUM_primitive_outline": {"indices": 3}}
+        }]}]
+    }"#;
+    let gltf = Gltf::<AnyExtensions>::from_json_string(json).unwrap();
+
+    let outline = &gltf.meshes[0].primitives[0].extensions["CESIUM_primitive_outline"];
+    match outline {
+        JsonValue::Object(outline) => assert_eq!(outline["indices"], JsonValue::Number(3.0)),
+        _ => panic!("expected an object"),
+    }
+}
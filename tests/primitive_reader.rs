@@ -0,0 +1,446 @@
+#![cfg(feature = "primitive_reader")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::primitive_reader::{
+    read_buffer_with_accessor, read_buffer_with_policy, Error, PrimitiveReader, ReadPolicy,
+    ReadWarning,
+};
+use goth_gltf::Gltf;
+use std::collections::HashMap;
+
+fn floats(values: &[f32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+// Three positions, with a buffer view that only holds two and a half of them.
+fn truncated() -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let gltf = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 30}],
+            "bufferViews": [{"buffer": 0, "byteLength": 30}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3"}
+            ],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}]
+        }"#,
+    )
+    .unwrap();
+
+    let bytes = floats(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+    let buffer_views = HashMap::from([(0, bytes[..30].to_vec())]);
+
+    (gltf, buffer_views)
+}
+
+#[test]
+fn truncated_accessors_fail_by_default() {
+    let (gltf, buffer_views) = truncated();
+
+    assert!(matches!(
+        read_buffer_with_accessor(&buffer_views, &gltf, &gltf.accessors[0]),
+        Err(Error::AccessorOutOfBounds(36, 30))
+    ));
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+    assert!(reader.read_positions().is_err());
+    assert!(reader.take_warnings().is_empty());
+}
+
+#[test]
+fn truncated_accessors_are_read_leniently_with_a_warning() {
+    let (gltf, buffer_views) = truncated();
+    let warning = ReadWarning::AccessorTruncated {
+        required: 36,
+        available: 30,
+    };
+
+    let read = read_buffer_with_policy(
+        &buffer_views,
+        &gltf,
+        &gltf.accessors[0],
+        &ReadPolicy::lenient(),
+    )
+    .unwrap();
+    assert_eq!(read.bytes.len(), 30);
+    assert_eq!(read.warnings, std::slice::from_ref(&warning));
+
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views)
+        .with_policy(ReadPolicy::strict().truncate_accessors(true));
+    let positions = reader.read_positions().unwrap().unwrap();
+    assert_eq!(&positions[..], &[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+    assert_eq!(reader.take_warnings(), [warning]);
+    assert!(reader.take_warnings().is_empty());
+}
+
+#[test]
+fn the_policy_applies_on_other_threads() {
+    let (gltf, buffer_views) = truncated();
+    let policy = ReadPolicy::lenient();
+
+    let positions = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views)
+                    .with_policy(policy)
+                    .read_positions()
+                    .map(|positions| positions.map(|positions| positions.len()))
+            })
+            .join()
+            .unwrap()
+    });
+
+    assert_eq!(positions.unwrap(), Some(2));
+}
+
+fn accessor_only(accessor: &str) -> Gltf<Extensions> {
+    Gltf::from_json_string(&format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": 12}}],
+            "bufferViews": [{{"buffer": 0, "byteLength": 12}}],
+            "accessors": [{}]
+        }}"#,
+        accessor
+    ))
+    .unwrap()
+}
+
+#[test]
+fn huge_accessor_counts_are_rejected_before_allocating() {
+    let buffer_views = HashMap::from([(0, vec![0; 12])]);
+
+    for count in ["1000000000000000", "18446744073709551615"] {
+        let zeroed = accessor_only(&format!(
+            r#"{{"componentType": 5126, "count": {}, "type": "VEC3"}}"#,
+            count
+        ));
+        assert!(matches!(
+            read_buffer_with_accessor(&buffer_views, &zeroed, &zeroed.accessors[0]),
+            Err(Error::AccessorTooLarge(_, 12))
+        ));
+
+        let backed = accessor_only(&format!(
+            r#"{{"bufferView": 0, "componentType": 5126, "count": {}, "type": "VEC3"}}"#,
+            count
+        ));
+        assert!(read_buffer_with_accessor(&buffer_views, &backed, &backed.accessors[0]).is_err());
+        // Lenient reads only get the elements that are there, unless the end of the accessor
+        // can't even be worked out.
+        let read = read_buffer_with_policy(
+            &buffer_views,
+            &backed,
+            &backed.accessors[0],
+            &ReadPolicy::lenient(),
+        );
+        match count {
+            "1000000000000000" => assert_eq!(read.unwrap().bytes.len(), 12),
+            _ => assert!(matches!(read, Err(Error::AccessorTooLarge(_, 12)))),
+        }
+    }
+}
+
+#[test]
+fn the_accessor_size_limit_can_be_changed() {
+    let buffer_views = HashMap::new();
+    let gltf = accessor_only(r#"{"componentType": 5126, "count": 100, "type": "VEC3"}"#);
+    let accessor = &gltf.accessors[0];
+
+    let policy = ReadPolicy::strict().max_accessor_bytes(1199);
+    assert!(matches!(
+        read_buffer_with_policy(&buffer_views, &gltf, accessor, &policy),
+        Err(Error::AccessorTooLarge(100, 12))
+    ));
+
+    let policy = policy.max_accessor_bytes(1200);
+    let read = read_buffer_with_policy(&buffer_views, &gltf, accessor, &policy).unwrap();
+    assert_eq!(read.bytes, vec![0; 1200]);
+}
+
+// The packed fast path has to give the same results as converting component by component.
+#[test]
+fn integer_conversion_matches_reading_components() {
+    use goth_gltf::primitive_reader::{read_f32x4, read_floats};
+    use goth_gltf::{Accessor, AccessorType, ComponentType};
+
+    let bytes: Vec<u8> = (0..4096_u32).map(|i| (i * 37 + i / 7) as u8).collect();
+
+    for (component_type, size) in [
+        (ComponentType::UnsignedByte, 1),
+        (ComponentType::Byte, 1),
+        (ComponentType::UnsignedShort, 2),
+        (ComponentType::Short, 2),
+    ] {
+        for stride in [None, Some(4 * size + 4)] {
+            let count = bytes.len() / stride.unwrap_or(4 * size);
+            let mut accessor = Accessor::<()>::new(0, component_type, AccessorType::Vec4, count);
+            accessor.normalized = true;
+
+            let expected = read_floats(&bytes, stride, &accessor);
+            let read = read_f32x4(&bytes, stride, &accessor).unwrap();
+
+            assert_eq!(read.len(), count);
+            assert_eq!(read.as_flattened(), expected);
+        }
+    }
+}
+
+fn sparse(base_buffer_view: &str, indices: [u8; 2]) -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let gltf = Gltf::from_json_string(&format!(
+        r#"{{
+            "asset": {{"version": "2.0"}},
+            "buffers": [{{"byteLength": 28}}],
+            "bufferViews": [
+                {{"buffer": 0, "byteLength": 16}},
+                {{"buffer": 0, "byteOffset": 16, "byteLength": 2}},
+                {{"buffer": 0, "byteOffset": 18, "byteLength": 8}}
+            ],
+            "accessors": [{{
+                {}
+                "componentType": 5126,
+                "count": 4,
+                "type": "SCALAR",
+                "sparse": {{
+                    "count": 2,
+                    "indices": {{"bufferView": 1, "componentType": 5121}},
+                    "values": {{"bufferView": 2}}
+                }}
+            }}]
+        }}"#,
+        base_buffer_view
+    ))
+    .unwrap();
+
+    let buffer_views = HashMap::from([
+        (0, floats(&[1.0, 2.0, 3.0, 4.0])),
+        (1, indices.to_vec()),
+        (2, floats(&[10.0, 30.0])),
+    ]);
+
+    (gltf, buffer_views)
+}
+
+#[test]
+fn sparse_values_replace_the_base_values() {
+    let (gltf, buffer_views) = sparse(r#""bufferView": 0,"#, [0, 2]);
+    let (bytes, stride) =
+        read_buffer_with_accessor(&buffer_views, &gltf, &gltf.accessors[0]).unwrap();
+
+    assert_eq!(bytes, floats(&[10.0, 2.0, 30.0, 4.0]));
+    assert_eq!(stride, None);
+}
+
+#[test]
+fn sparse_values_replace_zeros_without_a_buffer_view() {
+    let (gltf, buffer_views) = sparse("", [3, 1]);
+    let (bytes, _) = read_buffer_with_accessor(&buffer_views, &gltf, &gltf.accessors[0]).unwrap();
+
+    assert_eq!(bytes, floats(&[0.0, 30.0, 0.0, 10.0]));
+}
+
+#[test]
+fn sparse_indices_past_the_end_are_rejected() {
+    let (gltf, buffer_views) = sparse("", [0, 4]);
+
+    assert!(matches!(
+        read_buffer_with_accessor(&buffer_views, &gltf, &gltf.accessors[0]),
+        Err(Error::SparseIndexOutOfBounds(4))
+    ));
+}
+
+#[test]
+fn accessor_bounds_are_exact_for_large_integers() {
+    use goth_gltf::primitive_reader::{compute_accessor_bounds, AccessorBounds};
+    use goth_gltf::{Accessor, AccessorType, ComponentType};
+
+    let values = [16_777_217_u32, 3, u32::MAX];
+    let bytes: Vec<u8> = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    let accessor = Accessor::<()>::new(0, ComponentType::UnsignedInt, AccessorType::Scalar, 3);
+
+    assert_eq!(
+        compute_accessor_bounds(&bytes, None, &accessor).unwrap(),
+        Some(AccessorBounds {
+            min: vec![3.0],
+            max: vec![u32::MAX as f64],
+        })
+    );
+
+    let accessor = Accessor::<()>::new(0, ComponentType::UnsignedInt, AccessorType::Scalar, 2);
+    let bounds = compute_accessor_bounds(&bytes[..8], None, &accessor)
+        .unwrap()
+        .unwrap();
+    assert_eq!(bounds.max, [16_777_217.0]);
+
+    let empty = Accessor::<()>::new(0, ComponentType::UnsignedInt, AccessorType::Scalar, 0);
+    assert_eq!(compute_accessor_bounds(&[], None, &empty).unwrap(), None);
+}
+
+#[test]
+fn accessor_bounds_of_truncated_data_are_an_error() {
+    use goth_gltf::primitive_reader::compute_accessor_bounds;
+    use goth_gltf::{Accessor, AccessorType, ComponentType};
+
+    // Two strided VEC2 elements need 8 + 8 bytes, but the stride pads the first one to 12.
+    let bytes = floats(&[1.0, 2.0, 0.0, 3.0, 4.0]);
+    let accessor = Accessor::<()>::new(0, ComponentType::Float, AccessorType::Vec2, 2);
+
+    let bounds = compute_accessor_bounds(&bytes, Some(12), &accessor)
+        .unwrap()
+        .unwrap();
+    assert_eq!((bounds.min, bounds.max), (vec![1.0, 2.0], vec![3.0, 4.0]));
+
+    assert!(matches!(
+        compute_accessor_bounds(&bytes[..19], Some(12), &accessor),
+        Err(Error::AccessorOutOfBounds(20, 19))
+    ));
+}
+
+#[test]
+fn accessor_bounds_are_filled_in_and_corrected() {
+    use goth_gltf::primitive_reader::update_accessor_bounds;
+
+    let mut gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 12}],
+            "bufferViews": [{"buffer": 0, "byteLength": 12}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR"},
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR",
+                 "min": [-1], "max": [2]},
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR",
+                 "min": [-5], "max": [5]}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let buffer_views = HashMap::from([(0, floats(&[2.0, -1.0, 0.5]))]);
+
+    assert_eq!(
+        update_accessor_bounds(&mut gltf, &buffer_views).unwrap(),
+        [0, 2]
+    );
+    for accessor in &gltf.accessors {
+        assert_eq!(accessor.min.as_deref(), Some(&[-1.0][..]));
+        assert_eq!(accessor.max.as_deref(), Some(&[2.0][..]));
+    }
+}
+
+// Three positions and normals, two uvs and the indices `[0, 1, index]`, with a primitive for each
+// set of attributes.
+fn primitives(index: u16) -> (Gltf<Extensions>, HashMap<usize, Vec<u8>>) {
+    let gltf = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 94}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 72, "byteLength": 16},
+                {"buffer": 0, "byteOffset": 88, "byteLength": 6}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3"},
+                {"bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 2, "type": "VEC2"},
+                {"bufferView": 3, "componentType": 5123, "count": 3, "type": "SCALAR"}
+            ],
+            "meshes": [{"primitives": [
+                {"attributes": {"POSITION": 0, "NORMAL": 1}, "indices": 3},
+                {"attributes": {"POSITION": 0, "TEXCOORD_0": 2}},
+                {"attributes": {"NORMAL": 1}}
+            ]}]
+        }"#,
+    )
+    .unwrap();
+
+    let buffer_views = HashMap::from([
+        (0, floats(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0])),
+        (1, floats(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0])),
+        (2, floats(&[0.0, 0.0, 1.0, 1.0])),
+        (3, [0, 1, index].map(u16::to_le_bytes).concat()),
+    ]);
+
+    (gltf, buffer_views)
+}
+
+#[test]
+fn all_attributes_are_read_together() {
+    let (gltf, buffer_views) = primitives(2);
+    let reader = PrimitiveReader::new(&gltf, &gltf.meshes[0].primitives[0], &buffer_views);
+
+    let data = reader.read_all().unwrap();
+    assert_eq!(data.vertex_count(), 3);
+    assert_eq!(data.normals.unwrap()[..], [[0.0, 0.0, 1.0]; 3]);
+    assert_eq!(&data.indices.unwrap().into_u32()[..], &[0, 1, 2]);
+    assert!(data.uvs.is_none() && data.tangents.is_none() && data.joints.is_none());
+}
+
+#[test]
+fn read_all_checks_the_vertex_and_index_counts() {
+    let (gltf, buffer_views) = primitives(3);
+    let primitives = &gltf.meshes[0].primitives;
+    let read_all = |primitive| PrimitiveReader::new(&gltf, primitive, &buffer_views).read_all();
+
+    assert!(matches!(
+        read_all(&primitives[0]),
+        Err(Error::IndexOutOfBounds(3, 3))
+    ));
+    assert!(matches!(
+        read_all(&primitives[1]),
+        Err(Error::VertexCountMismatch("TEXCOORD_0", 3, 2))
+    ));
+    assert!(matches!(
+        read_all(&primitives[2]),
+        Err(Error::MissingPositions)
+    ));
+}
+
+#[test]
+fn cached_accessors_are_decoded_once() {
+    use goth_gltf::primitive_reader::AccessorCache;
+    use std::borrow::Cow;
+
+    // Two primitives sharing quantized positions, which have to be decoded.
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 6}],
+            "bufferViews": [{"buffer": 0, "byteLength": 6}],
+            "accessors": [{"bufferView": 0, "componentType": 5121, "count": 2, "type": "VEC3"}],
+            "meshes": [{"primitives": [
+                {"attributes": {"POSITION": 0}},
+                {"attributes": {"POSITION": 0}, "material": 0}
+            ]}]
+        }"#,
+    )
+    .unwrap();
+    let buffer_views = HashMap::from([(0, vec![1, 2, 3, 4, 5, 6])]);
+    let primitives = &gltf.meshes[0].primitives;
+
+    let cache = AccessorCache::new(&gltf);
+    let read = |primitive| {
+        PrimitiveReader::new(&gltf, primitive, &buffer_views)
+            .with_cache(&cache)
+            .read_positions()
+            .unwrap()
+            .unwrap()
+    };
+
+    let (first, second) = match (read(&primitives[0]), read(&primitives[1])) {
+        (Cow::Borrowed(first), Cow::Borrowed(second)) => (first, second),
+        _ => panic!("expected the positions to be borrowed from the cache"),
+    };
+    assert_eq!(first, [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    assert!(std::ptr::eq(first, second));
+
+    // Without a cache the positions are decoded every time.
+    let reader = PrimitiveReader::new(&gltf, &primitives[0], &buffer_views);
+    assert!(matches!(reader.read_positions(), Ok(Some(Cow::Owned(_)))));
+}
@@ -0,0 +1,162 @@
+use goth_gltf::sanitize::Repair;
+use goth_gltf::{default_extensions::Extensions, Gltf};
+
+fn parse(json: &str) -> Gltf<Extensions> {
+    Gltf::from_json_string(json).unwrap()
+}
+
+#[test]
+fn out_of_range_references_are_dropped() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scenes": [{"nodes": [0, 5]}],
+            "nodes": [{"mesh": 3, "children": [0, 1]}, {"camera": 0}],
+            "materials": [{"normalTexture": {"index": 2}}]
+        }"#,
+    );
+    let report = gltf.sanitize();
+
+    let dropped = |location: &str, index| Repair::DroppedReference {
+        location: location.to_string(),
+        index,
+    };
+    assert_eq!(
+        report.repairs,
+        [
+            dropped("materials[0].normalTexture", 2),
+            dropped("nodes[0].mesh", 3),
+            dropped("nodes[0].children", 0),
+            dropped("nodes[1].camera", 0),
+            dropped("scenes[0].nodes", 5),
+        ]
+    );
+
+    assert_eq!(gltf.nodes[0].mesh, None);
+    assert_eq!(gltf.nodes[0].children, [1]);
+    assert_eq!(gltf.nodes[1].camera, None);
+    assert!(gltf.materials[0].normal_texture.is_none());
+    assert_eq!(gltf.scenes[0].nodes, [0]);
+    assert!(gltf.sanitize().is_empty());
+}
+
+#[test]
+fn channels_that_cant_be_evaluated_are_dropped() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{}],
+            "accessors": [
+                {"componentType": 5126, "count": 1, "type": "SCALAR"},
+                {"componentType": 5126, "count": 1, "type": "VEC3"}
+            ],
+            "animations": [{
+                "samplers": [{"input": 0, "output": 1}, {"input": 0, "output": 2}],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 1, "path": "translation"}},
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}},
+                    {"sampler": 1, "target": {"node": 0, "path": "scale"}},
+                    {"sampler": 2, "target": {"node": 0, "path": "scale"}}
+                ]
+            }]
+        }"#,
+    );
+    let report = gltf.sanitize();
+
+    assert_eq!(
+        report.repairs,
+        [0, 2, 3].map(|channel| Repair::DroppedChannel {
+            animation: 0,
+            channel
+        })
+    );
+    assert_eq!(gltf.animations[0].channels.len(), 1);
+    assert_eq!(gltf.animations[0].channels[0].target.node, Some(0));
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn rotations_are_renormalized() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"rotation": [0, 0, 0, 2]}, {"rotation": [0, 0.6, 0, 0.8]}]
+        }"#,
+    );
+    let report = gltf.sanitize();
+
+    assert_eq!(report.repairs, [Repair::NormalizedRotation { node: 0 }]);
+    assert_eq!(gltf.nodes[0].rotation, Some([0.0, 0.0, 0.0, 1.0]));
+}
+
+#[test]
+fn morph_weights_are_resized_to_the_number_of_targets() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "accessors": [{"componentType": 5126, "count": 1, "type": "VEC3"}],
+            "meshes": [
+                {
+                    "primitives": [{"attributes": {}, "targets": [{"POSITION": 0}, {"POSITION": 0}]}],
+                    "weights": [0.5]
+                },
+                {"primitives": [{"attributes": {}}], "weights": [0.5, 0.25]}
+            ]
+        }"#,
+    );
+    let report = gltf.sanitize();
+
+    assert_eq!(
+        report.repairs,
+        [
+            Repair::ResizedMorphWeights {
+                mesh: 0,
+                from: 1,
+                to: 2
+            },
+            Repair::ResizedMorphWeights {
+                mesh: 1,
+                from: 2,
+                to: 0
+            },
+        ]
+    );
+    assert_eq!(gltf.meshes[0].weights.as_deref(), Some(&[0.5, 0.0][..]));
+    assert_eq!(gltf.meshes[1].weights.as_deref(), Some(&[][..]));
+}
+
+// JSON can't hold non-finite numbers, but they can come from edits or lossy conversions.
+#[test]
+fn non_finite_values_are_replaced_with_defaults() {
+    let mut gltf = parse(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"translation": [1, 2, 3], "scale": [2, 2, 2]}],
+            "materials": [{}]
+        }"#,
+    );
+    gltf.nodes[0].translation = Some([1.0, f32::NAN, 3.0]);
+    gltf.materials[0].pbr_metallic_roughness.roughness_factor = f32::INFINITY;
+    gltf.materials[0].emissive_factor = [0.5, 0.5, f32::NEG_INFINITY];
+
+    let report = gltf.sanitize();
+
+    let replaced = |location: &str| Repair::ReplacedNonFinite {
+        location: location.to_string(),
+    };
+    assert_eq!(
+        report.repairs,
+        [
+            replaced("materials[0].pbrMetallicRoughness.roughnessFactor"),
+            replaced("materials[0].emissiveFactor"),
+            replaced("nodes[0].translation"),
+        ]
+    );
+    assert_eq!(gltf.nodes[0].translation, Some([0.0; 3]));
+    assert_eq!(gltf.nodes[0].scale, Some([2.0; 3]));
+    assert_eq!(
+        gltf.materials[0].pbr_metallic_roughness.roughness_factor,
+        1.0
+    );
+    assert_eq!(gltf.materials[0].emissive_factor, [0.0; 3]);
+}
@@ -0,0 +1,91 @@
+use goth_gltf::{default_extensions::Extensions, scan::ScannedObject, Gltf};
+use std::borrow::Cow;
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0", "generator": "test"},
+    "nodes": [{"name": "root", "children": [1]}, {"mesh": 0}, {"name": "café \"1\" 😀"}],
+    "images": [{"uri": "textures/a.png"}, {"bufferView": 0, "mimeType": "image/png"}],
+    "extras": {"name": "not a node"}
+}"#;
+
+#[test]
+fn strings_without_escapes_are_borrowed() {
+    let document = ScannedObject::parse(JSON).unwrap();
+    let nodes = document.objects("nodes").unwrap();
+
+    assert_eq!(nodes.len(), 3);
+    assert!(matches!(nodes[0].name(), Some(Cow::Borrowed("root"))));
+    assert_eq!(nodes[1].name(), None);
+
+    let images = document.objects("images").unwrap();
+    assert!(matches!(
+        images[0].uri(),
+        Some(Cow::Borrowed("textures/a.png"))
+    ));
+    assert!(matches!(
+        images[1].mime_type(),
+        Some(Cow::Borrowed("image/png"))
+    ));
+
+    let asset = document.object("asset").unwrap();
+    assert!(matches!(
+        asset.str("generator"),
+        Some(Cow::Borrowed("test"))
+    ));
+}
+
+#[test]
+fn escaped_strings_are_read_the_same_as_when_deserializing() {
+    let document = ScannedObject::parse(JSON).unwrap();
+    let name = document.objects("nodes").unwrap()[2].name().unwrap();
+
+    assert!(matches!(name, Cow::Owned(_)));
+    assert_eq!(name, "café \"1\" 😀");
+
+    #[cfg(feature = "names")]
+    assert_eq!(
+        Gltf::<Extensions>::from_json_string(JSON).unwrap().nodes[2]
+            .name
+            .as_deref(),
+        Some(&*name)
+    );
+}
+
+#[test]
+fn members_of_the_wrong_type_are_none() {
+    let document = ScannedObject::parse(JSON).unwrap();
+    let nodes = document.objects("nodes").unwrap();
+
+    assert_eq!(nodes[0].str("children"), None);
+    assert_eq!(nodes[0].raw("children"), Some("[1]"));
+    assert!(nodes[0].objects("children").is_none());
+    assert!(document.object("nodes").is_none());
+    assert!(document.objects("skins").is_none());
+
+    for escaped in [r#"\x"#, r#"\u12"#, r#"\ud83d"#, r#"\ud83dA"#, r#"\ude00"#] {
+        let json = format!(r#"{{"name": "{}"}}"#, escaped);
+        assert_eq!(
+            ScannedObject::parse(&json).unwrap().name(),
+            None,
+            "{}",
+            escaped
+        );
+    }
+}
+
+#[test]
+fn malformed_documents_are_an_error() {
+    for json in ["", "[]", r#"{"nodes": [}"#, r#"{"nodes": []} {}"#] {
+        assert!(ScannedObject::parse(json).is_err(), "{}", json);
+    }
+}
+
+#[test]
+fn glbs_are_scanned_without_their_binary_chunk() {
+    let gltf = Gltf::<Extensions>::from_json_string(JSON).unwrap();
+    let glb = gltf.to_glb(None).unwrap();
+
+    let document = ScannedObject::from_bytes(&glb).unwrap();
+    let images = document.objects("images").unwrap();
+    assert_eq!(images[0].uri().as_deref(), Some("textures/a.png"));
+}
@@ -0,0 +1,235 @@
+use goth_gltf::scene::NodeGraphReport;
+use goth_gltf::{default_extensions::Extensions, Gltf};
+use std::fmt::Write;
+
+// Counts lines instead of storing them, as the indentation of a deep chain adds up.
+#[derive(Default)]
+struct LineCounter(usize);
+
+impl Write for LineCounter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0 += s.matches('\n').count();
+        Ok(())
+    }
+}
+
+#[test]
+fn deep_hierarchies_are_displayed_without_recursing() {
+    const DEPTH: usize = 5_000;
+
+    // Each node is the child of the one before it, and the last loops back to
+    // the first. An extra node outside the scene also points into the chain.
+    let nodes: Vec<String> = (0..DEPTH)
+        .map(|index| format!(r#"{{"children": [{}]}}"#, (index + 1) % DEPTH))
+        .collect();
+    let mut nodes = nodes.join(",");
+    nodes.push_str(r#",{"children": [2500]}"#);
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "scenes": [{{"nodes": [0]}}], "nodes": [{}]}}"#,
+        nodes
+    );
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(&json).unwrap();
+
+    // A small stack, so that recursing once per level would overflow it.
+    let lines = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            let mut counter = LineCounter::default();
+            write!(counter, "{}", gltf.display_hierarchy()).unwrap();
+            counter.0
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    // The scene and its chain, node 0 again at the bottom of it, then the extra node with
+    // node 2500 again under it.
+    assert_eq!(lines, 1 + DEPTH + 1 + 1 + 1 + 1);
+}
+
+#[test]
+fn the_default_scene_is_optional() {
+    let scenes = r#""scenes": [{"nodes": []}, {"nodes": [0]}], "nodes": [{}]"#;
+
+    let json = format!(r#"{{"asset": {{"version": "2.0"}}, {}}}"#, scenes);
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(&json).unwrap();
+    assert_eq!(gltf.scene, None);
+    assert!(gltf.default_scene().is_none());
+
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "scene": 1, {}}}"#,
+        scenes
+    );
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(&json).unwrap();
+    assert_eq!(gltf.scene, Some(1));
+    assert_eq!(gltf.default_scene().unwrap().nodes, [0]);
+
+    let json = format!(
+        r#"{{"asset": {{"version": "2.0"}}, "scene": 0, {}}}"#,
+        scenes
+    );
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(&json).unwrap();
+    assert_eq!(gltf.scene, Some(0));
+    assert!(gltf.default_scene().unwrap().nodes.is_empty());
+}
+
+#[test]
+fn an_out_of_range_default_scene_is_none() {
+    let json = r#"{"asset": {"version": "2.0"}, "scene": 3, "scenes": [{}]}"#;
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(json).unwrap();
+
+    assert!(gltf.default_scene().is_none());
+}
+
+#[test]
+fn the_default_scene_is_only_written_when_set() {
+    let json = r#"{"asset": {"version": "2.0"}, "scenes": [{}]}"#;
+    let mut gltf: Gltf<Extensions> = Gltf::from_json_string(json).unwrap();
+    assert!(!gltf.to_json_string().contains(r#""scene":"#));
+
+    gltf.scene = Some(0);
+    assert!(gltf.to_json_string().contains(r#""scene":0"#));
+}
+
+#[test]
+fn scene_nodes_accumulate_world_matrices() {
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scenes": [{"nodes": [0, 3, 9]}],
+            "nodes": [
+                {"children": [1], "translation": [1, 0, 0]},
+                {"children": [2, 9], "scale": [2, 2, 2]},
+                {"children": [0], "translation": [0, 1, 0]},
+                {"matrix": [1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 5, 1]}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let transform = |[x, y, z]: [f32; 3], scale: f32| {
+        [
+            scale, 0.0, 0.0, 0.0, 0.0, scale, 0.0, 0.0, 0.0, 0.0, scale, 0.0, x, y, z, 1.0,
+        ]
+    };
+
+    // Depth-first, with the cycle back to node 0 and the missing node 9 skipped.
+    let nodes: Vec<_> = gltf
+        .scene_nodes(0)
+        .unwrap()
+        .map(|node| (node.index, node.world_matrix))
+        .collect();
+    assert_eq!(
+        nodes,
+        [
+            (0, transform([1.0, 0.0, 0.0], 1.0)),
+            (1, transform([1.0, 0.0, 0.0], 2.0)),
+            (2, transform([1.0, 2.0, 0.0], 2.0)),
+            (3, transform([0.0, 0.0, 5.0], 1.0)),
+        ]
+    );
+
+    assert!(gltf.scene_nodes(1).is_none());
+}
+
+#[test]
+fn parents_are_looked_up_from_children() {
+    // Node 3 is the child of both 0 and 1, and 5 doesn't exist.
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"children": [1, 3]}, {"children": [2, 3, 5]}, {}, {}, {}]
+        }"#,
+    )
+    .unwrap();
+    let parents = gltf.node_parents();
+
+    assert_eq!(parents.parents, [None, Some(0), Some(1), Some(0), None]);
+    assert_eq!(parents.multiple_parents, [3]);
+    assert_eq!(parents.parent(2), Some(1));
+    assert_eq!(parents.parent(9), None);
+    assert_eq!(parents.ancestors(2).collect::<Vec<_>>(), [1, 0]);
+    assert_eq!(parents.ancestors(4).count(), 0);
+}
+
+#[test]
+fn the_node_graph_check_finds_cycles_and_bad_links() {
+    // 0 → 1 → 2 → 0 is a cycle, 3 is its own child, and 4 is shared by 3 and 5.
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [
+                {"children": [1]},
+                {"children": [2]},
+                {"children": [0, 7]},
+                {"children": [3, 4]},
+                {},
+                {"children": [4]}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        gltf.check_node_graph(),
+        NodeGraphReport {
+            cycles: vec![vec![0, 1, 2], vec![3]],
+            multiple_parents: vec![4],
+            out_of_bounds_children: vec![(2, 7)],
+        }
+    );
+    assert!(!gltf.check_node_graph().is_clean());
+
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{"asset": {"version": "2.0"}, "nodes": [{"children": [1, 2]}, {"children": [2]}, {}]}"#,
+    )
+    .unwrap();
+    // Node 2 is reached twice, but that's a second parent rather than a cycle.
+    assert_eq!(gltf.check_node_graph().cycles, Vec::<Vec<usize>>::new());
+    assert_eq!(gltf.check_node_graph().multiple_parents, [2]);
+}
+
+#[test]
+fn primitives_come_with_their_materials() {
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"mesh": 1, "children": [1]}, {"mesh": 1}],
+            "materials": [{"alphaCutoff": 0.25}],
+            "meshes": [
+                {"primitives": [{"attributes": {}, "material": 0}]},
+                {"primitives": [{"attributes": {}}, {"attributes": {}, "material": 3}]}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let primitives: Vec<_> = gltf.primitives().collect();
+    assert_eq!(
+        primitives
+            .iter()
+            .map(|primitive| (primitive.mesh, primitive.index, primitive.material.index()))
+            .collect::<Vec<_>>(),
+        [(0, 0, Some(0)), (1, 0, None), (1, 1, None)]
+    );
+    assert_eq!(primitives[0].material.alpha_cutoff, 0.25);
+    // Primitives without a material, or with one that doesn't exist, get the default material.
+    assert_eq!(primitives[1].material.alpha_cutoff, 0.5);
+    assert_eq!(
+        primitives[2]
+            .material
+            .pbr_metallic_roughness
+            .metallic_factor,
+        1.0
+    );
+
+    // The mesh is visited once for each node that uses it.
+    let nodes: Vec<_> = gltf
+        .scene_primitives(0)
+        .unwrap()
+        .map(|(node, primitive)| (node.index, primitive.mesh, primitive.index))
+        .collect();
+    assert_eq!(nodes, [(0, 1, 0), (0, 1, 1), (1, 1, 0), (1, 1, 1)]);
+    assert!(gltf.scene_primitives(1).is_none());
+}
@@ -0,0 +1,75 @@
+#![cfg(not(feature = "no_std"))]
+
+#[cfg(feature = "primitive_reader")]
+#[test]
+fn inverse_bind_matrices_are_read_or_default_to_identity() {
+    use goth_gltf::default_extensions::Extensions;
+    use goth_gltf::primitive_reader::SkinReader;
+    use goth_gltf::Gltf;
+    use std::collections::HashMap;
+
+    let identity = [
+        1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+    ];
+    let mut translated = identity;
+    translated[12..15].copy_from_slice(&[-1.0, -2.0, -3.0]);
+
+    let gltf: Gltf<Extensions> = Gltf::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{}, {}],
+            "buffers": [{"byteLength": 128}],
+            "bufferViews": [{"buffer": 0, "byteLength": 128}],
+            "accessors": [{"bufferView": 0, "componentType": 5126, "count": 2, "type": "MAT4"}],
+            "skins": [
+                {"joints": [0, 1], "inverseBindMatrices": 0},
+                {"joints": [0, 1]}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let buffer_views = HashMap::from([(
+        0,
+        [identity, translated]
+            .concat()
+            .iter()
+            .flat_map(|value: &f32| value.to_le_bytes())
+            .collect(),
+    )]);
+
+    let reader = SkinReader::new(&gltf, &gltf.skins[0], &buffer_views);
+    assert_eq!(
+        reader.read_inverse_bind_matrices().unwrap(),
+        [identity, translated]
+    );
+
+    let reader = SkinReader::new(&gltf, &gltf.skins[1], &buffer_views);
+    assert_eq!(
+        reader.read_inverse_bind_matrices().unwrap(),
+        [identity, identity]
+    );
+}
+
+#[test]
+fn weights_are_normalized() {
+    use goth_gltf::skinning::normalize_weights;
+
+    let mut weights = [
+        // Quantization drift.
+        [0.5, 0.25, 0.2, 0.0],
+        [f32::NAN, -1.0, 2.0, f32::INFINITY],
+        [0.0; 4],
+        [-0.5, 0.0, 0.0, 0.0],
+    ];
+    normalize_weights(&mut weights);
+
+    assert_eq!(
+        weights,
+        [
+            [0.5 / 0.95, 0.25 / 0.95, 0.2 / 0.95, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0; 4],
+            [0.0; 4],
+        ]
+    );
+}
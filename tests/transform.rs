@@ -0,0 +1,88 @@
+#![cfg(not(feature = "no_std"))]
+
+use goth_gltf::NodeTransform;
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (actual_value, expected_value) in actual.iter().zip(expected) {
+        assert!(
+            (actual_value - expected_value).abs() < 1e-5,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn transforms_round_trip_through_matrices() {
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    // A quarter turn around z.
+    let transform = NodeTransform::Set {
+        translation: [1.0, 2.0, 3.0],
+        rotation: [0.0, 0.0, half, half],
+        scale: [2.0, 3.0, 4.0],
+    };
+
+    let matrix = transform.to_matrix();
+    // Column-major, so x maps to y scaled by 2, and the translation is in the last column.
+    assert_close(
+        &matrix,
+        &[
+            0.0, 2.0, 0.0, 0.0, -3.0, 0.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 1.0, 2.0, 3.0, 1.0,
+        ],
+    );
+
+    let (translation, rotation, scale) = NodeTransform::Matrix(matrix).to_trs();
+    assert_close(&translation, &[1.0, 2.0, 3.0]);
+    assert_close(&rotation, &[0.0, 0.0, half, half]);
+    assert_close(&scale, &[2.0, 3.0, 4.0]);
+
+    match NodeTransform::Matrix(matrix).decomposed() {
+        NodeTransform::Set { translation, .. } => assert_close(&translation, &[1.0, 2.0, 3.0]),
+        other => panic!("expected a decomposed transform, got {:?}", other),
+    }
+}
+
+#[test]
+fn mirroring_is_folded_into_the_x_scale() {
+    // Mirrored along y.
+    let matrix = [
+        1.0, 0.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+    ];
+
+    let (_, rotation, scale) = NodeTransform::Matrix(matrix).to_trs();
+    assert_eq!(scale[0], -1.0);
+    assert_close(&[scale[1], scale[2]], &[1.0, 1.0]);
+
+    let rebuilt = NodeTransform::Set {
+        translation: [0.0; 3],
+        rotation,
+        scale,
+    };
+    assert_close(&rebuilt.to_matrix(), &matrix);
+}
+
+#[test]
+fn transforms_compose_parent_first() {
+    let parent = NodeTransform::Set {
+        translation: [1.0, 0.0, 0.0],
+        rotation: [0.0, 0.0, 0.0, 1.0],
+        scale: [2.0; 3],
+    };
+    let child = NodeTransform::Set {
+        translation: [0.0, 1.0, 0.0],
+        rotation: [0.0, 0.0, 0.0, 1.0],
+        scale: [1.0; 3],
+    };
+
+    // The child's translation is scaled by the parent's.
+    let (translation, _, scale) = (parent * child).to_trs();
+    assert_close(&translation, &[1.0, 2.0, 0.0]);
+    assert_close(&scale, &[2.0; 3]);
+
+    assert_eq!(
+        NodeTransform::default() * parent,
+        NodeTransform::Matrix(parent.to_matrix())
+    );
+}
@@ -0,0 +1,99 @@
+use goth_gltf::{default_extensions::Extensions, AnyExtensions, Gltf};
+
+const JSON: &str = r#"{
+    "asset": {"version": "2.0"},
+    "scenes": [{"nodes": [0, 1, 2]}],
+    "nodes": [
+        {"name": "first", "vendorData": "first"},
+        {"name": "second", "extensions": {"VENDOR_node": {"value": 2}}},
+        {"name": "third", "vendorData": "third"}
+    ],
+    "materials": [{"vendorData": 0}, {"vendorData": 1}],
+    "animations": [{
+        "samplers": [{"input": 0, "output": 1}],
+        "channels": [
+            {"sampler": 0, "target": {"node": 0, "path": "translation"}, "vendorData": "channel 0"},
+            {"sampler": 0, "target": {"node": 1, "path": "translation"}, "vendorData": "channel 1"}
+        ]
+    }]
+}"#;
+
+fn parse(json: &str) -> Gltf<Extensions> {
+    let mut gltf = Gltf::from_json_string(json).unwrap();
+    gltf.keep_unknown_json(json);
+    gltf
+}
+
+fn write(gltf: &Gltf<Extensions>) -> Gltf<AnyExtensions> {
+    Gltf::from_json_string(&gltf.to_json_string()).unwrap()
+}
+
+#[test]
+fn unknown_members_are_written_back() {
+    let gltf = parse(JSON);
+    let json = gltf.to_json_string();
+
+    assert!(write(&gltf).nodes[1].extensions.contains_key("VENDOR_node"));
+    assert!(json.contains(r#""vendorData":"first""#));
+    assert!(json.contains(r#""vendorData":"channel 0""#));
+}
+
+#[test]
+fn unknown_members_follow_their_objects_when_a_node_is_removed() {
+    let mut gltf = parse(JSON);
+    gltf.remove_node(0).unwrap();
+
+    let json = gltf.to_json_string();
+    let written = write(&gltf);
+
+    assert_eq!(written.nodes.len(), 2);
+    assert!(written.nodes[0].extensions.contains_key("VENDOR_node"));
+    assert!(!written.nodes[1].extensions.contains_key("VENDOR_node"));
+    // The first node's data went with it, and the third's moved down with it.
+    assert!(!json.contains(r#""vendorData":"first""#));
+    assert!(json.contains(r#""vendorData":"third""#));
+
+    // The channel that targeted the removed node was removed, and its data with it.
+    assert_eq!(written.animations[0].channels.len(), 1);
+    assert!(json.contains(r#""vendorData":"channel 1""#));
+    assert!(!json.contains(r#""vendorData":"channel 0""#));
+}
+
+#[test]
+fn unknown_members_follow_their_objects_when_a_material_is_removed() {
+    let mut gltf = parse(JSON);
+    gltf.remove_material(0).unwrap();
+
+    let json = gltf.to_json_string();
+
+    assert!(json.contains(r#""vendorData":1}]"#));
+    assert!(!json.contains(r#""vendorData":0"#));
+}
+
+#[test]
+fn empty_and_null_unknown_members_are_written_back_as_they_were() {
+    let json = r#"{
+        "asset": {"version": "2.0"},
+        "nodes": [{"vendorNull": null, "vendorArray": [], "vendorObject": {}}],
+        "vendorNested": [null, [], {}, [[null]], {"a": []}]
+    }"#;
+    let gltf = parse(json);
+    let written = gltf.to_json_string();
+
+    for member in [
+        r#""vendorNull":null"#,
+        r#""vendorArray":[]"#,
+        r#""vendorObject":{}"#,
+        r#""vendorNested":[null,[],{},[[null]],{"a":[]}]"#,
+    ] {
+        assert!(
+            written.contains(member),
+            "{} is missing from {}",
+            member,
+            written
+        );
+    }
+
+    // Writing it back out again doesn't change anything.
+    assert_eq!(parse(&written).to_json_string(), written);
+}
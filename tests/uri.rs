@@ -0,0 +1,131 @@
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::uri::{decode_data_uri, DataUri};
+#[cfg(not(feature = "no_std"))]
+use goth_gltf::uri::{resolve_uri, ResolveError};
+use goth_gltf::Gltf;
+#[cfg(not(feature = "no_std"))]
+use std::path::Path;
+
+#[test]
+fn data_uris_are_decoded() {
+    assert_eq!(
+        decode_data_uri("data:application/octet-stream;base64,AAECAwQ="),
+        Some(DataUri {
+            mime_type: "application/octet-stream",
+            data: vec![0, 1, 2, 3, 4],
+        })
+    );
+    // Unpadded and url-safe base64 are accepted too.
+    assert_eq!(
+        decode_data_uri("data:;base64,-_8").unwrap().data,
+        [0xfb, 0xff]
+    );
+    assert_eq!(decode_data_uri("data:;base64,").unwrap().data, []);
+
+    // Only base64 data uris are supported.
+    assert_eq!(decode_data_uri("data:text/plain,hello"), None);
+    assert_eq!(decode_data_uri("buffer.bin"), None);
+}
+
+#[test]
+fn buffers_and_images_have_embedded_data() {
+    let gltf = Gltf::<Extensions>::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [
+                {"byteLength": 3, "uri": "data:application/gltf-buffer;base64,AQID"},
+                {"byteLength": 3, "uri": "buffer.bin"}
+            ],
+            "images": [{"uri": "data:image/png;base64,iVBORw=="}]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(gltf.buffers[0].embedded_data(), Some(vec![1, 2, 3]));
+    assert_eq!(gltf.buffers[1].embedded_data(), None);
+
+    let image = gltf.images[0].embedded_data().unwrap();
+    assert_eq!(image.mime_type, "image/png");
+    assert_eq!(image.data, [0x89, b'P', b'N', b'G']);
+}
+
+#[test]
+fn malformed_base64_is_rejected() {
+    for uri in [
+        "data:;base64,AQ*D",
+        "data:;base64,AQ=D",
+        "data:;base64,AQ D",
+        // Truncated to a single character of the last group.
+        "data:;base64,AQIDB",
+        "data:;base64,AQID,AQID",
+        "data:;base64",
+    ] {
+        assert_eq!(decode_data_uri(uri), None, "{}", uri);
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn uris_are_resolved_within_the_root() {
+    let root = Path::new("/assets");
+    let base_dir = Path::new("/assets/models");
+    let resolve = |uri| resolve_uri(root, base_dir, uri);
+
+    assert_eq!(resolve("mesh.bin"), Ok("/assets/models/mesh.bin".into()));
+    assert_eq!(
+        resolve("./textures/my%20image.png?version=2#top"),
+        Ok("/assets/models/textures/my image.png".into())
+    );
+    assert_eq!(
+        resolve("../textures/a.png"),
+        Ok("/assets/textures/a.png".into())
+    );
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn uris_escaping_the_root_are_rejected() {
+    let root = Path::new("/assets");
+    let base_dir = Path::new("/assets/models");
+    let resolve = |uri| resolve_uri(root, base_dir, uri);
+
+    assert_eq!(resolve("../../etc/passwd"), Err(ResolveError::OutsideRoot));
+    assert_eq!(
+        resolve("textures/../../../etc/passwd"),
+        Err(ResolveError::OutsideRoot)
+    );
+    // Percent-encoded dots are decoded before the path is normalized.
+    assert_eq!(
+        resolve("%2e%2e/%2E%2E/etc/passwd"),
+        Err(ResolveError::OutsideRoot)
+    );
+    assert_eq!(
+        resolve("..%2F..%2Fetc/passwd"),
+        Err(ResolveError::OutsideRoot)
+    );
+
+    assert_eq!(resolve("/etc/passwd"), Err(ResolveError::Absolute));
+    assert_eq!(resolve("%2Fetc/passwd"), Err(ResolveError::Absolute));
+    assert_eq!(resolve("\\\\server\\share"), Err(ResolveError::Absolute));
+
+    assert_eq!(
+        resolve("file:///etc/passwd"),
+        Err(ResolveError::NotRelative)
+    );
+    assert_eq!(
+        resolve("https://example.com/mesh.bin"),
+        Err(ResolveError::NotRelative)
+    );
+    assert_eq!(resolve("data:;base64,AQID"), Err(ResolveError::NotRelative));
+    assert_eq!(
+        resolve("C:/Windows/win.ini"),
+        Err(ResolveError::NotRelative)
+    );
+
+    assert_eq!(resolve("mesh%zz.bin"), Err(ResolveError::InvalidEncoding));
+    assert_eq!(
+        resolve("mesh.bin%00.png"),
+        Err(ResolveError::InvalidEncoding)
+    );
+    assert_eq!(resolve("%ff.bin"), Err(ResolveError::InvalidEncoding));
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "wgpu-types")]
+
+use goth_gltf::default_extensions::Extensions;
+use goth_gltf::wgpu::{vertex_format, UnsupportedVertexFormat};
+use goth_gltf::{AccessorType, ComponentType, Gltf, PrimitiveMode};
+use wgpu_types::{AddressMode, FilterMode, MipmapFilterMode, PrimitiveTopology, VertexFormat};
+
+#[test]
+fn accessor_formats_map_to_vertex_formats() {
+    let cases = [
+        (
+            ComponentType::Float,
+            AccessorType::Vec3,
+            false,
+            VertexFormat::Float32x3,
+        ),
+        (
+            ComponentType::Float,
+            AccessorType::Scalar,
+            false,
+            VertexFormat::Float32,
+        ),
+        (
+            ComponentType::UnsignedByte,
+            AccessorType::Vec4,
+            true,
+            VertexFormat::Unorm8x4,
+        ),
+        (
+            ComponentType::Byte,
+            AccessorType::Vec2,
+            true,
+            VertexFormat::Snorm8x2,
+        ),
+        (
+            ComponentType::UnsignedShort,
+            AccessorType::Vec4,
+            false,
+            VertexFormat::Uint16x4,
+        ),
+        (
+            ComponentType::Short,
+            AccessorType::Vec4,
+            true,
+            VertexFormat::Snorm16x4,
+        ),
+        (
+            ComponentType::UnsignedInt,
+            AccessorType::Vec3,
+            false,
+            VertexFormat::Uint32x3,
+        ),
+    ];
+
+    for (component_type, accessor_type, normalized, format) in cases {
+        assert_eq!(
+            vertex_format(component_type, &accessor_type, normalized),
+            Ok(format)
+        );
+    }
+}
+
+#[test]
+fn unrepresentable_formats_are_reported() {
+    let cases = [
+        // No 3-component 8 or 16-bit formats.
+        (ComponentType::Byte, AccessorType::Vec3, true),
+        (ComponentType::UnsignedShort, AccessorType::Vec3, false),
+        (ComponentType::Float, AccessorType::Mat4, false),
+        (ComponentType::Float, AccessorType::Scalar, true),
+        (ComponentType::Unknown(1234), AccessorType::Vec2, false),
+    ];
+
+    for (component_type, accessor_type, normalized) in cases {
+        assert_eq!(
+            vertex_format(component_type, &accessor_type, normalized),
+            Err(UnsupportedVertexFormat {
+                component_type,
+                components: accessor_type.num_components(),
+                normalized,
+            })
+        );
+    }
+
+    assert_eq!(
+        vertex_format(ComponentType::Byte, &AccessorType::Vec3, true)
+            .unwrap_err()
+            .to_string(),
+        "no vertex format for 3 components of Byte (normalized)"
+    );
+}
+
+#[test]
+fn only_list_and_strip_modes_have_topologies() {
+    assert_eq!(
+        PrimitiveMode::Triangles.wgpu_topology(),
+        Some(PrimitiveTopology::TriangleList)
+    );
+    assert_eq!(
+        PrimitiveMode::TriangleStrip.wgpu_topology(),
+        Some(PrimitiveTopology::TriangleStrip)
+    );
+    assert_eq!(
+        PrimitiveMode::LineStrip.wgpu_topology(),
+        Some(PrimitiveTopology::LineStrip)
+    );
+    assert_eq!(
+        PrimitiveMode::Points.wgpu_topology(),
+        Some(PrimitiveTopology::PointList)
+    );
+    assert_eq!(PrimitiveMode::TriangleFan.wgpu_topology(), None);
+    assert_eq!(PrimitiveMode::LineLoop.wgpu_topology(), None);
+}
+
+#[test]
+fn samplers_map_to_descriptors() {
+    let gltf = Gltf::<Extensions>::from_json_string(
+        r#"{
+            "asset": {"version": "2.0"},
+            "samplers": [
+                {},
+                {"magFilter": 9728, "minFilter": 9985, "wrapS": 33071, "wrapT": 33648},
+                {"minFilter": 9728}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let default = gltf.samplers[0].wgpu_descriptor(Some("default"));
+    assert_eq!(default.label, Some("default"));
+    assert_eq!(default.address_mode_u, AddressMode::Repeat);
+    assert_eq!(default.address_mode_v, AddressMode::Repeat);
+    assert_eq!(default.mag_filter, FilterMode::Linear);
+    assert_eq!(default.min_filter, FilterMode::Linear);
+    assert_eq!(default.mipmap_filter, MipmapFilterMode::Linear);
+
+    // LINEAR_MIPMAP_NEAREST
+    let sampler = gltf.samplers[1].wgpu_descriptor(None);
+    assert_eq!(sampler.address_mode_u, AddressMode::ClampToEdge);
+    assert_eq!(sampler.address_mode_v, AddressMode::MirrorRepeat);
+    assert_eq!(sampler.mag_filter, FilterMode::Nearest);
+    assert_eq!(sampler.min_filter, FilterMode::Linear);
+    assert_eq!(sampler.mipmap_filter, MipmapFilterMode::Nearest);
+    assert!(sampler.lod_max_clamp > 0.0);
+
+    // Without mipmaps only the base level is used.
+    let sampler = gltf.samplers[2].wgpu_descriptor(None);
+    assert_eq!(sampler.min_filter, FilterMode::Nearest);
+    assert_eq!(sampler.lod_max_clamp, 0.0);
+}
@@ -0,0 +1,54 @@
+use goth_gltf::{default_extensions::Extensions, AnyExtensions, Gltf};
+
+#[test]
+fn empty_core_arrays_are_left_out() {
+    let json = r#"{"asset": {"version": "2.0"}, "nodes": [{"children": []}], "scenes": [{}]}"#;
+    let written = Gltf::<Extensions>::from_json_string(json)
+        .unwrap()
+        .to_json_string();
+
+    assert_eq!(
+        written,
+        r#"{"asset":{"version":"2.0"},"nodes":[{}],"scenes":[{}]}"#
+    );
+}
+
+#[test]
+fn extension_data_is_written_unchanged() {
+    let json = r#"{
+        "asset": {"version": "2.0"},
+        "nodes": [{"extensions": {"VENDOR_node": {
+            "nodes": [],
+            "value": null,
+            "extensions": {"VENDOR_nested": {"children": []}}
+        }}}]
+    }"#;
+
+    let written = Gltf::<AnyExtensions>::from_json_string(json)
+        .unwrap()
+        .to_json_string();
+
+    // The members of extension objects aren't kept in order.
+    assert!(written.contains(r#""nodes":[]"#));
+    assert!(written.contains(r#""value":null"#));
+    assert!(written.contains(r#""VENDOR_nested":{"children":[]}"#));
+
+    let node = &Gltf::<AnyExtensions>::from_json_string(&written)
+        .unwrap()
+        .nodes[0];
+    let reparsed = &Gltf::<AnyExtensions>::from_json_string(json).unwrap().nodes[0];
+    assert_eq!(
+        node.extensions.get("VENDOR_node"),
+        reparsed.extensions.get("VENDOR_node")
+    );
+}
+
+#[test]
+fn empty_extensions_and_extras_are_left_out() {
+    let json = r#"{"asset":{"version":"2.0","extensions":{},"extras":null},"nodes":[{"extensions":{},"extras":{}}]}"#;
+    let written = Gltf::<AnyExtensions>::from_json_string(json)
+        .unwrap()
+        .to_json_string();
+
+    assert_eq!(written, r#"{"asset":{"version":"2.0"},"nodes":[{}]}"#);
+}